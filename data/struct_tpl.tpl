@@ -1,4 +1,5 @@
-type {{struct_name}} struct {
-    Id int64 `gorm:"column:id;type:bigint(20);comment:主键" json:"id" form:"id"`
-    {{field_arr}}
+type {{ struct_name }} struct {
+{%- for field in field_arr %}
+	{{ to_big_case_camel_helper(word=field.name) }} {{ transfer_type_helper(column=field) }} `gorm:"column:{{ field.name }}" json:"{{ to_small_case_camel_helper(word=field.name) }}" form:"{{ to_small_case_camel_helper(word=field.name) }}"`
+{%- endfor %}
 }
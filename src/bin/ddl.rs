@@ -0,0 +1,107 @@
+//! MySQL 建表语句的格式化、检查工具
+//!
+//! ```text
+//! ddl fmt schema.sql
+//! ddl lint --json schema.sql other.sql
+//! ```
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+use my_parser::format_parse_error;
+use my_parser::sql1::lint::{lint_sql, LintIssue, LintSetting, LintSeverityEnum};
+use my_parser::sql1::parse_schema_file;
+use serde::Serialize;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "ddl", about = "格式化、检查 MySQL 建表语句")]
+enum Command {
+    /// 将文件中的建表语句输出为统一的格式，其余语句按原来的顺序原样输出
+    Fmt {
+        /// sql 文件
+        #[structopt(parse(from_os_str))]
+        file: PathBuf,
+    },
+    /// 检查文件中的建表语句，存在 error 级别的问题时以 1 退出
+    Lint {
+        /// 以 json 格式输出，每行一个问题
+        #[structopt(long)]
+        json: bool,
+        /// varchar 允许的最大长度
+        #[structopt(long, default_value = "1024")]
+        max_varchar_len: u32,
+        /// sql 文件
+        #[structopt(parse(from_os_str), required = true)]
+        files: Vec<PathBuf>,
+    },
+}
+
+/// json 输出中的一个问题，带上所在的文件
+#[derive(Serialize)]
+struct FileIssue<'a> {
+    file: String,
+    #[serde(flatten)]
+    issue: &'a LintIssue,
+}
+
+fn read_file(file: &PathBuf) -> String {
+    match fs::read_to_string(file) {
+        Ok(content) => content,
+        Err(err) => {
+            eprintln!("{}: {}", file.display(), err);
+            process::exit(2);
+        }
+    }
+}
+
+fn main() {
+    match Command::from_args() {
+        Command::Fmt { file } => {
+            let input = read_file(&file);
+            match parse_schema_file(&input) {
+                Ok((_, schema)) => println!("{}", schema.to_sql_with_skipped()),
+                Err(err) => {
+                    eprintln!("{}: {}", file.display(), format_parse_error(&input, &err));
+                    process::exit(2);
+                }
+            }
+        }
+        Command::Lint {
+            json,
+            max_varchar_len,
+            files,
+        } => {
+            let setting = LintSetting {
+                max_varchar_len,
+                ..Default::default()
+            };
+            let mut has_error = false;
+            for file in files.iter() {
+                let input = read_file(file);
+                let issue_arr = match lint_sql(&input, &setting) {
+                    Ok(issue_arr) => issue_arr,
+                    Err(err) => {
+                        eprintln!("{}: {}", file.display(), err);
+                        process::exit(2);
+                    }
+                };
+                for issue in issue_arr.iter() {
+                    has_error |= issue.severity == LintSeverityEnum::Error;
+                    if json {
+                        let file_issue = FileIssue {
+                            file: file.display().to_string(),
+                            issue,
+                        };
+                        println!("{}", serde_json::to_string(&file_issue).unwrap());
+                    } else {
+                        println!("{}: {}", file.display(), issue);
+                    }
+                }
+            }
+            if has_error {
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -12,6 +12,8 @@ mod redis;
 pub mod sql1;
 pub mod sql2_render;
 
+pub use sql1::{format_parse_error, parse_create_sql, TableSchema};
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 struct Element {
     name: String,
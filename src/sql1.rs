@@ -93,21 +93,30 @@ column_definition: {
 */
 
 //! 使用 nom 解析 MySQL 的建表语句
-use crate::parse_string::{
-    parse_str_with_escaped_and_combine, parse_str_with_escaped_and_combine_in_single_quote,
-};
 use nom::{
     branch::alt,
-    bytes::complete::{tag, tag_no_case, take_while1},
-    character::complete::u32 as nom_u32,
-    character::complete::{alphanumeric1, char as nom_char, multispace0},
-    character::complete::{space0, space1},
-    combinator::opt,
+    bytes::complete::{tag, tag_no_case, take_until, take_while1},
+    character::complete::{alphanumeric1, char as nom_char, multispace0, multispace1},
+    character::complete::{digit0, digit1, not_line_ending, one_of, space0, space1},
+    character::complete::{i64 as nom_i64, u32 as nom_u32, u64 as nom_u64},
+    combinator::{cut, map, not, opt, peek, recognize},
+    error::{context, ErrorKind, ParseError, VerboseError, VerboseErrorKind},
     multi::{fold_many1, many0, many1, many_m_n},
     sequence::{preceded, tuple},
-    IResult,
+    Err,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub mod alter;
+pub mod ddl;
+pub mod diff;
+pub mod lint;
+pub mod postgres;
+pub mod sqlite;
+
+/// sql1 中的解析器统一使用 `VerboseError`，以便在解析失败时给出上下文信息
+pub type IResult<I, O> = nom::IResult<I, O, VerboseError<I>>;
 
 /// 解析 default 部分
 // not null default 1
@@ -115,14 +124,10 @@ use serde::{Deserialize, Serialize};
 // [dataType] default null
 // [dataType] default AUTO_INCREMENT
 pub fn parse_column_definition_of_default(input: &str) -> IResult<&str, DefaultEnum> {
-    match tuple((opt(parse_column_definition_of_not_null), opt(parse_default)))(input) {
-        Ok((remain, (_not_null, default_val))) => {
-            // 有可能没有 not null 而只有 default ''
-            if default_val.is_some() {
-                Ok((remain, DefaultEnum::DefaultNull))
-            } else {
-                Ok((remain, DefaultEnum::DefaultNone))
-            }
+    match tuple((opt(parse_column_nullable), opt(parse_default)))(input) {
+        // 有可能没有 not null 而只有 default ''
+        Ok((remain, (_nullable, default_val))) => {
+            Ok((remain, default_val.unwrap_or(DefaultEnum::DefaultNone)))
         }
         Err(err) => Err(err),
     }
@@ -135,6 +140,19 @@ pub fn parse_column_definition_of_null(input: &str) -> IResult<&str, String> {
     }
 }
 
+/// 解析 `null` 或 `not null`，返回字段是否允许为 NULL
+pub fn parse_column_nullable(input: &str) -> IResult<&str, bool> {
+    let null_val = map(preceded(space1, sql_keyword("null")), |_| true);
+    match alt((
+        map(parse_column_definition_of_not_null, |_| false),
+        null_val,
+    ))(input)
+    {
+        Ok((remain, nullable)) => Ok((remain, nullable)),
+        Err(err) => Err(err),
+    }
+}
+
 // 解析 not null
 pub fn parse_column_definition_of_not_null(input: &str) -> IResult<&str, String> {
     match tuple((space1, tag_no_case("not"), space1, tag_no_case("null")))(input) {
@@ -167,34 +185,167 @@ pub fn sql_identifier(input: &str) -> IResult<&str, String> {
     }
 }
 
-// 处理类型
-#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// 字段的数据类型
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DataTypeEnum {
-    TinyInt,
-    SmallInt,
-    Int,
-    Bigint,
+    /// tinyint
+    TinyInt(IntTypeOption),
+    /// smallint
+    SmallInt(IntTypeOption),
+    /// mediumint
+    MediumInt(IntTypeOption),
+    /// int、integer
+    Int(IntTypeOption),
+    /// bigint
+    Bigint(IntTypeOption),
+    /// bit(M)，M 默认为 1
+    Bit(u32),
+    /// bool、boolean，MySQL 中是 tinyint(1) 的别名
+    Bool,
+    /// float、float(p)、float(M,D)
+    Float(DecimalTypeOption),
+    /// double、real、double(M,D)
+    Double(DecimalTypeOption),
+    /// char(M)，M 默认为 1
+    Char(u32),
+    /// varchar(M)
     VarChar(u32),
-    DateTime(u32),
+    /// binary(M)，M 默认为 1
+    Binary(u32),
+    /// varbinary(M)
+    VarBinary(u32),
+    /// tinytext
+    TinyText,
+    /// text
     Text,
+    /// mediumtext
+    MediumText,
+    /// longtext
+    LongText,
+    /// bigtext
     BigText,
-    Decimal(u8),
+    /// tinyblob
+    TinyBlob,
+    /// blob
+    Blob,
+    /// mediumblob
+    MediumBlob,
+    /// longblob
+    LongBlob,
+    /// date
+    Date,
+    /// time(fsp)，fsp 为秒的小数位数
+    Time(u32),
+    /// datetime(fsp)，fsp 为秒的小数位数
+    DateTime(u32),
+    /// timestamp(fsp)，fsp 为秒的小数位数
+    Timestamp(u32),
+    /// year
+    Year,
+    /// json
+    Json,
+    /// enum('a','b')，包含所有的枚举值
+    Enum(Vec<String>),
+    /// set('a','b')，包含所有的可选值
+    Set(Vec<String>),
+    /// geometry
+    Geometry,
+    /// point
+    Point,
+    /// linestring
+    LineString,
+    /// polygon
+    Polygon,
+    /// multipoint
+    MultiPoint,
+    /// multilinestring
+    MultiLineString,
+    /// multipolygon
+    MultiPolygon,
+    /// geometrycollection
+    GeometryCollection,
+    /// decimal(M,D)、numeric(M,D)
+    Decimal(DecimalTypeOption),
+    /// PostgreSQL 的 uuid
+    Uuid,
+    /// PostgreSQL 的 jsonb
+    Jsonb,
+    /// PostgreSQL 的 timestamptz、timestamp with time zone，包含小数秒精度
+    TimestampTz(u32),
+    /// PostgreSQL 的数组类型，如：`text[]`、`integer[][]`
+    Array(Box<DataTypeEnum>),
+    /// 其他可识别但没有专门对应的类型，如：PostgreSQL 的 inet、自定义的枚举类型等，保存类型名
+    Custom(String),
+    /// 无法识别的类型
     Unknown,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+/// 整数类型的附加属性，如：`int(11) unsigned zerofill`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntTypeOption {
+    /// 显示宽度，如 `int(11)` 中的 11
+    pub width: Option<u32>,
+    /// 是否无符号
+    pub unsigned: bool,
+    /// 是否用 0 填充显示宽度
+    pub zerofill: bool,
+}
+
+/// 定点数、浮点数类型的附加属性，如：`decimal(10,2) unsigned`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecimalTypeOption {
+    /// 精度，即总的有效位数，如 `decimal(10,2)` 中的 10
+    pub precision: Option<u32>,
+    /// 小数位数，如 `decimal(10,2)` 中的 2
+    pub scale: Option<u32>,
+    /// 是否无符号
+    pub unsigned: bool,
+    /// 是否用 0 填充显示宽度
+    pub zerofill: bool,
+}
+
+/// 字段的默认值
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DefaultEnum {
-    DefaultNone, // 没有 default 语句
+    /// 没有 default 语句
+    DefaultNone,
+    /// default null
     DefaultNull,
-    DefaultInt(u32),
+    /// 整数默认值，如：`default -1`
+    DefaultInt(i64),
+    /// 浮点数默认值，如：`default 0.5`，保留原始文本
+    DefaultFloat(String),
+    /// 字符串默认值，如：`default 'abc'`
     DefaultStr(String),
+    /// 布尔默认值，如：`default true`
+    DefaultBool(bool),
+    /// 表达式默认值，如：`default (uuid())` 中的 `uuid()`、`default b'0'`
+    DefaultExpr(String),
+    /// default current_timestamp
     DefaultCurStamp,
+    /// auto_increment
     DefaultAutoIncrement,
+    /// default current_timestamp on update current_timestamp
     DefaultCurStampOnUpdateCurStamp,
+    /// on update current_timestamp
     DefaultOnUpdateCurStamp,
+    /// 无法识别的默认值
     Unknown,
 }
 
+/// 解析一个关键字，关键字后面不能紧跟标识符字符，避免 `int` 匹配到 `integer` 的前半部分
+pub fn sql_keyword<'a>(keyword: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, &'a str> {
+    move |input: &'a str| match tag_no_case(keyword)(input) {
+        Ok((remain, word)) => match remain.chars().next() {
+            Some(c) if c.is_alphanumeric() || c == '_' => Err(Err::Error(
+                VerboseError::from_error_kind(input, ErrorKind::Tag),
+            )),
+            _ => Ok((remain, word)),
+        },
+        Err(err) => Err(err),
+    }
+}
+
 pub fn type_int_size(input: &str) -> IResult<&str, u32> {
     match tuple((tag("("), nom_u32, tag(")")))(input) {
         Ok((remain, (_, int_size, _))) => Ok((remain, int_size)),
@@ -203,8 +354,15 @@ pub fn type_int_size(input: &str) -> IResult<&str, u32> {
 }
 
 pub fn type_tiny_int(input: &str) -> IResult<&str, DataTypeEnum> {
-    match tuple((tag_no_case("tinyint"), opt(type_int_size)))(input) {
-        Ok((remain, (_, _))) => Ok((remain, DataTypeEnum::TinyInt)),
+    match tuple((sql_keyword("tinyint"), opt(type_int_size), type_number_attr))(input) {
+        Ok((remain, (_, width, (unsigned, zerofill)))) => Ok((
+            remain,
+            DataTypeEnum::TinyInt(IntTypeOption {
+                width,
+                unsigned,
+                zerofill,
+            }),
+        )),
         Err(err) => Err(err),
     }
 }
@@ -223,24 +381,72 @@ pub fn parse_int_is_unsigned(input: &str) -> IResult<&str, Option<i8>> {
     }
 }
 
+/// 解析数值类型后面的 `unsigned`、`signed`、`zerofill`，返回 (是否无符号, 是否 zerofill)
+/// zerofill 的字段，MySQL 会自动加上 unsigned
+pub fn type_number_attr(input: &str) -> IResult<&str, (bool, bool)> {
+    let one_attr = preceded(
+        space1,
+        alt((
+            sql_keyword("unsigned"),
+            sql_keyword("signed"),
+            sql_keyword("zerofill"),
+        )),
+    );
+    match many0(one_attr)(input) {
+        Ok((remain, attr_arr)) => {
+            let mut unsigned = false;
+            let mut zerofill = false;
+            for attr in attr_arr {
+                match attr.to_lowercase().as_str() {
+                    "unsigned" => unsigned = true,
+                    "zerofill" => {
+                        unsigned = true;
+                        zerofill = true;
+                    }
+                    _ => {}
+                }
+            }
+            Ok((remain, (unsigned, zerofill)))
+        }
+        Err(err) => Err(err),
+    }
+}
+
 pub fn type_some_int(input: &str) -> IResult<&str, DataTypeEnum> {
     match tuple((
         alt((
-            tag_no_case("int"),
-            tag_no_case("bigint"),
-            tag_no_case("smallint"),
-            tag_no_case("tinyint"),
+            sql_keyword("tinyint"),
+            sql_keyword("smallint"),
+            sql_keyword("mediumint"),
+            sql_keyword("middleint"),
+            sql_keyword("bigint"),
+            sql_keyword("integer"),
+            sql_keyword("int"),
+            sql_keyword("int1"),
+            sql_keyword("int2"),
+            sql_keyword("int3"),
+            sql_keyword("int4"),
+            sql_keyword("int8"),
+            sql_keyword("serial"),
         )),
         opt(type_int_size),
-        opt(parse_int_is_unsigned),
+        type_number_attr,
     ))(input)
     {
-        Ok((remain, (flag, _, _))) => {
-            let parse_res = match flag {
-                "int" => DataTypeEnum::Int,
-                "bigint" => DataTypeEnum::Bigint,
-                "smallint" => DataTypeEnum::SmallInt,
-                "tinyint" => DataTypeEnum::TinyInt,
+        Ok((remain, (flag, width, (unsigned, zerofill)))) => {
+            let flag = flag.to_lowercase();
+            let int_option = IntTypeOption {
+                width,
+                // serial 是 bigint unsigned not null auto_increment unique 的别名
+                unsigned: unsigned || flag == "serial",
+                zerofill,
+            };
+            let parse_res = match flag.as_str() {
+                "int" | "integer" | "int4" => DataTypeEnum::Int(int_option),
+                "bigint" | "int8" | "serial" => DataTypeEnum::Bigint(int_option),
+                "mediumint" | "middleint" | "int3" => DataTypeEnum::MediumInt(int_option),
+                "smallint" | "int2" => DataTypeEnum::SmallInt(int_option),
+                "tinyint" | "int1" => DataTypeEnum::TinyInt(int_option),
                 _ => DataTypeEnum::Unknown,
             };
             Ok((remain, parse_res))
@@ -249,6 +455,70 @@ pub fn type_some_int(input: &str) -> IResult<&str, DataTypeEnum> {
     }
 }
 
+/// 解析 bit(M)，M 默认为 1
+pub fn type_bit(input: &str) -> IResult<&str, DataTypeEnum> {
+    match tuple((sql_keyword("bit"), opt(type_int_size)))(input) {
+        Ok((remain, (_, size))) => Ok((remain, DataTypeEnum::Bit(size.unwrap_or(1)))),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析 bool、boolean
+pub fn type_bool(input: &str) -> IResult<&str, DataTypeEnum> {
+    match alt((sql_keyword("boolean"), sql_keyword("bool")))(input) {
+        Ok((remain, _)) => Ok((remain, DataTypeEnum::Bool)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析浮点数类型的精度部分，如：`(10)`、`(10,2)`
+pub fn type_float_size(input: &str) -> IResult<&str, (u32, Option<u32>)> {
+    let mut parser = tuple((
+        tag("("),
+        space0,
+        nom_u32,
+        opt(tuple((space0, tag(","), space0, nom_u32))),
+        space0,
+        tag(")"),
+    ));
+    match parser(input) {
+        Ok((remain, (_, _, size, scale_op, _, _))) => {
+            Ok((remain, (size, scale_op.map(|(_, _, _, scale)| scale))))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析浮点数类型：float、double、double precision、real
+pub fn type_float(input: &str) -> IResult<&str, DataTypeEnum> {
+    let double_precision = tuple((sql_keyword("double"), space1, sql_keyword("precision")));
+    match tuple((
+        alt((
+            map(double_precision, |(double, _, _)| double),
+            sql_keyword("double"),
+            sql_keyword("float"),
+            sql_keyword("real"),
+        )),
+        opt(type_float_size),
+        type_number_attr,
+    ))(input)
+    {
+        Ok((remain, (flag, size, (unsigned, zerofill)))) => {
+            let decimal_option = DecimalTypeOption {
+                precision: size.map(|(precision, _)| precision),
+                scale: size.and_then(|(_, scale)| scale),
+                unsigned,
+                zerofill,
+            };
+            match flag.to_lowercase().as_str() {
+                "float" => Ok((remain, DataTypeEnum::Float(decimal_option))),
+                _ => Ok((remain, DataTypeEnum::Double(decimal_option))),
+            }
+        }
+        Err(err) => Err(err),
+    }
+}
+
 pub fn type_collate(input: &str) -> IResult<&str, String> {
     match tuple((space1, tag_no_case("collate"), space1, sql_identifier))(input) {
         Ok((remain, (_, _, _, collate_name))) => Ok((remain, collate_name)),
@@ -256,86 +526,439 @@ pub fn type_collate(input: &str) -> IResult<&str, String> {
     }
 }
 
+/// 解析字段的字符集，如：`CHARACTER SET utf8mb4`、`CHARSET utf8mb4`
+pub fn type_charset(input: &str) -> IResult<&str, String> {
+    let charset_keyword = alt((
+        map(
+            tuple((sql_keyword("character"), space1, sql_keyword("set"))),
+            |_| (),
+        ),
+        map(sql_keyword("charset"), |_| ()),
+    ));
+    match tuple((space1, charset_keyword, space1, sql_identifier))(input) {
+        Ok((remain, (_, _, _, charset_name))) => Ok((remain, charset_name)),
+        Err(err) => Err(err),
+    }
+}
+
 // `user_name` varchar(50) COLLATE utf8mb4_bin DEFAULT NULL COMMENT '用户名',
 pub fn type_varchar(input: &str) -> IResult<&str, DataTypeEnum> {
-    match tuple((tag_no_case("varchar"), type_int_size, opt(type_collate)))(input) {
-        // 暂时忽略字符集排序
-        Ok((remain, (_, size, _))) => Ok((remain, DataTypeEnum::VarChar(size))),
+    let varchar = alt((
+        sql_keyword("varchar"),
+        sql_keyword("nvarchar"),
+        map(
+            tuple((sql_keyword("character"), space1, sql_keyword("varying"))),
+            |(word, _, _)| word,
+        ),
+        map(
+            tuple((sql_keyword("national"), space1, sql_keyword("varchar"))),
+            |(word, _, _)| word,
+        ),
+    ));
+    match tuple((varchar, type_int_size))(input) {
+        Ok((remain, (_, size))) => Ok((remain, DataTypeEnum::VarChar(size))),
         Err(err) => Err(err),
     }
 }
 
+/// 解析定长字符串 char(M)，M 默认为 1
+pub fn type_char(input: &str) -> IResult<&str, DataTypeEnum> {
+    let char_keyword = alt((
+        sql_keyword("char"),
+        sql_keyword("nchar"),
+        sql_keyword("character"),
+        map(
+            tuple((sql_keyword("national"), space1, sql_keyword("char"))),
+            |(word, _, _)| word,
+        ),
+    ));
+    match tuple((char_keyword, opt(type_int_size)))(input) {
+        Ok((remain, (_, size))) => Ok((remain, DataTypeEnum::Char(size.unwrap_or(1)))),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析二进制字符串 binary(M)、varbinary(M)
+pub fn type_binary(input: &str) -> IResult<&str, DataTypeEnum> {
+    let varbinary = map(
+        tuple((sql_keyword("varbinary"), type_int_size)),
+        |(_, size)| DataTypeEnum::VarBinary(size),
+    );
+    let binary = map(
+        tuple((sql_keyword("binary"), opt(type_int_size))),
+        |(_, size)| DataTypeEnum::Binary(size.unwrap_or(1)),
+    );
+    alt((varbinary, binary))(input)
+}
+
 // datetime(3) DEFAULT NULL COMMENT '创建时间',
 pub fn type_datetime(input: &str) -> IResult<&str, DataTypeEnum> {
-    match tuple((tag_no_case("datetime"), opt(type_int_size)))(input) {
+    match tuple((sql_keyword("datetime"), opt(type_int_size)))(input) {
         Ok((remain, (_, size_info))) => {
-            if size_info.is_some() {
-                Ok((remain, DataTypeEnum::DateTime(size_info.unwrap())))
-            } else {
-                Ok((remain, DataTypeEnum::DateTime(0)))
-            }
+            Ok((remain, DataTypeEnum::DateTime(size_info.unwrap_or(0))))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析日期时间相关的类型：date、time(fsp)、timestamp(fsp)、year
+pub fn type_date_and_time(input: &str) -> IResult<&str, DataTypeEnum> {
+    match tuple((
+        alt((
+            sql_keyword("date"),
+            sql_keyword("timestamp"),
+            sql_keyword("time"),
+            sql_keyword("year"),
+        )),
+        opt(type_int_size),
+    ))(input)
+    {
+        Ok((remain, (flag, size_info))) => {
+            let size = size_info.unwrap_or(0);
+            let parse_res = match flag.to_lowercase().as_str() {
+                "date" => DataTypeEnum::Date,
+                "timestamp" => DataTypeEnum::Timestamp(size),
+                "time" => DataTypeEnum::Time(size),
+                _ => DataTypeEnum::Year,
+            };
+            Ok((remain, parse_res))
         }
         Err(err) => Err(err),
     }
 }
 
 pub fn type_text(input: &str) -> IResult<&str, DataTypeEnum> {
-    match tuple((tag_no_case("text"), opt(type_collate)))(input) {
-        Ok((remain, (_, _))) => Ok((remain, DataTypeEnum::Text)),
+    let long_varchar = map(
+        tuple((sql_keyword("long"), space1, sql_keyword("varchar"))),
+        |(word, _, _)| word,
+    );
+    match tuple((
+        alt((
+            sql_keyword("tinytext"),
+            sql_keyword("text"),
+            sql_keyword("mediumtext"),
+            sql_keyword("longtext"),
+            long_varchar,
+            sql_keyword("long"),
+        )),
+        opt(type_int_size),
+    ))(input)
+    {
+        Ok((remain, (flag, _))) => {
+            let parse_res = match flag.to_lowercase().as_str() {
+                "tinytext" => DataTypeEnum::TinyText,
+                "text" => DataTypeEnum::Text,
+                "longtext" => DataTypeEnum::LongText,
+                // long、long varchar 是 mediumtext 的别名
+                _ => DataTypeEnum::MediumText,
+            };
+            Ok((remain, parse_res))
+        }
         Err(err) => Err(err),
     }
 }
 
 pub fn type_bigtext(input: &str) -> IResult<&str, DataTypeEnum> {
-    match tuple((tag_no_case("bigtext"), opt(type_collate)))(input) {
-        Ok((remain, (_, _))) => Ok((remain, DataTypeEnum::BigText)),
+    match sql_keyword("bigtext")(input) {
+        Ok((remain, _)) => Ok((remain, DataTypeEnum::BigText)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析 blob 系列类型：tinyblob、blob(M)、mediumblob、longblob、long varbinary
+pub fn type_blob(input: &str) -> IResult<&str, DataTypeEnum> {
+    let long_varbinary = map(
+        tuple((sql_keyword("long"), space1, sql_keyword("varbinary"))),
+        |(word, _, _)| word,
+    );
+    match tuple((
+        alt((
+            sql_keyword("tinyblob"),
+            sql_keyword("blob"),
+            sql_keyword("mediumblob"),
+            sql_keyword("longblob"),
+            long_varbinary,
+        )),
+        opt(type_int_size),
+    ))(input)
+    {
+        Ok((remain, (flag, _))) => {
+            let parse_res = match flag.to_lowercase().as_str() {
+                "tinyblob" => DataTypeEnum::TinyBlob,
+                "blob" => DataTypeEnum::Blob,
+                "longblob" => DataTypeEnum::LongBlob,
+                // long varbinary 是 mediumblob 的别名
+                _ => DataTypeEnum::MediumBlob,
+            };
+            Ok((remain, parse_res))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析 sql 中单引号或双引号包裹的字符串字面量，支持 `''` 以及反斜杠转义
+pub fn parse_sql_string(input: &str) -> IResult<&str, String> {
+    let mut chars = input.char_indices().peekable();
+    let quote = match chars.next() {
+        Some((_, c)) if c == '\'' || c == '"' => c,
+        _ => {
+            return Err(Err::Error(VerboseError::from_error_kind(
+                input,
+                ErrorKind::Char,
+            )))
+        }
+    };
+    let mut result = String::new();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            c if c == quote => {
+                if matches!(chars.peek(), Some((_, next)) if *next == quote) {
+                    chars.next();
+                    result.push(quote);
+                } else {
+                    return Ok((&input[idx + 1..], result));
+                }
+            }
+            '\\' => match chars.next() {
+                Some((_, 'n')) => result.push('\n'),
+                Some((_, 'r')) => result.push('\r'),
+                Some((_, 't')) => result.push('\t'),
+                Some((_, '0')) => result.push('\0'),
+                Some((_, 'Z')) => result.push('\u{1a}'),
+                Some((_, escaped)) => result.push(escaped),
+                None => break,
+            },
+            _ => result.push(c),
+        }
+    }
+    Err(Err::Error(VerboseError::from_error_kind(
+        input,
+        ErrorKind::Char,
+    )))
+}
+
+/// 解析 enum、set 的取值列表，如：`('a', 'b')`
+pub fn type_value_list(input: &str) -> IResult<&str, Vec<String>> {
+    let one_value = map(
+        tuple((multispace0, parse_sql_string, multispace0, opt(tag(",")))),
+        |(_, value, _, _)| value,
+    );
+    match tuple((tag("("), many1(one_value), tag(")")))(input) {
+        Ok((remain, (_, value_arr, _))) => Ok((remain, value_arr)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析 enum('a','b')、set('a','b')
+pub fn type_enum_or_set(input: &str) -> IResult<&str, DataTypeEnum> {
+    match tuple((
+        alt((sql_keyword("enum"), sql_keyword("set"))),
+        space0,
+        type_value_list,
+    ))(input)
+    {
+        Ok((remain, (flag, _, value_arr))) => match flag.to_lowercase().as_str() {
+            "enum" => Ok((remain, DataTypeEnum::Enum(value_arr))),
+            _ => Ok((remain, DataTypeEnum::Set(value_arr))),
+        },
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析 json 以及空间数据类型，这些类型都没有参数
+pub fn type_json_or_spatial(input: &str) -> IResult<&str, DataTypeEnum> {
+    match alt((
+        sql_keyword("json"),
+        sql_keyword("geometrycollection"),
+        sql_keyword("geomcollection"),
+        sql_keyword("geometry"),
+        sql_keyword("point"),
+        sql_keyword("linestring"),
+        sql_keyword("polygon"),
+        sql_keyword("multipoint"),
+        sql_keyword("multilinestring"),
+        sql_keyword("multipolygon"),
+    ))(input)
+    {
+        Ok((remain, flag)) => {
+            let parse_res = match flag.to_lowercase().as_str() {
+                "json" => DataTypeEnum::Json,
+                "geometry" => DataTypeEnum::Geometry,
+                "point" => DataTypeEnum::Point,
+                "linestring" => DataTypeEnum::LineString,
+                "polygon" => DataTypeEnum::Polygon,
+                "multipoint" => DataTypeEnum::MultiPoint,
+                "multilinestring" => DataTypeEnum::MultiLineString,
+                "multipolygon" => DataTypeEnum::MultiPolygon,
+                _ => DataTypeEnum::GeometryCollection,
+            };
+            Ok((remain, parse_res))
+        }
         Err(err) => Err(err),
     }
 }
 
-// decimal(30)
-// 最大可达 65
+// decimal(30)、decimal(10,2) unsigned
+// 精度最大可达 65，小数位数最大可达 30
 pub fn type_decimal(input: &str) -> IResult<&str, DataTypeEnum> {
-    match tuple((tag_no_case("decimal"), type_int_size))(input) {
-        Ok((remain, (_, size))) => Ok((remain, DataTypeEnum::Decimal(size as u8))),
+    match tuple((
+        alt((
+            sql_keyword("decimal"),
+            sql_keyword("dec"),
+            sql_keyword("numeric"),
+            sql_keyword("fixed"),
+        )),
+        opt(type_float_size),
+        type_number_attr,
+    ))(input)
+    {
+        Ok((remain, (_, size, (unsigned, zerofill)))) => Ok((
+            remain,
+            DataTypeEnum::Decimal(DecimalTypeOption {
+                precision: size.map(|(precision, _)| precision),
+                scale: size.and_then(|(_, scale)| scale),
+                unsigned,
+                zerofill,
+            }),
+        )),
         Err(err) => Err(err),
     }
 }
 
+/// 解析字段类型
 pub fn parse_data_type(input: &str) -> IResult<&str, DataTypeEnum> {
-    match alt((type_some_int, type_varchar, type_datetime, type_decimal))(input) {
+    match alt((
+        type_some_int,
+        type_bit,
+        type_bool,
+        type_float,
+        type_decimal,
+        type_varchar,
+        type_char,
+        type_binary,
+        type_blob,
+        type_text,
+        type_bigtext,
+        type_datetime,
+        type_date_and_time,
+        type_enum_or_set,
+        type_json_or_spatial,
+    ))(input)
+    {
         Ok((remain, parse_res)) => Ok((remain, parse_res)),
         Err(err) => Err(err),
     }
 }
 
 pub fn parse_default_int(input: &str) -> IResult<&str, DefaultEnum> {
-    match tuple((tag_no_case("default"), space1, nom_u32))(input) {
-        Ok((remain, (_, _, u32_val))) => Ok((remain, DefaultEnum::DefaultInt(u32_val))),
+    match tuple((tag_no_case("default"), space1, nom_i64, not(one_of(".eE"))))(input) {
+        Ok((remain, (_, _, i64_val, _))) => Ok((remain, DefaultEnum::DefaultInt(i64_val))),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析浮点数默认值，如：`default -1.5`、`default 1e3`，保留原始文本，避免精度丢失
+pub fn parse_default_float(input: &str) -> IResult<&str, DefaultEnum> {
+    let float_literal = recognize(tuple((
+        opt(one_of("+-")),
+        digit0,
+        opt(tuple((tag("."), digit0))),
+        opt(tuple((one_of("eE"), opt(one_of("+-")), digit1))),
+    )));
+    match tuple((tag_no_case("default"), space1, float_literal))(input) {
+        Ok((remain, (_, _, float_str))) if float_str.chars().any(|c| c.is_ascii_digit()) => {
+            Ok((remain, DefaultEnum::DefaultFloat(float_str.to_string())))
+        }
+        Ok(_) => Err(Err::Error(VerboseError::from_error_kind(
+            input,
+            ErrorKind::Float,
+        ))),
         Err(err) => Err(err),
     }
 }
 
 pub fn parse_default_str(input: &str) -> IResult<&str, DefaultEnum> {
+    match tuple((tag_no_case("default"), space1, parse_sql_string))(input) {
+        Ok((remain, (_, _, str_val))) => Ok((remain, DefaultEnum::DefaultStr(str_val))),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析布尔默认值，如：`default true`
+pub fn parse_default_bool(input: &str) -> IResult<&str, DefaultEnum> {
     match tuple((
         tag_no_case("default"),
         space1,
-        parse_str_with_escaped_and_combine_in_single_quote,
+        alt((sql_keyword("true"), sql_keyword("false"))),
     ))(input)
     {
-        Ok((remain, (_, _, str_val))) => Ok((remain, DefaultEnum::DefaultStr(str_val))),
+        Ok((remain, (_, _, bool_str))) => Ok((
+            remain,
+            DefaultEnum::DefaultBool(bool_str.eq_ignore_ascii_case("true")),
+        )),
         Err(err) => Err(err),
     }
 }
 
-pub fn parse_comment(input: &str) -> IResult<&str, String> {
+/// 解析括号包裹的表达式，返回括号内的文本。如：`(uuid())` 返回 `uuid()`
+/// 括号可以嵌套，引号中的括号不计入
+pub fn parse_paren_expr(input: &str) -> IResult<&str, String> {
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    let mut chars = input.char_indices();
+    while let Some((idx, c)) = chars.next() {
+        match quote {
+            Some(q) => {
+                if c == '\\' {
+                    chars.next();
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None => match c {
+                '(' => depth += 1,
+                ')' if depth > 0 => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok((&input[idx + 1..], input[1..idx].trim().to_string()));
+                    }
+                }
+                '\'' | '"' | '`' if depth > 0 => quote = Some(c),
+                _ if depth == 0 => break,
+                _ => {}
+            },
+        }
+    }
+    Err(Err::Error(VerboseError::from_error_kind(
+        input,
+        ErrorKind::Char,
+    )))
+}
+
+/// 解析表达式默认值，如：`default (uuid())`、`default b'0'`
+pub fn parse_default_expr(input: &str) -> IResult<&str, DefaultEnum> {
+    let bit_or_hex = map(
+        recognize(tuple((one_of("bBxX"), parse_sql_string))),
+        |literal: &str| literal.to_string(),
+    );
     match tuple((
+        tag_no_case("default"),
         space1,
-        tag_no_case("comment"),
-        space1,
-        parse_str_with_escaped_and_combine_in_single_quote,
+        alt((parse_paren_expr, bit_or_hex)),
     ))(input)
     {
+        Ok((remain, (_, _, expr))) => Ok((remain, DefaultEnum::DefaultExpr(expr))),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析引号包裹的字符串，如注释内容，支持 `''` 以及反斜杠转义
+fn parse_quoted_str(input: &str) -> IResult<&str, String> {
+    parse_sql_string(input)
+}
+
+pub fn parse_comment(input: &str) -> IResult<&str, String> {
+    match tuple((space1, tag_no_case("comment"), space1, parse_quoted_str))(input) {
         Ok((remain, (_, _, _, str_val))) => Ok((remain, str_val.to_string())),
         Err(err) => Err(err),
     }
@@ -348,6 +971,24 @@ pub fn parse_default_null(input: &str) -> IResult<&str, DefaultEnum> {
     }
 }
 
+/// 解析当前时间，如：`CURRENT_TIMESTAMP`、`CURRENT_TIMESTAMP(3)`、`NOW()`、`LOCALTIMESTAMP`
+pub fn parse_current_timestamp(input: &str) -> IResult<&str, ()> {
+    let fsp = tuple((tag("("), space0, opt(nom_u32), space0, tag(")")));
+    match tuple((
+        alt((
+            sql_keyword("current_timestamp"),
+            sql_keyword("localtimestamp"),
+            sql_keyword("localtime"),
+            sql_keyword("now"),
+        )),
+        opt(fsp),
+    ))(input)
+    {
+        Ok((remain, _)) => Ok((remain, ())),
+        Err(err) => Err(err),
+    }
+}
+
 pub fn parse_default_on_current_timestamp(input: &str) -> IResult<&str, DefaultEnum> {
     match tuple((
         space1,
@@ -355,7 +996,7 @@ pub fn parse_default_on_current_timestamp(input: &str) -> IResult<&str, DefaultE
         space1,
         tag_no_case("update"),
         space1,
-        tag_no_case("current_timestamp"),
+        parse_current_timestamp,
     ))(input)
     {
         Ok((remain, (_, _, _, _, _, _))) => Ok((remain, DefaultEnum::DefaultOnUpdateCurStamp)),
@@ -367,7 +1008,7 @@ pub fn parse_default_current_timestamp(input: &str) -> IResult<&str, DefaultEnum
     match tuple((
         tag_no_case("default"),
         space1,
-        tag_no_case("CURRENT_TIMESTAMP"),
+        parse_current_timestamp,
         opt(parse_default_on_current_timestamp),
     ))(input)
     {
@@ -392,9 +1033,12 @@ pub fn parse_default(input: &str) -> IResult<&str, DefaultEnum> {
         space1,
         alt((
             parse_default_int,
+            parse_default_float,
             parse_default_str,
             parse_default_null,
+            parse_default_bool,
             parse_default_current_timestamp,
+            parse_default_expr,
             parse_int_auto_increment,
         )),
     ))(input)
@@ -404,136 +1048,652 @@ pub fn parse_default(input: &str) -> IResult<&str, DefaultEnum> {
     }
 }
 
-/// MySQL 表中的一个字段
-#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct OneColumn {
-    pub name: String,
-    pub typ: DataTypeEnum,
-    pub comment: String,
+/// 外键的引用动作，如：`ON DELETE CASCADE` 中的 `CASCADE`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReferenceOptionEnum {
+    /// RESTRICT
+    Restrict,
+    /// CASCADE
+    Cascade,
+    /// SET NULL
+    SetNull,
+    /// NO ACTION
+    NoAction,
+    /// SET DEFAULT
+    SetDefault,
 }
 
-impl OneColumn {
-    fn new(name: String, typ: DataTypeEnum, comment: String) -> Self {
-        OneColumn { name, typ, comment }
-    }
+/// 引用声明，如：`REFERENCES user (id) ON DELETE CASCADE`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OneReference {
+    /// 被引用的表名
+    pub table_name: String,
+    /// 被引用的字段
+    pub column_names: Vec<String>,
+    /// MATCH FULL、MATCH PARTIAL、MATCH SIMPLE
+    pub match_type: Option<String>,
+    /// ON DELETE 的引用动作
+    pub on_delete: Option<ReferenceOptionEnum>,
+    /// ON UPDATE 的引用动作
+    pub on_update: Option<ReferenceOptionEnum>,
 }
 
-pub fn parse_end_has_comma(input: &str) -> IResult<&str, Option<i8>> {
-    let mut parse_has_comma = tuple((tag(","), opt(space0)));
-    match parse_has_comma(input) {
-        Ok((remain, (_, _))) => Ok((remain, Some(1))),
-        Err(err) => Err(err),
-    }
+/// check 约束，如：`CONSTRAINT chk_age CHECK (age > 0) NOT ENFORCED`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OneCheck {
+    /// 约束名
+    pub name: Option<String>,
+    /// 括号中的表达式
+    pub expr: String,
+    /// 是否强制执行，默认为 true
+    pub enforced: bool,
 }
-pub fn parse_end_no_comma(input: &str) -> IResult<&str, Option<i8>> {
-    let mut parse_no_comma = tuple((opt(space0), tag("}")));
-    match parse_no_comma(input) {
-        Ok((remain, (_, _))) => Ok((remain, Some(0))),
-        Err(err) => Err(err),
-    }
+
+/// 生成列，如：`GENERATED ALWAYS AS (price * num) STORED`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GeneratedColumn {
+    /// 括号中的表达式
+    pub expr: String,
+    /// 是否为 STORED，否则为 VIRTUAL
+    pub stored: bool,
 }
 
-// 最后一行字段定义，可能没有逗号
-pub fn parse_end_comma(input: &str) -> IResult<&str, Option<i8>> {
-    alt((parse_end_has_comma, parse_end_no_comma))(input)
+/// 字段声明中，类型之后的一个属性。这些属性可以以任意顺序出现
+#[derive(Debug, PartialEq, Eq)]
+pub enum ColumnAttrEnum {
+    /// null、not null
+    Nullable(bool),
+    /// default 语句，也包括 auto_increment、on update current_timestamp
+    Default(DefaultEnum),
+    /// comment 'xxx'
+    Comment(String),
+    /// character set xxx
+    Charset(String),
+    /// collate xxx
+    Collate(String),
+    /// [generated always] as (expr) [virtual | stored]
+    Generated(GeneratedColumn),
+    /// visible、invisible
+    Visible(bool),
+    /// srid xxx
+    Srid(u32),
+    /// [constraint [symbol]] check (expr) [[not] enforced]
+    Check(OneCheck),
+    /// references tbl_name (key_part,...)
+    References(OneReference),
+    /// [primary] key
+    PrimaryKey,
+    /// unique [key]
+    UniqueKey,
+    /// column_format {fixed | dynamic | default}
+    ColumnFormat(String),
+    /// storage {disk | memory}
+    Storage(String),
+    /// engine_attribute [=] 'string'
+    EngineAttribute(String),
+    /// secondary_engine_attribute [=] 'string'
+    SecondaryEngineAttribute(String),
 }
 
-/// 解析类型的定义，如：`int not null default '1' comment 'main key'`
-/// 在这其中，最重要的信息是 类型、默认值、注释
-pub fn parse_column_definition1(input: &str) -> IResult<&str, OneColumn> {
+/// 解析生成列，如：`GENERATED ALWAYS AS (price * num) STORED`
+pub fn parse_column_generated(input: &str) -> IResult<&str, GeneratedColumn> {
     let mut parser = tuple((
-        multispace0,
-        sql_identifier,
         space1,
-        parse_data_type,
-        parse_column_definition_of_default,
-        opt(parse_comment),
+        opt(tuple((
+            sql_keyword("generated"),
+            space1,
+            sql_keyword("always"),
+            space1,
+        ))),
+        sql_keyword("as"),
         space0,
-        tag(","),
-        opt(multispace0),
+        parse_paren_expr,
+        opt(preceded(
+            space1,
+            alt((sql_keyword("virtual"), sql_keyword("stored"))),
+        )),
     ));
     match parser(input) {
-        Ok((remain, (_, column_name, _, column_type, _, opt_comment, _, _, _))) => {
-            let mut comment = String::new();
-            if opt_comment.is_some() {
-                comment = opt_comment.unwrap();
-            }
-            Ok((remain, OneColumn::new(column_name, column_type, comment)))
-        }
+        Ok((remain, (_, _, _, _, expr, store_type))) => Ok((
+            remain,
+            GeneratedColumn {
+                expr,
+                stored: store_type
+                    .map(|typ| typ.eq_ignore_ascii_case("stored"))
+                    .unwrap_or(false),
+            },
+        )),
         Err(err) => Err(err),
     }
 }
 
-pub fn parse_column_definition2(input: &str) -> IResult<&str, OneLineEnum> {
+/// 解析约束名，如：`CONSTRAINT fk_user `，约束名可以省略
+pub fn parse_constraint_name(input: &str) -> IResult<&str, Option<String>> {
+    let constraint_type = alt((
+        sql_keyword("primary"),
+        sql_keyword("unique"),
+        sql_keyword("foreign"),
+        sql_keyword("check"),
+    ));
     let mut parser = tuple((
-        sql_identifier,
+        sql_keyword("constraint"),
+        opt(preceded(
+            space1,
+            preceded(not(constraint_type), sql_identifier),
+        )),
         space1,
-        parse_data_type,
-        parse_column_definition_of_default,
-        opt(parse_comment),
-        space0,
-        tag(","),
-        opt(multispace0),
     ));
     match parser(input) {
-        Ok((remain, (column_name, _, column_type, _, opt_comment, _, _, _))) => {
-            let mut comment = String::new();
-            if opt_comment.is_some() {
-                comment = opt_comment.unwrap();
-            }
-            let one_column = OneColumn::new(column_name, column_type, comment);
-            Ok((remain, OneLineEnum::Column(one_column)))
-        }
+        Ok((remain, (_, name, _))) => Ok((remain, name)),
         Err(err) => Err(err),
     }
 }
 
-pub fn parse_many_column_definition(input: &str) -> IResult<&str, Vec<OneColumn>> {
-    let mut column_define_builder =
-        fold_many1(parse_column_definition1, Vec::new, |mut arr, one_column| {
-            arr.push(one_column);
-            arr
-        });
-
-    match tuple((tag("("), space0, column_define_builder, space0, tag(")")))(input) {
-        Ok((remain, (_, _, column_arr, _, _))) => Ok((remain, column_arr)),
+/// 解析 check 约束，如：`CONSTRAINT chk_age CHECK (age > 0) NOT ENFORCED`
+pub fn parse_check(input: &str) -> IResult<&str, OneCheck> {
+    let enforced = tuple((
+        space1,
+        opt(tuple((sql_keyword("not"), space1))),
+        sql_keyword("enforced"),
+    ));
+    let mut parser = tuple((
+        opt(parse_constraint_name),
+        sql_keyword("check"),
+        space0,
+        parse_paren_expr,
+        opt(enforced),
+    ));
+    match parser(input) {
+        Ok((remain, (constraint, _, _, expr, enforced))) => Ok((
+            remain,
+            OneCheck {
+                name: constraint.flatten(),
+                expr,
+                enforced: !matches!(enforced, Some((_, Some(_), _))),
+            },
+        )),
         Err(err) => Err(err),
     }
 }
 
-/// MySQL 表中的索引
-#[derive(Debug, PartialEq, Eq)]
-pub struct OneIndex {
-    name: String,
-    using_type: Option<String>, // b-tree、hash、None
-    typ: IndexIdxTyeEnum,       //unique key、primary key
-    column_names: Vec<String>,
+/// 解析外键的引用动作，如：`CASCADE`、`SET NULL`
+pub fn parse_reference_option(input: &str) -> IResult<&str, ReferenceOptionEnum> {
+    alt((
+        map(sql_keyword("restrict"), |_| ReferenceOptionEnum::Restrict),
+        map(sql_keyword("cascade"), |_| ReferenceOptionEnum::Cascade),
+        map(
+            tuple((sql_keyword("set"), space1, sql_keyword("null"))),
+            |_| ReferenceOptionEnum::SetNull,
+        ),
+        map(
+            tuple((sql_keyword("no"), space1, sql_keyword("action"))),
+            |_| ReferenceOptionEnum::NoAction,
+        ),
+        map(
+            tuple((sql_keyword("set"), space1, sql_keyword("default"))),
+            |_| ReferenceOptionEnum::SetDefault,
+        ),
+    ))(input)
 }
 
-/// 一行建表语句的描述。包含字段描述、索引描述等。
-#[derive(Debug, PartialEq, Eq)]
-pub enum OneLineEnum {
-    Column(OneColumn),
+/// 解析引用声明，如：`REFERENCES user (id) ON DELETE CASCADE`
+pub fn parse_reference(input: &str) -> IResult<&str, OneReference> {
+    let match_type = preceded(
+        tuple((space1, sql_keyword("match"), space1)),
+        alt((
+            sql_keyword("full"),
+            sql_keyword("partial"),
+            sql_keyword("simple"),
+        )),
+    );
+    let on_action = tuple((
+        space1,
+        sql_keyword("on"),
+        space1,
+        alt((sql_keyword("delete"), sql_keyword("update"))),
+        space1,
+        parse_reference_option,
+    ));
+    let mut parser = tuple((
+        sql_keyword("references"),
+        space1,
+        sql_identifier,
+        parse_idx_column_name,
+        opt(match_type),
+        many_m_n(0, 2, on_action),
+    ));
+    match parser(input) {
+        Ok((remain, (_, _, table_name, column_names, match_type, action_arr))) => {
+            let mut reference = OneReference {
+                table_name,
+                column_names,
+                match_type: match_type.map(|typ| typ.to_uppercase()),
+                ..Default::default()
+            };
+            for (_, _, _, event, _, action) in action_arr {
+                if event.eq_ignore_ascii_case("delete") {
+                    reference.on_delete = Some(action);
+                } else {
+                    reference.on_update = Some(action);
+                }
+            }
+            Ok((remain, reference))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析 `[=] 'string'` 形式的属性值
+fn parse_attr_str_value(input: &str) -> IResult<&str, String> {
+    match tuple((space0, opt(tag("=")), space0, parse_sql_string))(input) {
+        Ok((remain, (_, _, _, value))) => Ok((remain, value)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析字段类型之后的一个属性，属性前面必须有空白
+pub fn parse_column_attr(input: &str) -> IResult<&str, ColumnAttrEnum> {
+    let primary_key = tuple((
+        opt(tuple((sql_keyword("primary"), space1))),
+        sql_keyword("key"),
+    ));
+    let unique_key = tuple((
+        sql_keyword("unique"),
+        opt(tuple((space1, sql_keyword("key")))),
+    ));
+    let visible = alt((sql_keyword("visible"), sql_keyword("invisible")));
+    let column_format = preceded(
+        tuple((sql_keyword("column_format"), space1)),
+        alt((
+            sql_keyword("fixed"),
+            sql_keyword("dynamic"),
+            sql_keyword("default"),
+        )),
+    );
+    let storage = preceded(
+        tuple((sql_keyword("storage"), space1)),
+        alt((sql_keyword("disk"), sql_keyword("memory"))),
+    );
+    alt((
+        map(parse_column_nullable, ColumnAttrEnum::Nullable),
+        map(parse_default, ColumnAttrEnum::Default),
+        map(
+            preceded(space1, parse_int_auto_increment),
+            ColumnAttrEnum::Default,
+        ),
+        map(parse_default_on_current_timestamp, ColumnAttrEnum::Default),
+        map(parse_comment, ColumnAttrEnum::Comment),
+        map(type_charset, ColumnAttrEnum::Charset),
+        map(type_collate, ColumnAttrEnum::Collate),
+        map(parse_column_generated, ColumnAttrEnum::Generated),
+        map(preceded(space1, visible), |flag: &str| {
+            ColumnAttrEnum::Visible(flag.eq_ignore_ascii_case("visible"))
+        }),
+        map(
+            tuple((space1, sql_keyword("srid"), space1, nom_u32)),
+            |(_, _, _, srid)| ColumnAttrEnum::Srid(srid),
+        ),
+        map(preceded(space1, parse_check), ColumnAttrEnum::Check),
+        map(
+            preceded(space1, parse_reference),
+            ColumnAttrEnum::References,
+        ),
+        map(preceded(space1, primary_key), |_| {
+            ColumnAttrEnum::PrimaryKey
+        }),
+        map(preceded(space1, unique_key), |_| ColumnAttrEnum::UniqueKey),
+        map(preceded(space1, column_format), |format: &str| {
+            ColumnAttrEnum::ColumnFormat(format.to_uppercase())
+        }),
+        map(preceded(space1, storage), |storage: &str| {
+            ColumnAttrEnum::Storage(storage.to_uppercase())
+        }),
+        map(
+            preceded(
+                tuple((space1, sql_keyword("engine_attribute"))),
+                parse_attr_str_value,
+            ),
+            ColumnAttrEnum::EngineAttribute,
+        ),
+        map(
+            preceded(
+                tuple((space1, sql_keyword("secondary_engine_attribute"))),
+                parse_attr_str_value,
+            ),
+            ColumnAttrEnum::SecondaryEngineAttribute,
+        ),
+    ))(input)
+}
+
+/// MySQL 表中的一个字段
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OneColumn {
+    /// 字段名
+    pub name: String,
+    /// 字段类型
+    pub typ: DataTypeEnum,
+    /// 字段注释
+    pub comment: String,
+    /// 是否允许为 NULL。没有声明 `NOT NULL` 的字段都允许为 NULL，主键字段除外
+    pub nullable: bool,
+    /// 默认值，没有 default 语句时为 `DefaultNone`
+    /// `AUTO_INCREMENT`、`ON UPDATE CURRENT_TIMESTAMP` 不视为默认值，而是记录在对应的字段上
+    pub default: DefaultEnum,
+    /// 是否自增
+    pub auto_increment: bool,
+    /// 是否声明了 `ON UPDATE CURRENT_TIMESTAMP`
+    pub on_update_cur_stamp: bool,
+    /// 字段的字符集，如：`CHARACTER SET utf8mb4`
+    pub charset: Option<String>,
+    /// 字段的字符集排序规则，如：`COLLATE utf8mb4_bin`
+    pub collate: Option<String>,
+    /// 生成列的表达式
+    pub generated: Option<GeneratedColumn>,
+    /// 是否为不可见字段
+    pub invisible: bool,
+    /// 空间类型字段的 SRID
+    pub srid: Option<u32>,
+    /// 是否在字段上直接声明了 `PRIMARY KEY`
+    pub primary_key: bool,
+    /// 是否在字段上直接声明了 `UNIQUE KEY`
+    pub unique_key: bool,
+    /// 字段上的 check 约束
+    pub check_arr: Vec<OneCheck>,
+    /// 字段上的引用声明
+    pub reference: Option<OneReference>,
+    /// COLUMN_FORMAT，如：FIXED、DYNAMIC
+    pub column_format: Option<String>,
+    /// STORAGE，如：DISK、MEMORY
+    pub storage: Option<String>,
+    /// ENGINE_ATTRIBUTE
+    pub engine_attribute: Option<String>,
+    /// SECONDARY_ENGINE_ATTRIBUTE
+    pub secondary_engine_attribute: Option<String>,
+}
+
+impl OneColumn {
+    /// 设置字段的默认值。`parse_default` 解析出的自增、`ON UPDATE` 会被拆分到对应的字段上
+    pub fn set_default(&mut self, default: DefaultEnum) {
+        match default {
+            DefaultEnum::DefaultAutoIncrement => self.auto_increment = true,
+            DefaultEnum::DefaultOnUpdateCurStamp => self.on_update_cur_stamp = true,
+            DefaultEnum::DefaultCurStampOnUpdateCurStamp => {
+                self.default = DefaultEnum::DefaultCurStamp;
+                self.on_update_cur_stamp = true;
+            }
+            _ => self.default = default,
+        }
+    }
+
+    /// 将一个字段属性记录到字段上
+    pub fn set_attr(&mut self, attr: ColumnAttrEnum) {
+        match attr {
+            ColumnAttrEnum::Nullable(nullable) => self.nullable = nullable,
+            ColumnAttrEnum::Default(default) => self.set_default(default),
+            ColumnAttrEnum::Comment(comment) => self.comment = comment,
+            ColumnAttrEnum::Charset(charset) => self.charset = Some(charset),
+            ColumnAttrEnum::Collate(collate) => self.collate = Some(collate),
+            ColumnAttrEnum::Generated(generated) => self.generated = Some(generated),
+            ColumnAttrEnum::Visible(visible) => self.invisible = !visible,
+            ColumnAttrEnum::Srid(srid) => self.srid = Some(srid),
+            ColumnAttrEnum::Check(check) => self.check_arr.push(check),
+            ColumnAttrEnum::References(reference) => self.reference = Some(reference),
+            ColumnAttrEnum::PrimaryKey => {
+                self.primary_key = true;
+                self.nullable = false;
+            }
+            ColumnAttrEnum::UniqueKey => self.unique_key = true,
+            ColumnAttrEnum::ColumnFormat(format) => self.column_format = Some(format),
+            ColumnAttrEnum::Storage(storage) => self.storage = Some(storage),
+            ColumnAttrEnum::EngineAttribute(value) => self.engine_attribute = Some(value),
+            ColumnAttrEnum::SecondaryEngineAttribute(value) => {
+                self.secondary_engine_attribute = Some(value)
+            }
+        }
+    }
+}
+
+impl Default for OneColumn {
+    fn default() -> Self {
+        OneColumn {
+            name: String::new(),
+            typ: DataTypeEnum::Unknown,
+            comment: String::new(),
+            nullable: true,
+            default: DefaultEnum::DefaultNone,
+            auto_increment: false,
+            on_update_cur_stamp: false,
+            charset: None,
+            collate: None,
+            generated: None,
+            invisible: false,
+            srid: None,
+            primary_key: false,
+            unique_key: false,
+            check_arr: vec![],
+            reference: None,
+            column_format: None,
+            storage: None,
+            engine_attribute: None,
+            secondary_engine_attribute: None,
+        }
+    }
+}
+
+pub fn parse_end_has_comma(input: &str) -> IResult<&str, Option<i8>> {
+    let mut parse_has_comma = tuple((tag(","), opt(space0)));
+    match parse_has_comma(input) {
+        Ok((remain, (_, _))) => Ok((remain, Some(1))),
+        Err(err) => Err(err),
+    }
+}
+pub fn parse_end_no_comma(input: &str) -> IResult<&str, Option<i8>> {
+    let mut parse_no_comma = tuple((opt(space0), tag("}")));
+    match parse_no_comma(input) {
+        Ok((remain, (_, _))) => Ok((remain, Some(0))),
+        Err(err) => Err(err),
+    }
+}
+
+// 最后一行字段定义，可能没有逗号
+pub fn parse_end_comma(input: &str) -> IResult<&str, Option<i8>> {
+    alt((parse_end_has_comma, parse_end_no_comma))(input)
+}
+
+/// 解析一个字段声明，不包含结尾的逗号。如：`id` int not null default '1' comment 'main key'
+/// 类型之后的属性可以以任意顺序出现
+pub fn parse_column_definition(input: &str) -> IResult<&str, OneColumn> {
+    // 字段名之后的类型解析失败时，不再回溯尝试其他分支，以便报告准确的错误位置
+    let mut parser = context(
+        "column definition",
+        tuple((
+            sql_identifier,
+            space1,
+            context("data type", cut(parse_data_type)),
+            many0(parse_column_attr),
+        )),
+    );
+    match parser(input) {
+        Ok((remain, (name, _, typ, attr_arr))) => {
+            let mut column = OneColumn {
+                name,
+                typ,
+                ..Default::default()
+            };
+            for attr in attr_arr {
+                column.set_attr(attr);
+            }
+            Ok((remain, column))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析类型的定义，如：`int not null default '1' comment 'main key'`
+/// 在这其中，最重要的信息是 类型、默认值、注释
+pub fn parse_column_definition1(input: &str) -> IResult<&str, OneColumn> {
+    let mut parser = tuple((
+        multispace0,
+        parse_column_definition,
+        space0,
+        tag(","),
+        opt(multispace0),
+    ));
+    match parser(input) {
+        Ok((remain, (_, column, _, _, _))) => Ok((remain, column)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析建表语句体中的一个字段声明，最后一个字段后面可以没有逗号
+pub fn parse_column_definition2(input: &str) -> IResult<&str, OneLineEnum> {
+    let mut parser = context(
+        "column definition",
+        tuple((
+            parse_column_definition,
+            multispace0,
+            context("column attribute", cut(alt((tag(","), peek(tag(")")))))),
+            opt(multispace0),
+        )),
+    );
+    match parser(input) {
+        Ok((remain, (column, _, _, _))) => Ok((remain, OneLineEnum::Column(column))),
+        Err(err) => Err(err),
+    }
+}
+
+pub fn parse_many_column_definition(input: &str) -> IResult<&str, Vec<OneColumn>> {
+    let mut column_define_builder =
+        fold_many1(parse_column_definition1, Vec::new, |mut arr, one_column| {
+            arr.push(one_column);
+            arr
+        });
+
+    match tuple((tag("("), space0, column_define_builder, space0, tag(")")))(input) {
+        Ok((remain, (_, _, column_arr, _, _))) => Ok((remain, column_arr)),
+        Err(err) => Err(err),
+    }
+}
+
+/// MySQL 表中的索引
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OneIndex {
+    /// 索引名，主键索引没有名字时为空字符串
+    pub name: String,
+    /// 索引结构，如：BTREE、HASH
+    pub using_type: Option<String>,
+    /// 索引类型，如：主键、唯一索引
+    pub typ: IndexIdxTyeEnum,
+    /// 索引包含的部分，按声明顺序排列
+    pub key_parts: Vec<OneKeyPart>,
+    /// 索引选项，如：KEY_BLOCK_SIZE、COMMENT、INVISIBLE
+    pub option: IndexOption,
+}
+
+impl OneIndex {
+    /// 索引包含的字段名，函数索引部分不计入
+    pub fn column_names(&self) -> Vec<String> {
+        self.key_parts
+            .iter()
+            .filter_map(|part| part.column_name.clone())
+            .collect()
+    }
+}
+
+/// 索引中的一部分，如：`name(20) DESC`、`(lower(email))`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OneKeyPart {
+    /// 字段名，函数索引部分为 None
+    pub column_name: Option<String>,
+    /// 前缀索引的长度，如：`name(20)` 中的 20
+    pub prefix_len: Option<u32>,
+    /// 排序方向
+    pub order: Option<IndexOrderEnum>,
+    /// 函数索引的表达式，如：`(lower(email))` 中的 `lower(email)`
+    pub expr: Option<String>,
+}
+
+impl OneKeyPart {
+    /// 只包含字段名的索引部分
+    pub fn new(column_name: &str) -> Self {
+        OneKeyPart {
+            column_name: Some(column_name.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+/// 索引部分的排序方向
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IndexOrderEnum {
+    /// ASC
+    Asc,
+    /// DESC
+    Desc,
+}
+
+/// 索引选项
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexOption {
+    /// KEY_BLOCK_SIZE [=] value
+    pub key_block_size: Option<u32>,
+    /// 索引注释
+    pub comment: Option<String>,
+    /// 是否为不可见索引
+    pub invisible: bool,
+    /// 全文索引的解析器，如：`WITH PARSER ngram`
+    pub parser: Option<String>,
+    /// ENGINE_ATTRIBUTE
+    pub engine_attribute: Option<String>,
+    /// SECONDARY_ENGINE_ATTRIBUTE
+    pub secondary_engine_attribute: Option<String>,
+}
+
+/// 一行建表语句的描述。包含字段描述、索引描述等。
+#[derive(Debug, PartialEq, Eq)]
+pub enum OneLineEnum {
+    Column(OneColumn),
     Index(OneIndex),
+    /// 外键约束
+    ForeignKey(OneForeignKey),
+    /// 表级别的 check 约束
+    Check(OneCheck),
+}
+
+/// 外键约束，如：`CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES user (id) ON DELETE CASCADE`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OneForeignKey {
+    /// 约束名，即 `CONSTRAINT` 后的名字
+    pub name: Option<String>,
+    /// 索引名，即 `FOREIGN KEY` 后的名字
+    pub index_name: Option<String>,
+    /// 本表中的字段
+    pub column_names: Vec<String>,
+    /// 引用的表及字段
+    pub reference: OneReference,
 }
 
 /// 索引类型，如：主键、唯一索引等
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum IndexIdxTyeEnum {
     Primary,
     Unique,
     Normal,
+    /// 全文索引，如：`FULLTEXT KEY`
+    FullText,
+    /// 空间索引，如：`SPATIAL KEY`
+    Spatial,
     None,
 }
 
 /// 解析索引语句中的字段部分 —— (`name`)
 pub fn parse_idx_column_name(input: &str) -> IResult<&str, Vec<String>> {
-    let column_plus = tuple((sql_identifier, space0, opt(tag(","))));
+    let column_plus = tuple((multispace0, sql_identifier, multispace0, opt(tag(","))));
     let mut parser = tuple((space0, tag("("), many1(column_plus), tag(")")));
     match parser(input) {
         Ok((remain, (_, _, column_name_arr, _))) => {
             let mut name_arr: Vec<String> = vec![];
-            for (name, _, _) in column_name_arr {
+            for (_, name, _, _) in column_name_arr {
                 name_arr.push(name);
             }
             Ok((remain, name_arr))
@@ -542,12 +1702,148 @@ pub fn parse_idx_column_name(input: &str) -> IResult<&str, Vec<String>> {
     }
 }
 
+/// 解析索引中的一部分，如：`name`、`name(20) DESC`、`(lower(email))`
+pub fn parse_idx_key_part(input: &str) -> IResult<&str, OneKeyPart> {
+    let prefix_len = tuple((space0, tag("("), space0, nom_u32, space0, tag(")")));
+    let column_part = map(
+        tuple((sql_identifier, opt(prefix_len))),
+        |(column_name, prefix_len)| OneKeyPart {
+            column_name: Some(column_name),
+            prefix_len: prefix_len.map(|(_, _, _, len, _, _)| len),
+            ..Default::default()
+        },
+    );
+    let expr_part = map(parse_paren_expr, |expr| OneKeyPart {
+        expr: Some(expr),
+        ..Default::default()
+    });
+    let order = preceded(space1, alt((sql_keyword("asc"), sql_keyword("desc"))));
+    match tuple((alt((column_part, expr_part)), opt(order)))(input) {
+        Ok((remain, (mut key_part, order))) => {
+            key_part.order = order.map(|order| {
+                if order.eq_ignore_ascii_case("desc") {
+                    IndexOrderEnum::Desc
+                } else {
+                    IndexOrderEnum::Asc
+                }
+            });
+            Ok((remain, key_part))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析索引语句中的索引部分 —— (`name`(20) DESC, (lower(`email`)))
+pub fn parse_idx_key_parts(input: &str) -> IResult<&str, Vec<OneKeyPart>> {
+    let key_part_plus = tuple((multispace0, parse_idx_key_part, multispace0, opt(tag(","))));
+    let mut parser = tuple((space0, tag("("), many1(key_part_plus), tag(")")));
+    match parser(input) {
+        Ok((remain, (_, _, key_part_arr, _))) => Ok((
+            remain,
+            key_part_arr
+                .into_iter()
+                .map(|(_, key_part, _, _)| key_part)
+                .collect(),
+        )),
+        Err(err) => Err(err),
+    }
+}
+
+/// 索引选项中的一项
+enum IndexOptionItemEnum {
+    Using(String),
+    KeyBlockSize(u32),
+    Comment(String),
+    Visible(bool),
+    Parser(String),
+    EngineAttribute(String),
+    SecondaryEngineAttribute(String),
+}
+
+/// 解析索引选项中的一项，选项前面必须有空白
+fn parse_idx_option_item(input: &str) -> IResult<&str, IndexOptionItemEnum> {
+    let key_block_size = tuple((
+        space1,
+        sql_keyword("key_block_size"),
+        space0,
+        opt(tag("=")),
+        space0,
+        nom_u32,
+    ));
+    let with_parser = tuple((
+        space1,
+        sql_keyword("with"),
+        space1,
+        sql_keyword("parser"),
+        space1,
+        sql_identifier,
+    ));
+    let visible = preceded(
+        space1,
+        alt((sql_keyword("visible"), sql_keyword("invisible"))),
+    );
+    alt((
+        map(parse_idx_using_struct, IndexOptionItemEnum::Using),
+        map(key_block_size, |(_, _, _, _, _, size)| {
+            IndexOptionItemEnum::KeyBlockSize(size)
+        }),
+        map(parse_comment, IndexOptionItemEnum::Comment),
+        map(visible, |flag: &str| {
+            IndexOptionItemEnum::Visible(flag.eq_ignore_ascii_case("visible"))
+        }),
+        map(with_parser, |(_, _, _, _, _, parser)| {
+            IndexOptionItemEnum::Parser(parser)
+        }),
+        map(
+            preceded(
+                tuple((space1, sql_keyword("engine_attribute"))),
+                parse_attr_str_value,
+            ),
+            IndexOptionItemEnum::EngineAttribute,
+        ),
+        map(
+            preceded(
+                tuple((space1, sql_keyword("secondary_engine_attribute"))),
+                parse_attr_str_value,
+            ),
+            IndexOptionItemEnum::SecondaryEngineAttribute,
+        ),
+    ))(input)
+}
+
+/// 解析索引字段之后的索引选项，返回索引结构以及其余选项
+pub fn parse_idx_option(input: &str) -> IResult<&str, (Option<String>, IndexOption)> {
+    match many0(parse_idx_option_item)(input) {
+        Ok((remain, item_arr)) => {
+            let mut using_type = None;
+            let mut option = IndexOption::default();
+            for item in item_arr {
+                match item {
+                    IndexOptionItemEnum::Using(typ) => using_type = Some(typ),
+                    IndexOptionItemEnum::KeyBlockSize(size) => option.key_block_size = Some(size),
+                    IndexOptionItemEnum::Comment(comment) => option.comment = Some(comment),
+                    IndexOptionItemEnum::Visible(visible) => option.invisible = !visible,
+                    IndexOptionItemEnum::Parser(parser) => option.parser = Some(parser),
+                    IndexOptionItemEnum::EngineAttribute(value) => {
+                        option.engine_attribute = Some(value)
+                    }
+                    IndexOptionItemEnum::SecondaryEngineAttribute(value) => {
+                        option.secondary_engine_attribute = Some(value)
+                    }
+                }
+            }
+            Ok((remain, (using_type, option)))
+        }
+        Err(err) => Err(err),
+    }
+}
+
 /// 解析 using btree 段
 pub fn parse_idx_using_struct(input: &str) -> IResult<&str, String> {
     let tree_type = alt((tag_no_case("btree"), tag_no_case("hash")));
     let mut parser = tuple((space1, tag_no_case("using"), space1, tree_type));
     match parser(input) {
-        Ok((remain, (_, _, _, tree_type))) => Ok((remain, tree_type.to_string())),
+        Ok((remain, (_, _, _, tree_type))) => Ok((remain, tree_type.to_uppercase())),
         Err(err) => Err(err),
     }
 }
@@ -559,14 +1855,14 @@ pub fn parse_idx_line(input: &str) -> IResult<&str, OneIndex> {
         tag_no_case("KEY"),
         space1,
         opt(sql_identifier),
-        parse_idx_column_name,
-        opt(parse_idx_using_struct),
+        parse_idx_key_parts,
+        parse_idx_option,
         opt(tag(",")),
         opt(multispace0),
     ));
 
     match parse_index_key(input) {
-        Ok((remain, (typ, _, _, _, idx_name_op, column_name_arr, using_type, _, _))) => {
+        Ok((remain, (typ, _, _, _, idx_name_op, key_parts, (using_type, option), _, _))) => {
             let mut idx_name = String::new();
             if idx_name_op.is_some() {
                 idx_name = idx_name_op.unwrap();
@@ -581,7 +1877,8 @@ pub fn parse_idx_line(input: &str) -> IResult<&str, OneIndex> {
                 name: idx_name,
                 using_type,
                 typ: typ_enum,
-                column_names: column_name_arr,
+                key_parts,
+                option,
             };
             Ok((remain, idx))
         }
@@ -591,50 +1888,85 @@ pub fn parse_idx_line(input: &str) -> IResult<&str, OneIndex> {
 
 // 解析索引声明的前半部分，如：`primary key`、`key`、`unique key`
 fn parse_idx_part_prev(input: &str) -> IResult<&str, String> {
-    // 解析索引声明的前半部分，如：`primary key`、`key`、`unique key`
-    let mut pri_or_uni_idx = tuple((alt((tag_no_case("primary"), tag_no_case("UNIQUE"))), space1));
+    // 解析索引声明的前半部分，如：`primary key`、`key`、`unique key`、`fulltext key`
+    let mut pri_or_uni_idx = tuple((
+        alt((
+            sql_keyword("primary"),
+            sql_keyword("unique"),
+            sql_keyword("fulltext"),
+            sql_keyword("spatial"),
+        )),
+        space1,
+    ));
     match pri_or_uni_idx(input) {
         Ok((remain, (idx_typ, _))) => Ok((remain, idx_typ.to_string())),
         Err(err) => Err(err),
     }
 }
 
-/// 解析一行索引声明。如：PRIMARY KEY (`id`)
+/// 解析一行声明的结尾，最后一行后面可以没有逗号
+fn parse_define_line_end(input: &str) -> IResult<&str, ()> {
+    match tuple((multispace0, alt((tag(","), peek(tag(")")))), multispace0))(input) {
+        Ok((remain, _)) => Ok((remain, ())),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析一行索引声明。如：PRIMARY KEY (`id`)、UNIQUE INDEX `uk_name` (`name`)、FULLTEXT KEY `ft_content` (`content`)
 pub fn parse_idx_line2(input: &str) -> IResult<&str, OneLineEnum> {
+    let key_or_index = || alt((sql_keyword("key"), sql_keyword("index")));
+    let idx_head = alt((
+        map(
+            tuple((parse_idx_part_prev, opt(key_or_index()))),
+            |(typ, _)| typ,
+        ),
+        map(key_or_index(), |_| String::new()),
+    ));
     let mut parse_index_key = tuple((
         multispace0,
-        opt(parse_idx_part_prev),
-        tag_no_case("KEY"),
-        space1,
-        opt(sql_identifier),
-        parse_idx_column_name,
+        opt(parse_constraint_name),
+        idx_head,
+        opt(preceded(space0, sql_identifier)),
         opt(parse_idx_using_struct),
+        parse_idx_key_parts,
+        parse_idx_option,
         opt(tag(",")),
         opt(multispace0),
     ));
 
     match parse_index_key(input) {
-        Ok((remain, (_, typ_op, _, _, idx_name_op, column_name_arr, using_type, _, _))) => {
-            let mut typ = "".to_string();
-            if typ_op.is_some() {
-                typ = typ_op.unwrap();
-            }
-            let mut idx_name = String::new();
-            if idx_name_op.is_some() {
-                idx_name = idx_name_op.unwrap();
-            }
-            let mut typ_enum = IndexIdxTyeEnum::None;
-            match typ.to_lowercase().as_str() {
-                "primary" => typ_enum = IndexIdxTyeEnum::Primary,
-                "unique" => typ_enum = IndexIdxTyeEnum::Unique,
-                "" => typ_enum = IndexIdxTyeEnum::Normal,
-                _ => typ_enum = IndexIdxTyeEnum::None,
-            }
+        Ok((
+            remain,
+            (
+                _,
+                constraint_name,
+                typ,
+                idx_name_op,
+                using_prev,
+                key_parts,
+                (using_type, option),
+                _,
+                _,
+            ),
+        )) => {
+            let typ_enum = match typ.to_lowercase().as_str() {
+                "primary" => IndexIdxTyeEnum::Primary,
+                "unique" => IndexIdxTyeEnum::Unique,
+                "fulltext" => IndexIdxTyeEnum::FullText,
+                "spatial" => IndexIdxTyeEnum::Spatial,
+                "" => IndexIdxTyeEnum::Normal,
+                _ => IndexIdxTyeEnum::None,
+            };
+            // 唯一索引没有指定索引名时，以约束名作为索引名
+            let idx_name = idx_name_op
+                .or_else(|| constraint_name.flatten())
+                .unwrap_or_default();
             let idx = OneIndex {
                 name: idx_name,
-                using_type,
+                using_type: using_type.or(using_prev),
                 typ: typ_enum,
-                column_names: column_name_arr,
+                key_parts,
+                option,
             };
             Ok((remain, OneLineEnum::Index(idx)))
         }
@@ -642,7 +1974,49 @@ pub fn parse_idx_line2(input: &str) -> IResult<&str, OneLineEnum> {
     }
 }
 
-/// 解析建表语句，返回表名
+/// 解析外键约束，如：`CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES user (id)`
+pub fn parse_foreign_key(input: &str) -> IResult<&str, OneForeignKey> {
+    let mut parser = tuple((
+        opt(parse_constraint_name),
+        sql_keyword("foreign"),
+        space1,
+        sql_keyword("key"),
+        opt(preceded(space1, sql_identifier)),
+        parse_idx_column_name,
+        multispace0,
+        parse_reference,
+    ));
+    match parser(input) {
+        Ok((remain, (name, _, _, _, index_name, column_names, _, reference))) => Ok((
+            remain,
+            OneForeignKey {
+                name: name.flatten(),
+                index_name,
+                column_names,
+                reference,
+            },
+        )),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析一行外键约束声明
+pub fn parse_foreign_key_line(input: &str) -> IResult<&str, OneLineEnum> {
+    match tuple((multispace0, parse_foreign_key, parse_define_line_end))(input) {
+        Ok((remain, (_, foreign_key, _))) => Ok((remain, OneLineEnum::ForeignKey(foreign_key))),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析一行表级别的 check 约束声明，如：`CONSTRAINT chk_age CHECK (age > 0)`
+pub fn parse_check_line(input: &str) -> IResult<&str, OneLineEnum> {
+    match tuple((multispace0, parse_check, parse_define_line_end))(input) {
+        Ok((remain, (_, check, _))) => Ok((remain, OneLineEnum::Check(check))),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析建表语句，返回表名，`TEMPORARY` 关键字可以省略
 /// 如：CREATE TABLE `demo_table_user`，则返回：`demo_table_user`
 pub fn parse_create_table(input: &str) -> IResult<&str, String> {
     let mut parse_if_not_exist = tuple((
@@ -656,6 +2030,7 @@ pub fn parse_create_table(input: &str) -> IResult<&str, String> {
     let mut parse_create = tuple((
         tag_no_case("create"),
         space1,
+        opt(tuple((sql_keyword("temporary"), space1))),
         tag_no_case("table"),
         opt(parse_if_not_exist),
         space1,
@@ -663,14 +2038,19 @@ pub fn parse_create_table(input: &str) -> IResult<&str, String> {
         multispace0,
     ));
     match parse_create(input) {
-        Ok((remain, (_, _, _, _, _, table_name, _))) => Ok((remain, table_name)),
+        Ok((remain, (_, _, _, _, _, _, table_name, _))) => Ok((remain, table_name)),
         Err(err) => Err(err),
     }
 }
 
 /// 解析建表语句体中的一段，无论是字段声明还是索引声明
 pub fn parse_one_define_line(input: &str) -> IResult<&str, OneLineEnum> {
-    let mut parser = alt((parse_idx_line2, parse_column_definition2));
+    let mut parser = alt((
+        parse_idx_line2,
+        parse_foreign_key_line,
+        parse_check_line,
+        parse_column_definition2,
+    ));
     parser(input)
 }
 
@@ -703,31 +2083,80 @@ pub fn parse_many1_define_line(input: &str) -> IResult<&str, Vec<OneLineEnum>> {
     }
 }
 
-/// 表配置
-#[derive(Debug, PartialEq, Eq)]
+/// 表配置，没有声明的配置为 None
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TableOption {
-    pub engine: String,
-    pub charset: String,
-    pub collate: String,
-    pub comment: String,
+    /// 存储引擎，如：InnoDB
+    pub engine: Option<String>,
+    /// 默认字符集，如：utf8mb4
+    pub charset: Option<String>,
+    /// 默认字符集排序规则，如：utf8mb4_bin
+    pub collate: Option<String>,
+    /// 表注释
+    pub comment: Option<String>,
+    /// 自增字段的起始值，如：`AUTO_INCREMENT=1234`
+    pub auto_increment: Option<u64>,
+    /// 行格式，如：DYNAMIC、COMPRESSED
+    pub row_format: Option<String>,
+    /// 分区声明原文，如：`PARTITION BY HASH (id) PARTITIONS 4`
+    pub partition: Option<String>,
+    /// 其余的表配置，键为大写的配置名，如：`KEY_BLOCK_SIZE` -> `8`
+    pub extra: BTreeMap<String, String>,
+}
+
+/// 表配置中的一项
+enum TableOptionItemEnum {
+    Engine(String),
+    Charset(String),
+    Collate(String),
+    Comment(String),
+    AutoIncrement(u64),
+    RowFormat(String),
+    Partition(String),
+    Other(String, String),
 }
 
-/// 解析建表语句中的表配置部分
+/// 解析建表语句中的表配置部分，配置可以以任意顺序出现，也可以用逗号分隔，全部省略也是合法的
 pub fn parse_table_option(input: &str) -> IResult<&str, TableOption> {
-    let mut parser = tuple((
-        table_option_engine,
-        table_option_char_set,
-        table_option_collate,
-        table_option_comment,
+    let item = alt((
+        map(table_option_engine, TableOptionItemEnum::Engine),
+        map(table_option_char_set, TableOptionItemEnum::Charset),
+        map(table_option_collate, TableOptionItemEnum::Collate),
+        map(table_option_comment, TableOptionItemEnum::Comment),
+        map(
+            table_option_auto_increment,
+            TableOptionItemEnum::AutoIncrement,
+        ),
+        map(table_option_row_format, TableOptionItemEnum::RowFormat),
+        map(table_option_partition, TableOptionItemEnum::Partition),
+        map(table_option_other, |(key, value)| {
+            TableOptionItemEnum::Other(key, value)
+        }),
     ));
+    let mut parser = many0(preceded(tuple((multispace0, opt(tag(",")))), item));
     match parser(input) {
-        Ok((remain, (engine, charset, collate, comment))) => {
-            let table_option = TableOption {
-                engine,
-                charset,
-                collate,
-                comment,
-            };
+        Ok((remain, item_arr)) => {
+            let mut table_option = TableOption::default();
+            for item in item_arr {
+                match item {
+                    TableOptionItemEnum::Engine(engine) => table_option.engine = Some(engine),
+                    TableOptionItemEnum::Charset(charset) => table_option.charset = Some(charset),
+                    TableOptionItemEnum::Collate(collate) => table_option.collate = Some(collate),
+                    TableOptionItemEnum::Comment(comment) => table_option.comment = Some(comment),
+                    TableOptionItemEnum::AutoIncrement(val) => {
+                        table_option.auto_increment = Some(val)
+                    }
+                    TableOptionItemEnum::RowFormat(row_format) => {
+                        table_option.row_format = Some(row_format)
+                    }
+                    TableOptionItemEnum::Partition(partition) => {
+                        table_option.partition = Some(partition)
+                    }
+                    TableOptionItemEnum::Other(key, value) => {
+                        table_option.extra.insert(key, value);
+                    }
+                }
+            }
             Ok((remain, table_option))
         }
         Err(err) => Err(err),
@@ -738,7 +2167,7 @@ pub fn parse_table_option(input: &str) -> IResult<&str, TableOption> {
 pub fn table_option_engine(input: &str) -> IResult<&str, String> {
     let mut parser = tuple((
         space0,
-        tag_no_case("engine"),
+        sql_keyword("engine"),
         opt(tuple((space0, tag("=")))),
         space0,
         sql_identifier,
@@ -752,11 +2181,11 @@ pub fn table_option_engine(input: &str) -> IResult<&str, String> {
 /// 表配置解析 —— [DEFAULT] CHARACTER SET [=] charset_name
 pub fn table_option_char_set(input: &str) -> IResult<&str, String> {
     let mut parser = tuple((
-        opt(tuple((space1, tag_no_case("default")))),
-        opt(space0),
+        space0,
+        opt(tuple((sql_keyword("default"), space1))),
         alt((
-            tuple((space0, space0, tag_no_case("CHARSET"))),
-            tuple((tag_no_case("CHARACTER"), space1, tag_no_case("set"))),
+            tuple((space0, space0, sql_keyword("CHARSET"))),
+            tuple((tag_no_case("CHARACTER"), space1, sql_keyword("set"))),
         )),
         tuple((space0, alt((tag("="), space0)), space0)),
         sql_identifier,
@@ -770,9 +2199,9 @@ pub fn table_option_char_set(input: &str) -> IResult<&str, String> {
 /// 表配置解析 —— 字符集排序，如 `COLLATE=utf8mb4_bin`
 pub fn table_option_collate(input: &str) -> IResult<&str, String> {
     let mut parser = tuple((
-        opt(tuple((space1, tag_no_case("default")))),
-        opt(space0),
-        tag_no_case("COLLATE"),
+        space0,
+        opt(tuple((sql_keyword("default"), space1))),
+        sql_keyword("COLLATE"),
         tuple((space0, alt((tag("="), space0)), space0)),
         sql_identifier,
     ));
@@ -782,72 +2211,460 @@ pub fn table_option_collate(input: &str) -> IResult<&str, String> {
     }
 }
 
-/// 表配置解析 —— 表注释、说明
-pub fn table_option_comment(input: &str) -> IResult<&str, String> {
-    let mut parser = tuple((
-        space1,
-        tag_no_case("comment"),
-        tuple((space0, alt((tag("="), space0)), space0)),
-        parse_str_with_escaped_and_combine_in_single_quote,
-    ));
-    match parser(input) {
-        Ok((remain, (_, _, _, comment))) => Ok((remain, comment)),
+/// 表配置解析 —— 表注释、说明
+pub fn table_option_comment(input: &str) -> IResult<&str, String> {
+    let mut parser = tuple((
+        space0,
+        sql_keyword("comment"),
+        tuple((space0, alt((tag("="), space0)), space0)),
+        parse_quoted_str,
+    ));
+    match parser(input) {
+        Ok((remain, (_, _, _, comment))) => Ok((remain, comment)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 表配置解析 —— AUTO_INCREMENT [=] value
+pub fn table_option_auto_increment(input: &str) -> IResult<&str, u64> {
+    let mut parser = tuple((
+        space0,
+        sql_keyword("auto_increment"),
+        tuple((space0, opt(tag("=")), space0)),
+        nom_u64,
+    ));
+    match parser(input) {
+        Ok((remain, (_, _, _, val))) => Ok((remain, val)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 表配置解析 —— ROW_FORMAT [=] {DEFAULT | DYNAMIC | FIXED | COMPRESSED | REDUNDANT | COMPACT}
+pub fn table_option_row_format(input: &str) -> IResult<&str, String> {
+    let mut parser = tuple((
+        space0,
+        sql_keyword("row_format"),
+        tuple((space0, opt(tag("=")), space0)),
+        sql_identifier,
+    ));
+    match parser(input) {
+        Ok((remain, (_, _, _, row_format))) => Ok((remain, row_format.to_uppercase())),
+        Err(err) => Err(err),
+    }
+}
+
+/// 表配置解析 —— 分区声明，如：`PARTITION BY RANGE (id) (...)`
+/// 也支持 `SHOW CREATE TABLE` 输出的条件注释形式：`/*!50100 PARTITION BY ... */`
+/// 分区声明的内容不再细分，原样保存到语句结尾（顶层的 `;`）
+pub fn table_option_partition(input: &str) -> IResult<&str, String> {
+    let version_comment = tuple((tag("/*!"), digit1, space0));
+    let mut parser = tuple((
+        space0,
+        opt(version_comment),
+        recognize(tuple((
+            sql_keyword("partition"),
+            multispace1,
+            sql_keyword("by"),
+        ))),
+    ));
+    let (remain, (_, version_comment, clause_head)) = parser(input)?;
+    let start = input.len() - remain.len() - clause_head.len();
+    let body = &input[start..];
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    let mut end = body.len();
+    let mut chars = body.char_indices().peekable();
+    while let Some((idx, c)) = chars.next() {
+        match quote {
+            Some(q) => {
+                if c == '\\' {
+                    chars.next();
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None => match c {
+                '\'' | '"' | '`' => quote = Some(c),
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                '*' if version_comment.is_some() && matches!(chars.peek(), Some((_, '/'))) => {
+                    end = idx;
+                    break;
+                }
+                ';' if depth <= 0 => {
+                    end = idx;
+                    break;
+                }
+                _ => {}
+            },
+        }
+    }
+    let partition = body[..end].trim().to_string();
+    let remain = if version_comment.is_some() && end < body.len() {
+        &body[end + 2..]
+    } else {
+        &body[end..]
+    };
+    Ok((remain, partition))
+}
+
+/// 表配置解析 —— 其余形如 `KEY_BLOCK_SIZE=8`、`DATA DIRECTORY='/data'` 的配置
+/// 为了避免把后面的语句误认为配置，这里要求必须有 `=`
+pub fn table_option_other(input: &str) -> IResult<&str, (String, String)> {
+    let option_name = alt((
+        map(
+            recognize(tuple((
+                alt((sql_keyword("data"), sql_keyword("index"))),
+                space1,
+                sql_keyword("directory"),
+            ))),
+            |name: &str| name.split_whitespace().collect::<Vec<&str>>().join(" "),
+        ),
+        sql_identifier,
+    ));
+    let option_value = alt((
+        parse_sql_string,
+        map(parse_paren_expr, |expr| format!("({})", expr)),
+        map(
+            take_while1(|c: char| c.is_alphanumeric() || c == '_' || c == '.' || c == '-'),
+            |val: &str| val.to_string(),
+        ),
+    ));
+    let mut parser = tuple((
+        space0,
+        option_name,
+        tuple((space0, tag("="), space0)),
+        option_value,
+    ));
+    match parser(input) {
+        Ok((remain, (_, name, _, value))) => Ok((remain, (name.to_uppercase(), value))),
+        Err(err) => Err(err),
+    }
+}
+
+/// 建表语句所属的数据库方言
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DialectEnum {
+    /// MySQL
+    #[default]
+    MySql,
+    /// PostgreSQL
+    Postgres,
+    /// SQLite
+    Sqlite,
+}
+
+/// 一个表，表所包含的主要信息
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableSchema {
+    /// 建表语句的方言
+    pub dialect: DialectEnum,
+    /// 表所在的 schema，如 PostgreSQL 中 `public.user` 的 `public`
+    pub schema_name: Option<String>,
+    /// 表名
+    pub table_name: String,
+    /// 字段列表，按建表语句中的顺序排列
+    pub column_arr: Vec<OneColumn>,
+    /// 索引列表，按建表语句中的顺序排列
+    pub index_arr: Vec<OneIndex>,
+    /// 外键列表，按建表语句中的顺序排列
+    pub foreign_key_arr: Vec<OneForeignKey>,
+    /// 表级别的 check 约束列表
+    pub check_arr: Vec<OneCheck>,
+    /// 表配置
+    pub option: TableOption,
+}
+
+impl TableSchema {
+    /// 由建表语句体中的各行声明构造表结构，主键字段会被标记为不允许为 NULL
+    pub fn new(table_name: String, line_arr: Vec<OneLineEnum>, option: TableOption) -> Self {
+        let mut column_arr: Vec<OneColumn> = vec![];
+        let mut index_arr: Vec<OneIndex> = vec![];
+        let mut foreign_key_arr: Vec<OneForeignKey> = vec![];
+        let mut check_arr: Vec<OneCheck> = vec![];
+        for one_line in line_arr {
+            match one_line {
+                OneLineEnum::Column(column) => column_arr.push(column),
+                OneLineEnum::Index(index) => index_arr.push(index),
+                OneLineEnum::ForeignKey(foreign_key) => foreign_key_arr.push(foreign_key),
+                OneLineEnum::Check(check) => check_arr.push(check),
+            }
+        }
+        let mut table = TableSchema {
+            dialect: DialectEnum::MySql,
+            schema_name: None,
+            table_name,
+            column_arr,
+            index_arr,
+            foreign_key_arr,
+            check_arr,
+            option,
+        };
+        table.mark_primary_key_not_null();
+        table
+    }
+
+    /// 主键字段隐式地不允许为 NULL
+    fn mark_primary_key_not_null(&mut self) {
+        for index in self.index_arr.iter() {
+            if index.typ != IndexIdxTyeEnum::Primary {
+                continue;
+            }
+            for column in self.column_arr.iter_mut() {
+                if index.column_names().contains(&column.name) {
+                    column.nullable = false;
+                }
+            }
+        }
+    }
+
+    /// 按名字查找字段
+    pub fn column_mut(&mut self, name: &str) -> Option<&mut OneColumn> {
+        self.column_arr
+            .iter_mut()
+            .find(|column| column.name == name)
+    }
+}
+
+/// 解析整个建表语句，返回表名、字段、索引以及表配置
+/// 如：CREATE TABLE `t` (...) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_bin COMMENT='';
+pub fn parse_create_sql(input: &str) -> IResult<&str, TableSchema> {
+    let mut parser = context(
+        "create table",
+        tuple((
+            parse_create_table,
+            space0,
+            context("table body", cut(parse_many1_define_line)),
+            space0,
+            context("table option", cut(parse_table_option)),
+            opt(tuple((space0, tag(";")))),
+        )),
+    );
+    match parser(input) {
+        Ok((remain, (table_name, _, line_arr, _, table_option, _))) => {
+            Ok((remain, TableSchema::new(table_name, line_arr, table_option)))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// 将解析建表语句时的错误格式化为可读的报告，包括行号、列号、出错的行、指向出错位置的 `^` 以及语法规则栈
+/// 如：
+/// ```text
+/// error at line 3, column 12: expected data type
+///   3 |   `amount` decimal(10,
+///     |            ^
+/// in create table → table body → column definition `amount` → data type
+/// ```
+pub fn format_parse_error(input: &str, err: &Err<VerboseError<&str>>) -> String {
+    let err = match err {
+        Err::Error(err) | Err::Failure(err) => err,
+        Err::Incomplete(_) => return "error: incomplete input".to_string(),
+    };
+    let (err_input, err_kind) = match err.errors.first() {
+        Some(first) => first,
+        None => return "error: unknown parse error".to_string(),
+    };
+    let offset = input.len().saturating_sub(err_input.len());
+    let prefix = &input[..offset];
+    let line_no = prefix.matches('\n').count() + 1;
+    let line_start = prefix.rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+    let line_text = input[line_start..].lines().next().unwrap_or("");
+    let column_no = prefix[line_start..].chars().count() + 1;
+    // 保留行首的制表符，使 `^` 与出错位置对齐
+    let caret_pad: String = prefix[line_start..]
+        .chars()
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+
+    // errors 中越靠后的上下文越外层
+    let mut context_arr: Vec<(&str, &str)> = vec![];
+    for (ctx_input, kind) in err.errors.iter().rev() {
+        if let VerboseErrorKind::Context(ctx) = kind {
+            let is_dup = context_arr
+                .last()
+                .map(|(last_input, last_ctx)| {
+                    last_ctx == ctx && last_input.as_ptr() == ctx_input.as_ptr()
+                })
+                .unwrap_or(false);
+            if !is_dup {
+                context_arr.push((ctx_input, ctx));
+            }
+        }
+    }
+    let expected = match context_arr.last() {
+        Some((ctx_input, ctx)) if ctx_input.as_ptr() == err_input.as_ptr() => ctx.to_string(),
+        _ => match err_kind {
+            VerboseErrorKind::Char(c) => format!("'{}'", c),
+            VerboseErrorKind::Context(ctx) => ctx.to_string(),
+            VerboseErrorKind::Nom(kind) => kind.description().to_lowercase(),
+        },
+    };
+    let rule_stack: Vec<String> = context_arr
+        .iter()
+        .map(|(ctx_input, ctx)| {
+            let name = sql_identifier(ctx_input)
+                .or_else(|_| postgres::pg_identifier(ctx_input))
+                .or_else(|_| sqlite::sqlite_identifier(ctx_input));
+            (ctx, name)
+        })
+        .map(|(ctx, name)| match (*ctx, name) {
+            ("column definition", Ok((_, name))) => format!("{} `{}`", ctx, name),
+            _ => ctx.to_string(),
+        })
+        .collect();
+
+    let line_no_str = line_no.to_string();
+    let gutter = " ".repeat(line_no_str.len());
+    let mut report = format!(
+        "error at line {}, column {}: expected {}\n {} | {}\n {} | {}^",
+        line_no, column_no, expected, line_no_str, line_text, gutter, caret_pad
+    );
+    if !rule_stack.is_empty() {
+        report += &format!("\nin {}", rule_stack.join(" → "));
+    }
+    report
+}
+
+/// schema 文件中被跳过的语句，如：`DROP TABLE IF EXISTS`、`/*!40101 SET ... */`
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkippedStatement {
+    /// 语句起始位置所在的行号，从 1 开始
+    pub line: usize,
+    /// 语句原文，不含结尾的 `;`
+    pub statement: String,
+}
+
+/// 一个 schema 文件的解析结果
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaFile {
+    /// 文件中所有的建表语句
+    pub table_arr: Vec<TableSchema>,
+    /// 每个建表语句起始位置所在的行号，与 `table_arr` 一一对应
+    #[serde(default)]
+    pub table_line_arr: Vec<usize>,
+    /// 不是建表语句而被跳过的语句
+    pub skipped_arr: Vec<SkippedStatement>,
+}
+
+/// 解析 sql 中的注释，包括 `-- xxx`、`# xxx` 以及 `/* xxx */`
+/// MySQL 的条件注释 `/*!40101 xxx */` 实际上是会被执行的语句，不在此处理
+pub fn parse_sql_comment(input: &str) -> IResult<&str, &str> {
+    let block_comment = tuple((tag("/*"), not(tag("!")), take_until("*/"), tag("*/")));
+    match alt((
+        preceded(tag("--"), not_line_ending),
+        preceded(tag("#"), not_line_ending),
+        map(block_comment, |(_, _, comment, _)| comment),
+    ))(input)
+    {
+        Ok((remain, comment)) => Ok((remain, comment)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 跳过空白字符和注释
+pub fn parse_space_or_comment(input: &str) -> IResult<&str, ()> {
+    match many0(alt((multispace1, parse_sql_comment)))(input) {
+        Ok((remain, _)) => Ok((remain, ())),
         Err(err) => Err(err),
     }
 }
 
-/// 一个表，表所包含的主要信息
-#[derive(Debug, PartialEq, Eq)]
-struct TableSchema {
-    table_name: String,
-    column_arr: Vec<OneColumn>,
-    option: TableOption,
+/// 解析一条不关心的语句，直到语句结尾的 `;` 或者输入结束。会跳过引号和注释中的 `;`
+pub fn parse_other_statement(input: &str) -> IResult<&str, String> {
+    let mut quote: Option<char> = None;
+    let mut in_comment = false;
+    let mut end = input.len();
+    let mut chars = input.char_indices().peekable();
+    while let Some((idx, c)) = chars.next() {
+        if in_comment {
+            if c == '*' && matches!(chars.peek(), Some((_, '/'))) {
+                chars.next();
+                in_comment = false;
+            }
+            continue;
+        }
+        match quote {
+            Some(q) => {
+                if c == '\\' {
+                    chars.next();
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None => match c {
+                '\'' | '"' | '`' => quote = Some(c),
+                '/' if matches!(chars.peek(), Some((_, '*'))) => {
+                    chars.next();
+                    in_comment = true;
+                }
+                ';' => {
+                    end = idx;
+                    break;
+                }
+                _ => {}
+            },
+        }
+    }
+    let statement = input[..end].trim();
+    if statement.is_empty() && end == input.len() {
+        return Err(Err::Error(VerboseError::from_error_kind(
+            input,
+            ErrorKind::Eof,
+        )));
+    }
+    let remain = if end < input.len() {
+        &input[end + 1..]
+    } else {
+        &input[end..]
+    };
+    Ok((remain, statement.to_string()))
 }
 
-/// 解析整个建表语句 totest
-fn parse_create_sql(input: &str) -> IResult<&str, TableSchema> {
-    let mut parser = tuple((
-        parse_create_table,
-        space0,
-        parse_many1_define_line,
-        space0,
-        parse_table_option,
-    ));
-    match parser(input) {
-        Ok((remain, (table_name, _, column_arr, _, table_option))) => {
-            // 拿出字段部分，和索引部分，但索引的信息暂时用不上，所以就不取了
-            let column_arr: Vec<OneColumn> = column_arr
-                .into_iter()
-                .filter(|one_line| match one_line {
-                    OneLineEnum::Column(_) => true,
-                    _ => false,
-                })
-                .map(|one_line| match one_line {
-                    OneLineEnum::Column(column_line) => column_line,
-                    _ => OneColumn {
-                        name: "".to_string(),
-                        typ: DataTypeEnum::Unknown,
-                        comment: "".to_string(),
-                    },
-                })
-                .collect();
-
-            Ok((
-                remain,
-                TableSchema {
-                    table_name,
-                    column_arr,
-                    option: table_option,
-                },
-            ))
+/// 解析整个 schema 文件，如 `mysqldump --no-data` 导出的文件
+/// 文件中的建表语句会被解析成 `TableSchema`，其余的语句（`DROP TABLE`、`SET`、条件注释等）会被跳过并记录下来
+pub fn parse_schema_file(input: &str) -> IResult<&str, SchemaFile> {
+    let mut table_arr: Vec<TableSchema> = vec![];
+    let mut table_line_arr: Vec<usize> = vec![];
+    let mut skipped_arr: Vec<SkippedStatement> = vec![];
+    let mut remain = input;
+    loop {
+        remain = parse_space_or_comment(remain)?.0;
+        if remain.is_empty() {
+            break;
         }
-        Err(err) => Err(err),
+        let line = input[..input.len() - remain.len()].matches('\n').count() + 1;
+        match parse_create_sql(remain) {
+            Ok((next, table)) => {
+                table_arr.push(table);
+                table_line_arr.push(line);
+                remain = next;
+                continue;
+            }
+            // 已经确定是建表语句但内容有误，不能当作其他语句跳过
+            Err(err @ Err::Failure(_)) => return Err(err),
+            Err(_) => {}
+        }
+        let (next, statement) = parse_other_statement(remain)?;
+        if !statement.is_empty() {
+            skipped_arr.push(SkippedStatement { line, statement });
+        }
+        remain = next;
     }
+    Ok((
+        remain,
+        SchemaFile {
+            table_arr,
+            table_line_arr,
+            skipped_arr,
+        },
+    ))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::parse_string::parse_str_with_escaped_and_combine_in_single_quote;
 
     #[test]
     fn test_parse_column_definition_of_not_null() {
@@ -913,13 +2730,29 @@ mod tests {
     fn test_type_tiny_int() {
         assert_eq!(
             type_tiny_int("tinyint(10)"),
-            Ok(("", DataTypeEnum::TinyInt))
+            Ok((
+                "",
+                DataTypeEnum::TinyInt(IntTypeOption {
+                    width: Some(10),
+                    ..Default::default()
+                })
+            ))
+        );
+        assert_eq!(
+            type_some_int("tinyint(10) unsigned zerofill"),
+            Ok((
+                "",
+                DataTypeEnum::TinyInt(IntTypeOption {
+                    width: Some(10),
+                    unsigned: true,
+                    zerofill: true,
+                })
+            ))
         );
         assert_eq!(
-            type_some_int("tinyint(10)"),
-            Ok(("", DataTypeEnum::TinyInt))
+            type_some_int("bigint"),
+            Ok(("", DataTypeEnum::Bigint(IntTypeOption::default())))
         );
-        assert_eq!(type_some_int("bigint"), Ok(("", DataTypeEnum::Bigint)));
     }
 
     #[test]
@@ -932,10 +2765,19 @@ mod tests {
 
     #[test]
     fn test_parse_data_type() {
-        assert_eq!(parse_data_type("int"), Ok(("", DataTypeEnum::Int)));
+        assert_eq!(
+            parse_data_type("int"),
+            Ok(("", DataTypeEnum::Int(IntTypeOption::default())))
+        );
         assert_eq!(
             parse_data_type("bigint(20)"),
-            Ok(("", DataTypeEnum::Bigint))
+            Ok((
+                "",
+                DataTypeEnum::Bigint(IntTypeOption {
+                    width: Some(20),
+                    ..Default::default()
+                })
+            ))
         );
         assert_eq!(
             parse_data_type("varchar(255)"),
@@ -943,7 +2785,134 @@ mod tests {
         );
         assert_eq!(
             parse_data_type("varchar(50) COLLATE utf8mb4_bin"),
-            Ok(("", DataTypeEnum::VarChar(50)))
+            Ok((" COLLATE utf8mb4_bin", DataTypeEnum::VarChar(50)))
+        );
+        assert_eq!(
+            parse_data_type("INT(11) UNSIGNED"),
+            Ok((
+                "",
+                DataTypeEnum::Int(IntTypeOption {
+                    width: Some(11),
+                    unsigned: true,
+                    zerofill: false,
+                })
+            ))
+        );
+        assert_eq!(
+            parse_data_type("integer"),
+            Ok(("", DataTypeEnum::Int(IntTypeOption::default())))
+        );
+        assert_eq!(
+            parse_data_type("mediumint(8) unsigned"),
+            Ok((
+                "",
+                DataTypeEnum::MediumInt(IntTypeOption {
+                    width: Some(8),
+                    unsigned: true,
+                    zerofill: false,
+                })
+            ))
+        );
+        assert_eq!(parse_data_type("char"), Ok(("", DataTypeEnum::Char(1))));
+        assert_eq!(
+            parse_data_type("char(32)"),
+            Ok(("", DataTypeEnum::Char(32)))
+        );
+        assert_eq!(
+            parse_data_type("varbinary(16)"),
+            Ok(("", DataTypeEnum::VarBinary(16)))
+        );
+        assert_eq!(
+            parse_data_type("float"),
+            Ok(("", DataTypeEnum::Float(DecimalTypeOption::default())))
+        );
+        assert_eq!(
+            parse_data_type("double precision"),
+            Ok(("", DataTypeEnum::Double(DecimalTypeOption::default())))
+        );
+        assert_eq!(parse_data_type("date"), Ok(("", DataTypeEnum::Date)));
+        assert_eq!(
+            parse_data_type("timestamp(6)"),
+            Ok(("", DataTypeEnum::Timestamp(6)))
+        );
+        assert_eq!(parse_data_type("time"), Ok(("", DataTypeEnum::Time(0))));
+        assert_eq!(parse_data_type("year(4)"), Ok(("", DataTypeEnum::Year)));
+        assert_eq!(parse_data_type("json"), Ok(("", DataTypeEnum::Json)));
+        assert_eq!(
+            parse_data_type("longtext"),
+            Ok(("", DataTypeEnum::LongText))
+        );
+        assert_eq!(
+            parse_data_type("long varbinary"),
+            Ok(("", DataTypeEnum::MediumBlob))
+        );
+        assert_eq!(
+            parse_data_type("decimal(10,2) unsigned"),
+            Ok((
+                "",
+                DataTypeEnum::Decimal(DecimalTypeOption {
+                    precision: Some(10),
+                    scale: Some(2),
+                    unsigned: true,
+                    zerofill: false,
+                })
+            ))
+        );
+        assert_eq!(
+            parse_data_type("numeric(30)"),
+            Ok((
+                "",
+                DataTypeEnum::Decimal(DecimalTypeOption {
+                    precision: Some(30),
+                    ..Default::default()
+                })
+            ))
+        );
+        assert_eq!(
+            parse_data_type("double(16,4)"),
+            Ok((
+                "",
+                DataTypeEnum::Double(DecimalTypeOption {
+                    precision: Some(16),
+                    scale: Some(4),
+                    ..Default::default()
+                })
+            ))
+        );
+        assert_eq!(
+            parse_data_type("serial"),
+            Ok((
+                "",
+                DataTypeEnum::Bigint(IntTypeOption {
+                    unsigned: true,
+                    ..Default::default()
+                })
+            ))
+        );
+        assert_eq!(parse_data_type("bit(8)"), Ok(("", DataTypeEnum::Bit(8))));
+        assert_eq!(parse_data_type("boolean"), Ok(("", DataTypeEnum::Bool)));
+        assert_eq!(parse_data_type("point"), Ok(("", DataTypeEnum::Point)));
+    }
+
+    #[test]
+    fn test_type_enum_or_set() {
+        assert_eq!(
+            parse_data_type("enum('male','female', 'it''s')"),
+            Ok((
+                "",
+                DataTypeEnum::Enum(vec![
+                    "male".to_string(),
+                    "female".to_string(),
+                    "it's".to_string()
+                ])
+            ))
+        );
+        assert_eq!(
+            parse_data_type("set('a','b')"),
+            Ok((
+                "",
+                DataTypeEnum::Set(vec!["a".to_string(), "b".to_string()])
+            ))
         );
     }
 
@@ -953,9 +2922,272 @@ mod tests {
             parse_column_definition1(r##"id int not null default 1 comment "主键","##),
             Ok((
                 "",
-                OneColumn::new("id".to_string(), DataTypeEnum::Int, "主键".to_string())
+                OneColumn {
+                    name: "id".to_string(),
+                    typ: DataTypeEnum::Int(IntTypeOption::default()),
+                    comment: "主键".to_string(),
+                    nullable: false,
+                    default: DefaultEnum::DefaultInt(1),
+                    ..Default::default()
+                }
+            ))
+        )
+    }
+
+    #[test]
+    fn test_parse_column_definition() {
+        assert_eq!(
+            parse_column_definition(
+                "`name` varchar(50) CHARACTER SET utf8mb4 COLLATE utf8mb4_bin DEFAULT NULL COMMENT '名称'"
+            ),
+            Ok((
+                "",
+                OneColumn {
+                    name: "name".to_string(),
+                    typ: DataTypeEnum::VarChar(50),
+                    comment: "名称".to_string(),
+                    default: DefaultEnum::DefaultNull,
+                    charset: Some("utf8mb4".to_string()),
+                    collate: Some("utf8mb4_bin".to_string()),
+                    ..Default::default()
+                }
             ))
+        );
+        assert_eq!(
+            parse_column_definition(
+                "`updated_at` datetime(3) DEFAULT NULL ON UPDATE CURRENT_TIMESTAMP(3)"
+            ),
+            Ok((
+                "",
+                OneColumn {
+                    name: "updated_at".to_string(),
+                    typ: DataTypeEnum::DateTime(3),
+                    default: DefaultEnum::DefaultNull,
+                    on_update_cur_stamp: true,
+                    ..Default::default()
+                }
+            ))
+        );
+        let (_, column) =
+            parse_column_definition("`id` bigint unsigned NOT NULL AUTO_INCREMENT").unwrap();
+        assert!(!column.nullable);
+        assert!(column.auto_increment);
+        assert_eq!(column.default, DefaultEnum::DefaultNone);
+    }
+
+    #[test]
+    fn test_parse_column_attr_any_order() {
+        let (remain, column) = parse_column_definition(
+            "`age` int DEFAULT '0' COMMENT '年龄' NOT NULL CHECK (age >= 0) INVISIBLE",
+        )
+        .unwrap();
+        assert_eq!(remain, "");
+        assert!(!column.nullable);
+        assert!(column.invisible);
+        assert_eq!(column.default, DefaultEnum::DefaultStr("0".to_string()));
+        assert_eq!(column.comment, "年龄");
+        assert_eq!(
+            column.check_arr,
+            vec![OneCheck {
+                name: None,
+                expr: "age >= 0".to_string(),
+                enforced: true,
+            }]
+        );
+
+        let (_, column) = parse_column_definition(
+            "`total` decimal(10,2) GENERATED ALWAYS AS (`price` * `num`) STORED NOT NULL",
+        )
+        .unwrap();
+        assert_eq!(
+            column.generated,
+            Some(GeneratedColumn {
+                expr: "`price` * `num`".to_string(),
+                stored: true,
+            })
+        );
+        assert!(!column.nullable);
+
+        let (_, column) = parse_column_definition("`pos` point NOT NULL SRID 4326").unwrap();
+        assert_eq!(column.srid, Some(4326));
+
+        let (_, column) = parse_column_definition(
+            "`user_id` bigint NOT NULL REFERENCES `user` (`id`) ON DELETE CASCADE ON UPDATE SET NULL",
         )
+        .unwrap();
+        assert_eq!(
+            column.reference,
+            Some(OneReference {
+                table_name: "user".to_string(),
+                column_names: vec!["id".to_string()],
+                on_delete: Some(ReferenceOptionEnum::Cascade),
+                on_update: Some(ReferenceOptionEnum::SetNull),
+                ..Default::default()
+            })
+        );
+
+        let (_, column) =
+            parse_column_definition("`id` int AUTO_INCREMENT PRIMARY KEY COMMENT 'id'").unwrap();
+        assert!(column.primary_key);
+        assert!(column.auto_increment);
+        assert!(!column.nullable);
+        assert_eq!(column.comment, "id");
+    }
+
+    #[test]
+    fn test_format_parse_error() {
+        let sql = "CREATE TABLE `t` (
+  `id` int NOT NULL,
+  `amount` decimel(10,2) NOT NULL,
+  PRIMARY KEY (`id`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_bin COMMENT='';";
+        let err = parse_create_sql(sql).unwrap_err();
+        assert_eq!(
+            format_parse_error(sql, &err),
+            "error at line 3, column 12: expected data type
+ 3 |   `amount` decimel(10,2) NOT NULL,
+   |            ^
+in create table → table body → column definition `amount` → data type"
+        );
+
+        let sql = "CREATE TABLE `t` (
+  `id` int NOT NUL,
+  PRIMARY KEY (`id`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_bin COMMENT='';";
+        let err = parse_create_sql(sql).unwrap_err();
+        assert_eq!(
+            format_parse_error(sql, &err),
+            "error at line 2, column 12: expected column attribute
+ 2 |   `id` int NOT NUL,
+   |            ^
+in create table → table body → column definition `id` → column attribute"
+        );
+    }
+
+    #[test]
+    fn test_parse_constraint_line() {
+        let sql = "CREATE TABLE `order` (
+  `id` bigint NOT NULL AUTO_INCREMENT,
+  `user_id` bigint NOT NULL,
+  `amount` int NOT NULL,
+  `content` text,
+  `pos` point NOT NULL,
+  PRIMARY KEY (`id`),
+  UNIQUE INDEX `uk_user` (`user_id`, `amount`),
+  INDEX `idx_amount` (`amount`),
+  FULLTEXT KEY `ft_content` (`content`),
+  SPATIAL KEY `sp_pos` (`pos`),
+  CONSTRAINT `fk_user` FOREIGN KEY (`user_id`) REFERENCES `user` (`id`) ON DELETE CASCADE,
+  CONSTRAINT `chk_amount` CHECK (`amount` > 0),
+  CHECK (`amount` < 1000000) NOT ENFORCED
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_bin COMMENT='订单';";
+        let (remain, table) = parse_create_sql(sql).unwrap();
+        assert_eq!(remain, "");
+        assert_eq!(table.column_arr.len(), 5);
+        let typ_arr: Vec<&IndexIdxTyeEnum> = table.index_arr.iter().map(|idx| &idx.typ).collect();
+        assert_eq!(
+            typ_arr,
+            vec![
+                &IndexIdxTyeEnum::Primary,
+                &IndexIdxTyeEnum::Unique,
+                &IndexIdxTyeEnum::Normal,
+                &IndexIdxTyeEnum::FullText,
+                &IndexIdxTyeEnum::Spatial,
+            ]
+        );
+        assert_eq!(
+            table.index_arr[1].column_names(),
+            vec!["user_id".to_string(), "amount".to_string()]
+        );
+        assert_eq!(
+            table.foreign_key_arr,
+            vec![OneForeignKey {
+                name: Some("fk_user".to_string()),
+                index_name: None,
+                column_names: vec!["user_id".to_string()],
+                reference: OneReference {
+                    table_name: "user".to_string(),
+                    column_names: vec!["id".to_string()],
+                    on_delete: Some(ReferenceOptionEnum::Cascade),
+                    ..Default::default()
+                },
+            }]
+        );
+        assert_eq!(
+            table.check_arr,
+            vec![
+                OneCheck {
+                    name: Some("chk_amount".to_string()),
+                    expr: "`amount` > 0".to_string(),
+                    enforced: true,
+                },
+                OneCheck {
+                    name: None,
+                    expr: "`amount` < 1000000".to_string(),
+                    enforced: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_create_temporary_table() {
+        assert_eq!(
+            parse_create_table("CREATE TEMPORARY TABLE IF NOT EXISTS `tmp_user` ("),
+            Ok(("(", "tmp_user".to_string()))
+        );
+        let input = "SET NAMES utf8mb4;
+create temporary table tmp_user (id bigint NOT NULL, PRIMARY KEY (id));
+";
+        let (_, schema) = parse_schema_file(input).unwrap();
+        assert_eq!(schema.table_arr.len(), 1);
+        assert_eq!(schema.table_arr[0].table_name, "tmp_user");
+        assert_eq!(schema.table_line_arr, vec![2]);
+        assert_eq!(schema.skipped_arr.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_create_sql_without_index() {
+        let sql = "CREATE TABLE `t` (
+  `id` int NOT NULL PRIMARY KEY,
+  `name` varchar(20) DEFAULT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_bin COMMENT='';";
+        let (_, table) = parse_create_sql(sql).unwrap();
+        assert_eq!(table.column_arr.len(), 2);
+        assert!(table.index_arr.is_empty());
+        assert!(table.column_arr[0].primary_key);
+    }
+
+    #[test]
+    fn test_parse_default_value() {
+        assert_eq!(
+            parse_default(" default -1"),
+            Ok(("", DefaultEnum::DefaultInt(-1)))
+        );
+        assert_eq!(
+            parse_default(" DEFAULT 0.00"),
+            Ok(("", DefaultEnum::DefaultFloat("0.00".to_string())))
+        );
+        assert_eq!(
+            parse_default(" DEFAULT (uuid())"),
+            Ok(("", DefaultEnum::DefaultExpr("uuid()".to_string())))
+        );
+        assert_eq!(
+            parse_default(" DEFAULT b'0'"),
+            Ok(("", DefaultEnum::DefaultExpr("b'0'".to_string())))
+        );
+        assert_eq!(
+            parse_default(" DEFAULT 'it''s'"),
+            Ok(("", DefaultEnum::DefaultStr("it's".to_string())))
+        );
+        assert_eq!(
+            parse_default(" DEFAULT TRUE"),
+            Ok(("", DefaultEnum::DefaultBool(true)))
+        );
+        assert_eq!(
+            parse_default(" DEFAULT CURRENT_TIMESTAMP(3)"),
+            Ok(("", DefaultEnum::DefaultCurStamp))
+        );
     }
 
     #[test]
@@ -982,12 +3214,78 @@ mod tests {
                     name: String::from("user_name_idx"),
                     using_type: Some("BTREE".to_string()),
                     typ: IndexIdxTyeEnum::Unique,
-                    column_names: vec!["user_name".to_string()],
+                    key_parts: vec![OneKeyPart::new("user_name")],
+                    option: IndexOption::default(),
                 })
             ))
         );
     }
 
+    #[test]
+    fn test_parse_idx_line2_unique_without_key() {
+        let (remain, line) = parse_idx_line2("UNIQUE uk (a)").unwrap();
+        assert_eq!(remain, "");
+        match line {
+            OneLineEnum::Index(idx) => {
+                assert_eq!(idx.name, "uk");
+                assert_eq!(idx.typ, IndexIdxTyeEnum::Unique);
+                assert_eq!(idx.key_parts, vec![OneKeyPart::new("a")]);
+            }
+            _ => panic!("expect index"),
+        }
+
+        let (_, table) = parse_create_sql("CREATE TABLE t (a int, UNIQUE uk (a))").unwrap();
+        assert_eq!(table.index_arr.len(), 1);
+        assert_eq!(table.index_arr[0].name, "uk");
+    }
+
+    #[test]
+    fn test_parse_idx_key_parts_and_option() {
+        let input = "KEY `idx_name` USING BTREE (`name`(20) DESC, (lower(`email`)), `age` ASC) KEY_BLOCK_SIZE=8 COMMENT '组合索引' INVISIBLE";
+        let (remain, line) = parse_idx_line2(input).unwrap();
+        assert_eq!(remain, "");
+        assert_eq!(
+            line,
+            OneLineEnum::Index(OneIndex {
+                name: "idx_name".to_string(),
+                using_type: Some("BTREE".to_string()),
+                typ: IndexIdxTyeEnum::Normal,
+                key_parts: vec![
+                    OneKeyPart {
+                        column_name: Some("name".to_string()),
+                        prefix_len: Some(20),
+                        order: Some(IndexOrderEnum::Desc),
+                        expr: None,
+                    },
+                    OneKeyPart {
+                        expr: Some("lower(`email`)".to_string()),
+                        ..Default::default()
+                    },
+                    OneKeyPart {
+                        order: Some(IndexOrderEnum::Asc),
+                        ..OneKeyPart::new("age")
+                    },
+                ],
+                option: IndexOption {
+                    key_block_size: Some(8),
+                    comment: Some("组合索引".to_string()),
+                    invisible: true,
+                    ..Default::default()
+                },
+            })
+        );
+
+        let (_, line) =
+            parse_idx_line2("FULLTEXT KEY `ft_content` (`content`) WITH PARSER ngram").unwrap();
+        match line {
+            OneLineEnum::Index(index) => {
+                assert_eq!(index.column_names(), vec!["content".to_string()]);
+                assert_eq!(index.option.parser, Some("ngram".to_string()));
+            }
+            _ => panic!("expect index"),
+        }
+    }
+
     #[test]
     fn test_parse_many_column_definition2() {
         let input = r##"(`id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '主键',
@@ -1013,11 +3311,26 @@ mod tests {
 `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '主键',
 PRIMARY KEY (`id`)
         )"##;
-        let result: Vec<OneLineEnum> = vec![OneLineEnum::Column(OneColumn {
-            name: "id".to_string(),
-            typ: DataTypeEnum::Bigint,
-            comment: "主键".to_string(),
-        })];
+        let result: Vec<OneLineEnum> = vec![
+            OneLineEnum::Column(OneColumn {
+                name: "id".to_string(),
+                typ: DataTypeEnum::Bigint(IntTypeOption {
+                    unsigned: true,
+                    ..Default::default()
+                }),
+                comment: "主键".to_string(),
+                nullable: false,
+                auto_increment: true,
+                ..Default::default()
+            }),
+            OneLineEnum::Index(OneIndex {
+                name: "".to_string(),
+                using_type: None,
+                typ: IndexIdxTyeEnum::Primary,
+                key_parts: vec![OneKeyPart::new("id")],
+                option: IndexOption::default(),
+            }),
+        ];
         assert_eq!(parse_many1_define_line(input), Ok(("", result)));
     }
 
@@ -1073,10 +3386,11 @@ PRIMARY KEY (`id`)
     #[test]
     fn test_parse_table_option() {
         let expect = TableOption {
-            engine: "InnoDB".to_string(),
-            charset: "utf8mb4".to_string(),
-            collate: "utf8mb4_bin".to_string(),
-            comment: "配置表".to_string(),
+            engine: Some("InnoDB".to_string()),
+            charset: Some("utf8mb4".to_string()),
+            collate: Some("utf8mb4_bin".to_string()),
+            comment: Some("配置表".to_string()),
+            ..Default::default()
         };
         assert_eq!(
             parse_table_option(
@@ -1086,6 +3400,53 @@ PRIMARY KEY (`id`)
         );
     }
 
+    #[test]
+    fn test_parse_table_option_any_order() {
+        let (remain, option) = parse_table_option(
+            " COMMENT='日志' ROW_FORMAT=dynamic, AUTO_INCREMENT=1234 ENGINE=InnoDB KEY_BLOCK_SIZE=8 DATA DIRECTORY = '/data' DEFAULT CHARSET=utf8mb4;",
+        )
+        .unwrap();
+        assert_eq!(remain, ";");
+        let mut extra = BTreeMap::new();
+        extra.insert("KEY_BLOCK_SIZE".to_string(), "8".to_string());
+        extra.insert("DATA DIRECTORY".to_string(), "/data".to_string());
+        assert_eq!(
+            option,
+            TableOption {
+                engine: Some("InnoDB".to_string()),
+                charset: Some("utf8mb4".to_string()),
+                comment: Some("日志".to_string()),
+                auto_increment: Some(1234),
+                row_format: Some("DYNAMIC".to_string()),
+                extra,
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(parse_table_option(";"), Ok((";", TableOption::default())));
+
+        let (remain, option) = parse_table_option(
+            " ENGINE=InnoDB\n/*!50100 PARTITION BY RANGE (`year`)\n(PARTITION p0 VALUES LESS THAN (1991) ENGINE = InnoDB) */;",
+        )
+        .unwrap();
+        assert_eq!(remain, ";");
+        assert_eq!(
+            option.partition,
+            Some(
+                "PARTITION BY RANGE (`year`)\n(PARTITION p0 VALUES LESS THAN (1991) ENGINE = InnoDB)"
+                    .to_string()
+            )
+        );
+
+        let (remain, option) =
+            parse_table_option(" ENGINE=InnoDB PARTITION BY HASH (id) PARTITIONS 4;").unwrap();
+        assert_eq!(remain, ";");
+        assert_eq!(
+            option.partition,
+            Some("PARTITION BY HASH (id) PARTITIONS 4".to_string())
+        );
+    }
+
     #[test]
     fn test_parse_create_sql() {
         let input = r###"CREATE TABLE `demo_table_user` (
@@ -1102,53 +3463,173 @@ PRIMARY KEY (`id`)
 ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_bin COMMENT='模板分类关联表';"###;
         let res = parse_create_sql(input);
         assert_eq!(
-            res.unwrap().1,
-            TableSchema {
-                table_name: "demo_table_user".to_string(),
-                column_arr: vec![
-                    OneColumn {
-                        name: "id".to_string(),
-                        typ: DataTypeEnum::Bigint,
-                        comment: "主键".to_string(),
-                    },
-                    OneColumn {
-                        name: "creator".to_string(),
-                        typ: DataTypeEnum::Bigint,
-                        comment: "创建人".to_string(),
-                    },
-                    OneColumn {
-                        name: "create_time".to_string(),
-                        typ: DataTypeEnum::DateTime(0,),
-                        comment: "创建时间".to_string(),
-                    },
-                    OneColumn {
-                        name: "updator".to_string(),
-                        typ: DataTypeEnum::Bigint,
-                        comment: "更新人".to_string(),
-                    },
-                    OneColumn {
-                        name: "update_time".to_string(),
-                        typ: DataTypeEnum::DateTime(0,),
-                        comment: "更新时间".to_string(),
-                    },
-                    OneColumn {
-                        name: "version".to_string(),
-                        typ: DataTypeEnum::Int,
-                        comment: "乐观锁".to_string(),
-                    },
-                    OneColumn {
-                        name: "del_flag".to_string(),
-                        typ: DataTypeEnum::TinyInt,
-                        comment: "是否删除,1是,2否".to_string(),
+            res.unwrap(),
+            (
+                "",
+                TableSchema {
+                    dialect: DialectEnum::MySql,
+                    schema_name: None,
+                    table_name: "demo_table_user".to_string(),
+                    column_arr: vec![
+                        OneColumn {
+                            name: "id".to_string(),
+                            typ: DataTypeEnum::Bigint(IntTypeOption::default()),
+                            comment: "主键".to_string(),
+                            nullable: false,
+                            ..Default::default()
+                        },
+                        OneColumn {
+                            name: "creator".to_string(),
+                            typ: DataTypeEnum::Bigint(IntTypeOption::default()),
+                            comment: "创建人".to_string(),
+                            nullable: false,
+                            default: DefaultEnum::DefaultStr("0".to_string()),
+                            ..Default::default()
+                        },
+                        OneColumn {
+                            name: "create_time".to_string(),
+                            typ: DataTypeEnum::DateTime(0,),
+                            comment: "创建时间".to_string(),
+                            nullable: false,
+                            default: DefaultEnum::DefaultCurStamp,
+                            ..Default::default()
+                        },
+                        OneColumn {
+                            name: "updator".to_string(),
+                            typ: DataTypeEnum::Bigint(IntTypeOption::default()),
+                            comment: "更新人".to_string(),
+                            nullable: false,
+                            default: DefaultEnum::DefaultStr("0".to_string()),
+                            ..Default::default()
+                        },
+                        OneColumn {
+                            name: "update_time".to_string(),
+                            typ: DataTypeEnum::DateTime(0,),
+                            comment: "更新时间".to_string(),
+                            nullable: false,
+                            default: DefaultEnum::DefaultCurStamp,
+                            on_update_cur_stamp: true,
+                            ..Default::default()
+                        },
+                        OneColumn {
+                            name: "version".to_string(),
+                            typ: DataTypeEnum::Int(IntTypeOption::default()),
+                            comment: "乐观锁".to_string(),
+                            nullable: false,
+                            default: DefaultEnum::DefaultStr("1".to_string()),
+                            ..Default::default()
+                        },
+                        OneColumn {
+                            name: "del_flag".to_string(),
+                            typ: DataTypeEnum::TinyInt(IntTypeOption::default()),
+                            comment: "是否删除,1是,2否".to_string(),
+                            nullable: false,
+                            default: DefaultEnum::DefaultStr("2".to_string()),
+                            ..Default::default()
+                        },
+                    ],
+                    index_arr: vec![
+                        OneIndex {
+                            name: "".to_string(),
+                            using_type: Some("BTREE".to_string()),
+                            typ: IndexIdxTyeEnum::Primary,
+                            key_parts: vec![OneKeyPart::new("id")],
+                            option: IndexOption::default(),
+                        },
+                        OneIndex {
+                            name: "relate_idx".to_string(),
+                            using_type: Some("BTREE".to_string()),
+                            typ: IndexIdxTyeEnum::Normal,
+                            key_parts: vec![OneKeyPart::new("relate_id")],
+                            option: IndexOption::default(),
+                        },
+                        OneIndex {
+                            name: "tpl_id_idx".to_string(),
+                            using_type: Some("BTREE".to_string()),
+                            typ: IndexIdxTyeEnum::Normal,
+                            key_parts: vec![OneKeyPart::new("tpl_id")],
+                            option: IndexOption::default(),
+                        },
+                    ],
+                    foreign_key_arr: vec![],
+                    check_arr: vec![],
+                    option: TableOption {
+                        engine: Some("InnoDB".to_string()),
+                        charset: Some("utf8mb4".to_string()),
+                        collate: Some("utf8mb4_bin".to_string()),
+                        comment: Some("模板分类关联表".to_string()),
+                        ..Default::default()
                     },
-                ],
-                option: TableOption {
-                    engine: "InnoDB".to_string(),
-                    charset: "utf8mb4".to_string(),
-                    collate: "utf8mb4_bin".to_string(),
-                    comment: "模板分类关联表".to_string(),
-                },
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_other_statement() {
+        assert_eq!(
+            parse_other_statement("DROP TABLE IF EXISTS `user`;\nCREATE"),
+            Ok(("\nCREATE", "DROP TABLE IF EXISTS `user`".to_string()))
+        );
+        assert_eq!(
+            parse_other_statement("/*!40101 SET NAMES utf8mb4 */;"),
+            Ok(("", "/*!40101 SET NAMES utf8mb4 */".to_string()))
+        );
+        assert_eq!(
+            parse_other_statement("SET @a = ';' ;"),
+            Ok(("", "SET @a = ';'".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_schema_file() {
+        let input = r###"-- MySQL dump 10.13  Distrib 8.0.26, for Linux (x86_64)
+--
+-- Host: localhost    Database: demo
+-- ------------------------------------------------------
+
+/*!40101 SET @OLD_CHARACTER_SET_CLIENT=@@CHARACTER_SET_CLIENT */;
+/*!40101 SET NAMES utf8mb4 */;
+
+--
+-- Table structure for table `user`
+--
+
+DROP TABLE IF EXISTS `user`;
+/*!40101 SET @saved_cs_client     = @@character_set_client */;
+CREATE TABLE `user` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '主键',
+  `user_name` varchar(120) NOT NULL DEFAULT '' COMMENT '用户名',
+  PRIMARY KEY (`id`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_bin COMMENT='用户表';
+/*!40101 SET character_set_client = @saved_cs_client */;
+
+DROP TABLE IF EXISTS `role`;
+CREATE TABLE `role` (
+  `id` int NOT NULL COMMENT '主键',
+  PRIMARY KEY (`id`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_bin COMMENT='角色表';
+"###;
+        let (remain, schema_file) = parse_schema_file(input).unwrap();
+        assert_eq!(remain, "");
+        let table_name_arr: Vec<&str> = schema_file
+            .table_arr
+            .iter()
+            .map(|table| table.table_name.as_str())
+            .collect();
+        assert_eq!(table_name_arr, vec!["user", "role"]);
+        assert_eq!(schema_file.table_line_arr, vec![15, 23]);
+        assert_eq!(schema_file.table_arr[0].column_arr.len(), 2);
+        assert_eq!(schema_file.skipped_arr.len(), 6);
+        assert_eq!(
+            schema_file.skipped_arr[2],
+            SkippedStatement {
+                line: 13,
+                statement: "DROP TABLE IF EXISTS `user`".to_string(),
             }
         );
+
+        let input = "SET NAMES utf8mb4;\nCREATE TABLE `user` (\n  `id` bigint NOT NULL,\n  `name` varchar(\n);\n";
+        assert!(matches!(parse_schema_file(input), Err(Err::Failure(_))));
     }
 }
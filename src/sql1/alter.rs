@@ -0,0 +1,1047 @@
+//! 解析 MySQL 的 `ALTER TABLE` 语句，并将其应用到已解析的 `TableSchema` 上
+//!
+//! 表结构通常由一份建表语句加上若干迁移文件组成，将迁移中的 `ALTER TABLE` 依次应用到建表语句的解析结果上，
+//! 就能得到当前实际生效的表结构，渲染器再基于它生成代码
+use super::*;
+use nom::multi::separated_list1;
+
+/// 新增、修改字段时指定的字段位置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnPositionEnum {
+    /// FIRST
+    First,
+    /// AFTER col_name
+    After(String),
+}
+
+/// `ALTER TABLE` 中的一项修改
+#[derive(Debug, PartialEq, Eq)]
+pub enum AlterSpecEnum {
+    /// ADD [COLUMN] col_name column_definition [FIRST | AFTER col_name]
+    AddColumn(OneColumn, Option<ColumnPositionEnum>),
+    /// MODIFY [COLUMN] col_name column_definition [FIRST | AFTER col_name]
+    ModifyColumn(OneColumn, Option<ColumnPositionEnum>),
+    /// CHANGE [COLUMN] old_col_name new_col_name column_definition [FIRST | AFTER col_name]
+    ChangeColumn(String, OneColumn, Option<ColumnPositionEnum>),
+    /// RENAME COLUMN old_col_name TO new_col_name
+    RenameColumn(String, String),
+    /// DROP [COLUMN] col_name
+    DropColumn(String),
+    /// ALTER [COLUMN] col_name {SET DEFAULT {literal | (expr)} | DROP DEFAULT}，删除默认值时为 `DefaultNone`
+    AlterColumnDefault(String, DefaultEnum),
+    /// ALTER [COLUMN] col_name SET {VISIBLE | INVISIBLE}
+    AlterColumnVisible(String, bool),
+    /// ADD {INDEX | KEY | PRIMARY KEY | UNIQUE | FULLTEXT | SPATIAL} ...
+    AddIndex(OneIndex),
+    /// DROP {INDEX | KEY} index_name
+    DropIndex(String),
+    /// DROP PRIMARY KEY
+    DropPrimaryKey,
+    /// RENAME {INDEX | KEY} old_index_name TO new_index_name
+    RenameIndex(String, String),
+    /// ALTER INDEX index_name {VISIBLE | INVISIBLE}
+    AlterIndexVisible(String, bool),
+    /// ADD [CONSTRAINT [symbol]] FOREIGN KEY ...
+    AddForeignKey(OneForeignKey),
+    /// DROP FOREIGN KEY fk_symbol
+    DropForeignKey(String),
+    /// ADD [CONSTRAINT [symbol]] CHECK (expr) [[NOT] ENFORCED]
+    AddCheck(OneCheck),
+    /// DROP {CHECK | CONSTRAINT} symbol
+    DropConstraint(String),
+    /// RENAME [TO | AS] new_tbl_name
+    RenameTable(String),
+    /// CONVERT TO CHARACTER SET charset_name [COLLATE collation_name]
+    ConvertCharset(String, Option<String>),
+    /// 表配置，如：`ENGINE=InnoDB COMMENT='xxx'`，只有声明了的配置会被修改
+    TableOption(TableOption),
+}
+
+/// 一条 `ALTER TABLE` 语句
+#[derive(Debug, PartialEq, Eq)]
+pub struct AlterTable {
+    /// 表名
+    pub table_name: String,
+    /// 修改项，按声明顺序排列
+    pub spec_arr: Vec<AlterSpecEnum>,
+}
+
+/// 一条 `DROP TABLE` 语句
+#[derive(Debug, PartialEq, Eq)]
+pub struct DropTable {
+    /// 是否带有 `IF EXISTS`，带有时删除不存在的表不会出错
+    pub if_exists: bool,
+    /// 删除的表名
+    pub table_name_arr: Vec<String>,
+}
+
+/// 解析 `[COLUMN] `，`COLUMN` 关键字可以省略
+fn opt_column_keyword(input: &str) -> IResult<&str, ()> {
+    match opt(tuple((sql_keyword("column"), multispace1)))(input) {
+        Ok((remain, _)) => Ok((remain, ())),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析字段位置，如：` FIRST`、` AFTER name`，位置前面必须有空白
+pub fn parse_column_position(input: &str) -> IResult<&str, ColumnPositionEnum> {
+    preceded(
+        multispace1,
+        alt((
+            map(sql_keyword("first"), |_| ColumnPositionEnum::First),
+            map(
+                tuple((sql_keyword("after"), multispace1, sql_identifier)),
+                |(_, _, name)| ColumnPositionEnum::After(name),
+            ),
+        )),
+    )(input)
+}
+
+/// 解析 ADD 开头的修改项，包括新增字段、索引、外键以及 check 约束
+fn parse_alter_add(input: &str) -> IResult<&str, Vec<AlterSpecEnum>> {
+    let add_index = map(parse_idx_line2, |line| match line {
+        OneLineEnum::Index(index) => vec![AlterSpecEnum::AddIndex(index)],
+        _ => vec![],
+    });
+    let add_foreign_key = map(parse_foreign_key, |foreign_key| {
+        vec![AlterSpecEnum::AddForeignKey(foreign_key)]
+    });
+    let add_check = map(parse_check, |check| vec![AlterSpecEnum::AddCheck(check)]);
+    // ADD [COLUMN] (col_name column_definition,...)
+    let add_column_list = map(
+        tuple((
+            opt(tuple((sql_keyword("column"), multispace0))),
+            tag("("),
+            multispace0,
+            separated_list1(
+                tuple((multispace0, tag(","), multispace0)),
+                parse_column_definition,
+            ),
+            multispace0,
+            tag(")"),
+        )),
+        |(_, _, _, column_arr, _, _)| {
+            column_arr
+                .into_iter()
+                .map(|column| AlterSpecEnum::AddColumn(column, None))
+                .collect()
+        },
+    );
+    let add_column = map(
+        tuple((
+            opt_column_keyword,
+            parse_column_definition,
+            opt(parse_column_position),
+        )),
+        |(_, column, position)| vec![AlterSpecEnum::AddColumn(column, position)],
+    );
+    // 字段声明中的类型解析失败时不会回溯，因此要先尝试索引、约束
+    match tuple((
+        sql_keyword("add"),
+        multispace1,
+        alt((
+            add_index,
+            add_foreign_key,
+            add_check,
+            add_column_list,
+            add_column,
+        )),
+    ))(input)
+    {
+        Ok((remain, (_, _, spec_arr))) => Ok((remain, spec_arr)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析 DROP 开头的修改项
+fn parse_alter_drop(input: &str) -> IResult<&str, AlterSpecEnum> {
+    let key_or_index = alt((sql_keyword("index"), sql_keyword("key")));
+    let drop_item = alt((
+        map(
+            tuple((sql_keyword("primary"), multispace1, sql_keyword("key"))),
+            |_| AlterSpecEnum::DropPrimaryKey,
+        ),
+        map(
+            tuple((key_or_index, multispace1, sql_identifier)),
+            |(_, _, name)| AlterSpecEnum::DropIndex(name),
+        ),
+        map(
+            tuple((
+                sql_keyword("foreign"),
+                multispace1,
+                sql_keyword("key"),
+                multispace1,
+                sql_identifier,
+            )),
+            |(_, _, _, _, name)| AlterSpecEnum::DropForeignKey(name),
+        ),
+        map(
+            tuple((
+                alt((sql_keyword("check"), sql_keyword("constraint"))),
+                multispace1,
+                sql_identifier,
+            )),
+            |(_, _, name)| AlterSpecEnum::DropConstraint(name),
+        ),
+        map(
+            preceded(opt_column_keyword, sql_identifier),
+            AlterSpecEnum::DropColumn,
+        ),
+    ));
+    match tuple((sql_keyword("drop"), multispace1, drop_item))(input) {
+        Ok((remain, (_, _, spec))) => Ok((remain, spec)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析 MODIFY、CHANGE 开头的修改项
+fn parse_alter_modify(input: &str) -> IResult<&str, AlterSpecEnum> {
+    let modify = map(
+        tuple((
+            sql_keyword("modify"),
+            multispace1,
+            opt_column_keyword,
+            parse_column_definition,
+            opt(parse_column_position),
+        )),
+        |(_, _, _, column, position)| AlterSpecEnum::ModifyColumn(column, position),
+    );
+    let change = map(
+        tuple((
+            sql_keyword("change"),
+            multispace1,
+            opt_column_keyword,
+            sql_identifier,
+            multispace1,
+            parse_column_definition,
+            opt(parse_column_position),
+        )),
+        |(_, _, _, old_name, _, column, position)| {
+            AlterSpecEnum::ChangeColumn(old_name, column, position)
+        },
+    );
+    alt((modify, change))(input)
+}
+
+/// 解析 ALTER 开头的修改项，如：`ALTER COLUMN status SET DEFAULT 1`、`ALTER INDEX idx_name INVISIBLE`
+fn parse_alter_alter(input: &str) -> IResult<&str, AlterSpecEnum> {
+    let visible = || alt((sql_keyword("visible"), sql_keyword("invisible")));
+    let alter_index = map(
+        tuple((
+            sql_keyword("index"),
+            multispace1,
+            sql_identifier,
+            multispace1,
+            visible(),
+        )),
+        |(_, _, name, _, flag)| {
+            AlterSpecEnum::AlterIndexVisible(name, flag.eq_ignore_ascii_case("visible"))
+        },
+    );
+    let column_action = alt((
+        map(preceded(sql_keyword("set"), parse_default), Ok),
+        map(
+            tuple((sql_keyword("drop"), multispace1, sql_keyword("default"))),
+            |_| Ok(DefaultEnum::DefaultNone),
+        ),
+        map(
+            tuple((sql_keyword("set"), multispace1, visible())),
+            |(_, _, flag)| Err(flag.eq_ignore_ascii_case("visible")),
+        ),
+    ));
+    let alter_column = map(
+        tuple((
+            opt_column_keyword,
+            sql_identifier,
+            multispace1,
+            column_action,
+        )),
+        |(_, name, _, action)| match action {
+            Ok(default) => AlterSpecEnum::AlterColumnDefault(name, default),
+            Err(visible) => AlterSpecEnum::AlterColumnVisible(name, visible),
+        },
+    );
+    match tuple((
+        sql_keyword("alter"),
+        multispace1,
+        alt((alter_index, alter_column)),
+    ))(input)
+    {
+        Ok((remain, (_, _, spec))) => Ok((remain, spec)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析 RENAME 开头的修改项，包括重命名字段、索引以及表
+fn parse_alter_rename(input: &str) -> IResult<&str, AlterSpecEnum> {
+    let rename_pair = || {
+        tuple((
+            sql_identifier,
+            multispace1,
+            sql_keyword("to"),
+            multispace1,
+            sql_identifier,
+        ))
+    };
+    let rename_item = alt((
+        map(
+            preceded(tuple((sql_keyword("column"), multispace1)), rename_pair()),
+            |(old_name, _, _, _, new_name)| AlterSpecEnum::RenameColumn(old_name, new_name),
+        ),
+        map(
+            preceded(
+                tuple((alt((sql_keyword("index"), sql_keyword("key"))), multispace1)),
+                rename_pair(),
+            ),
+            |(old_name, _, _, _, new_name)| AlterSpecEnum::RenameIndex(old_name, new_name),
+        ),
+        map(
+            preceded(
+                opt(tuple((
+                    alt((sql_keyword("to"), sql_keyword("as"))),
+                    multispace1,
+                ))),
+                sql_identifier,
+            ),
+            AlterSpecEnum::RenameTable,
+        ),
+    ));
+    match tuple((sql_keyword("rename"), multispace1, rename_item))(input) {
+        Ok((remain, (_, _, spec))) => Ok((remain, spec)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析 `CONVERT TO CHARACTER SET utf8mb4 COLLATE utf8mb4_bin`
+fn parse_alter_convert(input: &str) -> IResult<&str, AlterSpecEnum> {
+    let charset_keyword = alt((
+        recognize(tuple((
+            sql_keyword("character"),
+            multispace1,
+            sql_keyword("set"),
+        ))),
+        sql_keyword("charset"),
+    ));
+    let mut parser = tuple((
+        sql_keyword("convert"),
+        multispace1,
+        sql_keyword("to"),
+        multispace1,
+        charset_keyword,
+        multispace1,
+        sql_identifier,
+        opt(tuple((
+            multispace1,
+            sql_keyword("collate"),
+            multispace1,
+            sql_identifier,
+        ))),
+    ));
+    match parser(input) {
+        Ok((remain, (_, _, _, _, _, _, charset, collate))) => Ok((
+            remain,
+            AlterSpecEnum::ConvertCharset(charset, collate.map(|(_, _, _, name)| name)),
+        )),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析修改项中的表配置，至少要有一项配置
+fn parse_alter_table_option(input: &str) -> IResult<&str, AlterSpecEnum> {
+    match parse_table_option(input) {
+        Ok((remain, option)) if option != TableOption::default() => {
+            Ok((remain, AlterSpecEnum::TableOption(option)))
+        }
+        Ok(_) => Err(Err::Error(VerboseError::from_error_kind(
+            input,
+            ErrorKind::Alt,
+        ))),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析一个修改项。`ADD COLUMN (a int, b int)` 会被拆分成多个修改项
+pub fn parse_alter_spec(input: &str) -> IResult<&str, Vec<AlterSpecEnum>> {
+    context(
+        "alter specification",
+        alt((
+            parse_alter_add,
+            map(
+                alt((
+                    parse_alter_drop,
+                    parse_alter_modify,
+                    parse_alter_alter,
+                    parse_alter_rename,
+                    parse_alter_convert,
+                    parse_alter_table_option,
+                )),
+                |spec| vec![spec],
+            ),
+        )),
+    )(input)
+}
+
+/// 解析 `ALTER TABLE` 语句的开头，返回表名。如：ALTER TABLE `user`
+pub fn parse_alter_table(input: &str) -> IResult<&str, String> {
+    let mut parser = tuple((
+        sql_keyword("alter"),
+        multispace1,
+        opt(tuple((sql_keyword("online"), multispace1))),
+        opt(tuple((sql_keyword("ignore"), multispace1))),
+        sql_keyword("table"),
+        multispace1,
+        sql_identifier,
+        multispace1,
+    ));
+    match parser(input) {
+        Ok((remain, (_, _, _, _, _, _, table_name, _))) => Ok((remain, table_name)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析整个 `ALTER TABLE` 语句，多个修改项以逗号分隔
+/// 如：ALTER TABLE `user` ADD COLUMN `age` int NOT NULL DEFAULT 0 AFTER `name`, DROP INDEX `idx_name`;
+pub fn parse_alter_sql(input: &str) -> IResult<&str, AlterTable> {
+    // 索引声明会连同结尾的逗号、空白一起解析，因此修改项之间的分隔符都是可选的
+    let spec_line = tuple((multispace0, parse_alter_spec, multispace0, opt(tag(","))));
+    let mut parser = context(
+        "alter table",
+        tuple((
+            parse_alter_table,
+            cut(many1(spec_line)),
+            opt(tuple((multispace0, tag(";")))),
+        )),
+    );
+    match parser(input) {
+        Ok((remain, (table_name, spec_line_arr, _))) => Ok((
+            remain,
+            AlterTable {
+                table_name,
+                spec_arr: spec_line_arr
+                    .into_iter()
+                    .flat_map(|(_, spec_arr, _, _)| spec_arr)
+                    .collect(),
+            },
+        )),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析 `DROP TABLE` 语句，如：DROP TABLE IF EXISTS `user`, `role`;
+pub fn parse_drop_table_sql(input: &str) -> IResult<&str, DropTable> {
+    let mut parser = context(
+        "drop table",
+        tuple((
+            sql_keyword("drop"),
+            multispace1,
+            opt(tuple((sql_keyword("temporary"), multispace1))),
+            sql_keyword("table"),
+            multispace1,
+            opt(tuple((
+                sql_keyword("if"),
+                multispace1,
+                sql_keyword("exists"),
+                multispace1,
+            ))),
+            separated_list1(tuple((multispace0, tag(","), multispace0)), sql_identifier),
+            opt(tuple((
+                multispace1,
+                alt((sql_keyword("restrict"), sql_keyword("cascade"))),
+            ))),
+            opt(tuple((multispace0, tag(";")))),
+        )),
+    );
+    match parser(input) {
+        Ok((remain, (_, _, _, _, _, if_exists, table_name_arr, _, _))) => Ok((
+            remain,
+            DropTable {
+                if_exists: if_exists.is_some(),
+                table_name_arr,
+            },
+        )),
+        Err(err) => Err(err),
+    }
+}
+
+/// 建表语句是否带有 `IF NOT EXISTS`，如：`CREATE TABLE IF NOT EXISTS user (...)`
+fn is_create_if_not_exists(statement: &str) -> bool {
+    let word_arr: Vec<String> = statement
+        .split_whitespace()
+        .take(6)
+        .map(|word| word.to_lowercase())
+        .filter(|word| word != "temporary")
+        .collect();
+    word_arr.len() >= 5 && word_arr[2..5] == ["if", "not", "exists"]
+}
+
+impl TableSchema {
+    /// 按名字查找字段的位置，MySQL 的字段名不区分大小写
+    fn column_pos(&self, name: &str) -> Option<usize> {
+        self.column_arr
+            .iter()
+            .position(|column| column.name.eq_ignore_ascii_case(name))
+    }
+
+    /// 按名字查找字段的位置，找不到时返回错误
+    fn existing_column_pos(&self, name: &str) -> Result<usize, String> {
+        self.column_pos(name).ok_or(format!(
+            "unknown column `{}` in table `{}`",
+            name, self.table_name
+        ))
+    }
+
+    /// 按名字查找索引的位置，索引名不区分大小写
+    fn index_pos(&self, name: &str) -> Option<usize> {
+        self.index_arr
+            .iter()
+            .position(|index| index.name.eq_ignore_ascii_case(name))
+    }
+
+    /// 将字段放到指定的位置，没有指定位置时放到 `default_pos`
+    fn insert_column(
+        &mut self,
+        column: OneColumn,
+        position: Option<ColumnPositionEnum>,
+        default_pos: usize,
+    ) -> Result<(), String> {
+        let pos = match position {
+            None => default_pos,
+            Some(ColumnPositionEnum::First) => 0,
+            Some(ColumnPositionEnum::After(name)) => self.existing_column_pos(&name)? + 1,
+        };
+        self.column_arr.insert(pos, column);
+        Ok(())
+    }
+
+    /// 字段改名后，同步修改索引、外键中的字段名
+    fn rename_column_ref(&mut self, old_name: &str, new_name: &str) {
+        for index in self.index_arr.iter_mut() {
+            for part in index.key_parts.iter_mut() {
+                if matches!(&part.column_name, Some(name) if name.eq_ignore_ascii_case(old_name)) {
+                    part.column_name = Some(new_name.to_string());
+                }
+            }
+        }
+        for foreign_key in self.foreign_key_arr.iter_mut() {
+            for name in foreign_key.column_names.iter_mut() {
+                if name.eq_ignore_ascii_case(old_name) {
+                    *name = new_name.to_string();
+                }
+            }
+        }
+    }
+
+    /// 将一条 `ALTER TABLE` 语句应用到表结构上
+    /// 某一项修改失败时返回错误，表结构保持不变
+    pub fn apply_alter(&mut self, alter: AlterTable) -> Result<(), String> {
+        if !alter.table_name.eq_ignore_ascii_case(&self.table_name) {
+            return Err(format!(
+                "alter table `{}` can not be applied to table `{}`",
+                alter.table_name, self.table_name
+            ));
+        }
+        let mut table = self.clone();
+        for spec in alter.spec_arr {
+            table.apply_alter_spec(spec)?;
+        }
+        *self = table;
+        Ok(())
+    }
+
+    /// 将一项修改应用到表结构上
+    pub fn apply_alter_spec(&mut self, spec: AlterSpecEnum) -> Result<(), String> {
+        match spec {
+            AlterSpecEnum::AddColumn(column, position) => {
+                if self.column_pos(&column.name).is_some() {
+                    return Err(format!("duplicate column name `{}`", column.name));
+                }
+                let default_pos = self.column_arr.len();
+                self.insert_column(column, position, default_pos)?;
+            }
+            AlterSpecEnum::ModifyColumn(column, position) => {
+                let pos = self.existing_column_pos(&column.name)?;
+                self.column_arr.remove(pos);
+                self.insert_column(column, position, pos)?;
+                self.mark_primary_key_not_null();
+            }
+            AlterSpecEnum::ChangeColumn(old_name, column, position) => {
+                let pos = self.existing_column_pos(&old_name)?;
+                if !old_name.eq_ignore_ascii_case(&column.name)
+                    && self.column_pos(&column.name).is_some()
+                {
+                    return Err(format!("duplicate column name `{}`", column.name));
+                }
+                self.rename_column_ref(&old_name, &column.name);
+                self.column_arr.remove(pos);
+                self.insert_column(column, position, pos)?;
+                self.mark_primary_key_not_null();
+            }
+            AlterSpecEnum::RenameColumn(old_name, new_name) => {
+                let pos = self.existing_column_pos(&old_name)?;
+                if !old_name.eq_ignore_ascii_case(&new_name) && self.column_pos(&new_name).is_some()
+                {
+                    return Err(format!("duplicate column name `{}`", new_name));
+                }
+                self.rename_column_ref(&old_name, &new_name);
+                self.column_arr[pos].name = new_name;
+            }
+            AlterSpecEnum::DropColumn(name) => {
+                let pos = self.existing_column_pos(&name)?;
+                let in_foreign_key = self.foreign_key_arr.iter().any(|foreign_key| {
+                    foreign_key
+                        .column_names
+                        .iter()
+                        .any(|column| column.eq_ignore_ascii_case(&name))
+                });
+                if in_foreign_key {
+                    return Err(format!(
+                        "column `{}` is needed in a foreign key constraint",
+                        name
+                    ));
+                }
+                self.column_arr.remove(pos);
+                // 索引中的该字段被移除，字段全部被移除的索引也随之删除
+                for index in self.index_arr.iter_mut() {
+                    index.key_parts.retain(|part| {
+                        !matches!(&part.column_name, Some(column) if column.eq_ignore_ascii_case(&name))
+                    });
+                }
+                self.index_arr.retain(|index| !index.key_parts.is_empty());
+            }
+            AlterSpecEnum::AlterColumnDefault(name, default) => {
+                let pos = self.existing_column_pos(&name)?;
+                self.column_arr[pos].default = default;
+            }
+            AlterSpecEnum::AlterColumnVisible(name, visible) => {
+                let pos = self.existing_column_pos(&name)?;
+                self.column_arr[pos].invisible = !visible;
+            }
+            AlterSpecEnum::AddIndex(mut index) => {
+                for column_name in index.column_names() {
+                    self.existing_column_pos(&column_name)?;
+                }
+                if index.typ == IndexIdxTyeEnum::Primary {
+                    if self
+                        .index_arr
+                        .iter()
+                        .any(|index| index.typ == IndexIdxTyeEnum::Primary)
+                    {
+                        return Err("multiple primary key defined".to_string());
+                    }
+                } else if index.name.is_empty() {
+                    // 没有指定索引名时，MySQL 以第一个字段名作为索引名，重名时加上数字后缀
+                    let base_name = index.column_names().first().cloned().unwrap_or_default();
+                    let mut name = base_name.clone();
+                    let mut suffix = 2;
+                    while self.index_pos(&name).is_some() {
+                        name = format!("{}_{}", base_name, suffix);
+                        suffix += 1;
+                    }
+                    index.name = name;
+                } else if self.index_pos(&index.name).is_some() {
+                    return Err(format!("duplicate key name `{}`", index.name));
+                }
+                self.index_arr.push(index);
+                self.mark_primary_key_not_null();
+            }
+            AlterSpecEnum::DropIndex(name) => {
+                let pos = self
+                    .index_pos(&name)
+                    .ok_or(format!("unknown index `{}`", name))?;
+                self.index_arr.remove(pos);
+            }
+            AlterSpecEnum::DropPrimaryKey => {
+                let pos = self
+                    .index_arr
+                    .iter()
+                    .position(|index| index.typ == IndexIdxTyeEnum::Primary);
+                match pos {
+                    Some(pos) => {
+                        self.index_arr.remove(pos);
+                    }
+                    None if self.column_arr.iter().any(|column| column.primary_key) => {}
+                    None => return Err("table has no primary key".to_string()),
+                }
+                for column in self.column_arr.iter_mut() {
+                    column.primary_key = false;
+                }
+            }
+            AlterSpecEnum::RenameIndex(old_name, new_name) => {
+                let pos = self
+                    .index_pos(&old_name)
+                    .ok_or(format!("unknown index `{}`", old_name))?;
+                if !old_name.eq_ignore_ascii_case(&new_name) && self.index_pos(&new_name).is_some()
+                {
+                    return Err(format!("duplicate key name `{}`", new_name));
+                }
+                self.index_arr[pos].name = new_name;
+            }
+            AlterSpecEnum::AlterIndexVisible(name, visible) => {
+                let pos = self
+                    .index_pos(&name)
+                    .ok_or(format!("unknown index `{}`", name))?;
+                self.index_arr[pos].option.invisible = !visible;
+            }
+            AlterSpecEnum::AddForeignKey(foreign_key) => {
+                for column_name in foreign_key.column_names.iter() {
+                    self.existing_column_pos(column_name)?;
+                }
+                self.foreign_key_arr.push(foreign_key);
+            }
+            AlterSpecEnum::DropForeignKey(name) => {
+                let pos = self
+                    .foreign_key_arr
+                    .iter()
+                    .position(|foreign_key| {
+                        matches!(&foreign_key.name, Some(fk_name) if fk_name.eq_ignore_ascii_case(&name))
+                    })
+                    .ok_or(format!("unknown foreign key `{}`", name))?;
+                self.foreign_key_arr.remove(pos);
+            }
+            AlterSpecEnum::AddCheck(check) => self.check_arr.push(check),
+            AlterSpecEnum::DropConstraint(name) => {
+                let is_name = |constraint: &Option<String>| matches!(constraint, Some(constraint) if constraint.eq_ignore_ascii_case(&name));
+                let constraint_count = |table: &TableSchema| {
+                    table.check_arr.len()
+                        + table.foreign_key_arr.len()
+                        + table.index_arr.len()
+                        + table
+                            .column_arr
+                            .iter()
+                            .map(|column| column.check_arr.len())
+                            .sum::<usize>()
+                };
+                let count = constraint_count(self);
+                self.check_arr.retain(|check| !is_name(&check.name));
+                for column in self.column_arr.iter_mut() {
+                    column.check_arr.retain(|check| !is_name(&check.name));
+                }
+                self.foreign_key_arr
+                    .retain(|foreign_key| !is_name(&foreign_key.name));
+                self.index_arr.retain(|index| {
+                    !(index.typ == IndexIdxTyeEnum::Unique
+                        && index.name.eq_ignore_ascii_case(&name))
+                });
+                if constraint_count(self) == count {
+                    return Err(format!("unknown constraint `{}`", name));
+                }
+            }
+            AlterSpecEnum::RenameTable(name) => self.table_name = name,
+            AlterSpecEnum::ConvertCharset(charset, collate) => {
+                // 已经单独声明了字符集的字段也会被转换
+                for column in self.column_arr.iter_mut() {
+                    if column.charset.is_some() {
+                        column.charset = Some(charset.clone());
+                        column.collate = collate.clone();
+                    }
+                }
+                self.option.charset = Some(charset);
+                self.option.collate = collate;
+            }
+            AlterSpecEnum::TableOption(option) => {
+                let TableOption {
+                    engine,
+                    charset,
+                    collate,
+                    comment,
+                    auto_increment,
+                    row_format,
+                    partition,
+                    extra,
+                } = option;
+                self.option.engine = engine.or_else(|| self.option.engine.take());
+                self.option.charset = charset.or_else(|| self.option.charset.take());
+                self.option.collate = collate.or_else(|| self.option.collate.take());
+                self.option.comment = comment.or_else(|| self.option.comment.take());
+                self.option.auto_increment =
+                    auto_increment.or_else(|| self.option.auto_increment.take());
+                self.option.row_format = row_format.or_else(|| self.option.row_format.take());
+                self.option.partition = partition.or_else(|| self.option.partition.take());
+                self.option.extra.extend(extra);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SchemaFile {
+    /// 按名字查找表的位置，MySQL 的表名在大多数平台上不区分大小写
+    fn table_pos(&self, name: &str) -> Option<usize> {
+        self.table_arr
+            .iter()
+            .position(|table| table.table_name.eq_ignore_ascii_case(name))
+    }
+
+    /// 将一个迁移文件应用到 schema 上
+    /// 文件中的 `CREATE TABLE` 会新增表，`ALTER TABLE` 会修改同名的表，`DROP TABLE` 会删除表，
+    /// 其余的语句被跳过并记录下来。新增已经存在的表时出错，带有 `IF NOT EXISTS` 时忽略
+    /// 出错时返回可读的错误信息，已经应用的语句不会回滚
+    pub fn apply_migration(&mut self, input: &str) -> Result<(), String> {
+        let mut remain = input;
+        loop {
+            remain = match parse_space_or_comment(remain) {
+                Ok((next, _)) => next,
+                Err(err) => return Err(format_parse_error(input, &err)),
+            };
+            if remain.is_empty() {
+                break;
+            }
+            let line = input[..input.len() - remain.len()].matches('\n').count() + 1;
+            match parse_create_sql(remain) {
+                Ok((next, table)) => {
+                    if self.table_pos(&table.table_name).is_none() {
+                        self.table_arr.push(table);
+                        self.table_line_arr.push(line);
+                    } else if !is_create_if_not_exists(remain) {
+                        return Err(format!(
+                            "line {}: table `{}` already exists",
+                            line, table.table_name
+                        ));
+                    }
+                    remain = next;
+                    continue;
+                }
+                Err(err @ Err::Failure(_)) => return Err(format_parse_error(input, &err)),
+                Err(_) => {}
+            }
+            if let Ok((next, drop)) = parse_drop_table_sql(remain) {
+                for table_name in drop.table_name_arr.iter() {
+                    match self.table_pos(table_name) {
+                        Some(pos) => {
+                            self.table_arr.remove(pos);
+                            if pos < self.table_line_arr.len() {
+                                self.table_line_arr.remove(pos);
+                            }
+                        }
+                        None if drop.if_exists => {}
+                        None => {
+                            return Err(format!("line {}: unknown table `{}`", line, table_name))
+                        }
+                    }
+                }
+                remain = next;
+                continue;
+            }
+            match parse_alter_sql(remain) {
+                Ok((next, alter)) => {
+                    let pos = self.table_pos(&alter.table_name).ok_or(format!(
+                        "line {}: unknown table `{}`",
+                        line, alter.table_name
+                    ))?;
+                    self.table_arr[pos]
+                        .apply_alter(alter)
+                        .map_err(|err| format!("line {}: {}", line, err))?;
+                    remain = next;
+                    continue;
+                }
+                Err(err @ Err::Failure(_)) => return Err(format_parse_error(input, &err)),
+                Err(_) => {}
+            }
+            let (next, statement) = match parse_other_statement(remain) {
+                Ok(res) => res,
+                Err(err) => return Err(format_parse_error(input, &err)),
+            };
+            if !statement.is_empty() {
+                self.skipped_arr.push(SkippedStatement { line, statement });
+            }
+            remain = next;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn demo_table() -> TableSchema {
+        let sql = r#"CREATE TABLE `user` (
+  `id` bigint(20) unsigned NOT NULL AUTO_INCREMENT,
+  `name` varchar(64) NOT NULL DEFAULT '' COMMENT '姓名',
+  `email` varchar(128) NOT NULL DEFAULT '',
+  `status` tinyint(4) NOT NULL DEFAULT 0,
+  PRIMARY KEY (`id`),
+  KEY `idx_name_email` (`name`, `email`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='用户表';"#;
+        parse_create_sql(sql).unwrap().1
+    }
+
+    #[test]
+    fn test_parse_alter_sql() {
+        let sql = "ALTER TABLE `user` ADD COLUMN `age` int NOT NULL DEFAULT 0 AFTER `name`,
+  ADD UNIQUE KEY `uk_email` (`email`),
+  MODIFY `status` tinyint(1) NOT NULL DEFAULT 1 COMMENT '状态' FIRST,
+  CHANGE COLUMN `name` `nickname` varchar(32) NOT NULL,
+  DROP INDEX `idx_name`,
+  ALTER COLUMN `email` DROP DEFAULT,
+  RENAME TO `member`,
+  ENGINE=InnoDB;";
+        let (remain, alter) = parse_alter_sql(sql).unwrap();
+        assert_eq!(remain, "");
+        assert_eq!(alter.table_name, "user");
+        assert_eq!(alter.spec_arr.len(), 8);
+        assert!(matches!(
+            &alter.spec_arr[0],
+            AlterSpecEnum::AddColumn(column, Some(ColumnPositionEnum::After(after)))
+                if column.name == "age" && after == "name"
+        ));
+        assert!(matches!(
+            &alter.spec_arr[1],
+            AlterSpecEnum::AddIndex(index) if index.typ == IndexIdxTyeEnum::Unique
+        ));
+        assert!(matches!(
+            &alter.spec_arr[2],
+            AlterSpecEnum::ModifyColumn(column, Some(ColumnPositionEnum::First))
+                if column.comment == "状态"
+        ));
+        assert!(matches!(
+            &alter.spec_arr[3],
+            AlterSpecEnum::ChangeColumn(old_name, column, None)
+                if old_name == "name" && column.name == "nickname"
+        ));
+        assert_eq!(
+            alter.spec_arr[4],
+            AlterSpecEnum::DropIndex("idx_name".to_string())
+        );
+        assert_eq!(
+            alter.spec_arr[5],
+            AlterSpecEnum::AlterColumnDefault("email".to_string(), DefaultEnum::DefaultNone)
+        );
+        assert_eq!(
+            alter.spec_arr[6],
+            AlterSpecEnum::RenameTable("member".to_string())
+        );
+        assert!(matches!(
+            &alter.spec_arr[7],
+            AlterSpecEnum::TableOption(option) if option.engine == Some("InnoDB".to_string())
+        ));
+
+        let (_, alter) =
+            parse_alter_sql("ALTER TABLE t ADD COLUMN (a int, b text), DROP PRIMARY KEY").unwrap();
+        assert_eq!(alter.spec_arr.len(), 3);
+        assert_eq!(alter.spec_arr[2], AlterSpecEnum::DropPrimaryKey);
+    }
+
+    #[test]
+    fn test_apply_alter() {
+        let mut table = demo_table();
+        let sql = "ALTER TABLE `user`
+  ADD COLUMN `age` int NOT NULL DEFAULT 0 AFTER `name`,
+  CHANGE `name` `nickname` varchar(32) NOT NULL,
+  DROP COLUMN `email`,
+  ADD INDEX (`age`),
+  COMMENT='会员表'";
+        let (remain, alter) = parse_alter_sql(sql).unwrap();
+        assert_eq!(remain, "");
+        table.apply_alter(alter).unwrap();
+        let name_arr: Vec<&str> = table
+            .column_arr
+            .iter()
+            .map(|column| column.name.as_str())
+            .collect();
+        assert_eq!(name_arr, vec!["id", "nickname", "age", "status"]);
+        assert_eq!(table.column_arr[1].typ, DataTypeEnum::VarChar(32));
+        assert_eq!(table.index_arr.len(), 3);
+        assert_eq!(
+            table.index_arr[1].column_names(),
+            vec!["nickname".to_string()]
+        );
+        assert_eq!(table.index_arr[2].name, "age");
+        assert_eq!(table.option.comment, Some("会员表".to_string()));
+        assert_eq!(table.option.engine, Some("InnoDB".to_string()));
+    }
+
+    #[test]
+    fn test_apply_alter_primary_key_not_null() {
+        let mut table = demo_table();
+        let (_, alter) =
+            parse_alter_sql("ALTER TABLE `user` MODIFY `id` bigint unsigned AUTO_INCREMENT")
+                .unwrap();
+        table.apply_alter(alter).unwrap();
+        assert!(!table.column_arr[0].nullable);
+
+        let (_, alter) = parse_alter_sql("ALTER TABLE `user` CHANGE `id` `uid` bigint").unwrap();
+        table.apply_alter(alter).unwrap();
+        assert_eq!(table.column_arr[0].name, "uid");
+        assert!(!table.column_arr[0].nullable);
+    }
+
+    #[test]
+    fn test_apply_alter_error_keeps_table() {
+        let mut table = demo_table();
+        let (_, alter) =
+            parse_alter_sql("ALTER TABLE user DROP COLUMN status, DROP COLUMN not_exists").unwrap();
+        assert_eq!(
+            table.apply_alter(alter),
+            Err("unknown column `not_exists` in table `user`".to_string())
+        );
+        assert_eq!(table, demo_table());
+    }
+
+    #[test]
+    fn test_apply_migration() {
+        let mut schema = SchemaFile {
+            table_arr: vec![demo_table()],
+            table_line_arr: vec![1],
+            skipped_arr: vec![],
+        };
+        let migration = "-- 2021-10-01
+ALTER TABLE `user` ADD COLUMN `age` int NOT NULL DEFAULT 0;
+SET FOREIGN_KEY_CHECKS = 0;
+ALTER TABLE `user` RENAME INDEX `idx_name_email` TO `idx_ne`;
+";
+        schema.apply_migration(migration).unwrap();
+        let table = &schema.table_arr[0];
+        assert_eq!(table.column_arr.last().unwrap().name, "age");
+        assert_eq!(table.index_arr[1].name, "idx_ne");
+        assert_eq!(schema.skipped_arr.len(), 1);
+        assert_eq!(schema.skipped_arr[0].line, 3);
+
+        assert_eq!(
+            schema.apply_migration("ALTER TABLE `order` DROP COLUMN id;"),
+            Err("line 1: unknown table `order`".to_string())
+        );
+        let skipped_len = schema.skipped_arr.len();
+        assert!(schema
+            .apply_migration("CREATE TABLE `role` (\n  `name` varchar(10) BOGUS\n);")
+            .is_err());
+        assert_eq!(schema.table_arr.len(), 1);
+        assert_eq!(schema.skipped_arr.len(), skipped_len);
+    }
+
+    #[test]
+    fn test_apply_migration_drop_and_create() {
+        let mut schema = SchemaFile {
+            table_arr: vec![demo_table()],
+            table_line_arr: vec![1],
+            skipped_arr: vec![],
+        };
+        let migration = "DROP TABLE `user`;
+CREATE TABLE `user` (id bigint, name text);
+ALTER TABLE `user` ADD COLUMN x int;
+CREATE TABLE IF NOT EXISTS `user` (id bigint);
+DROP TABLE IF EXISTS `role`, `log`;
+";
+        schema.apply_migration(migration).unwrap();
+        assert_eq!(schema.table_arr.len(), 1);
+        assert_eq!(schema.table_line_arr, vec![2]);
+        assert!(schema.skipped_arr.is_empty());
+        let name_arr: Vec<&str> = schema.table_arr[0]
+            .column_arr
+            .iter()
+            .map(|column| column.name.as_str())
+            .collect();
+        assert_eq!(name_arr, vec!["id", "name", "x"]);
+
+        assert_eq!(
+            schema.apply_migration("CREATE TABLE `USER` (id int);"),
+            Err("line 1: table `USER` already exists".to_string())
+        );
+        assert_eq!(
+            schema.apply_migration("\nDROP TABLE `role`;"),
+            Err("line 2: unknown table `role`".to_string())
+        );
+        schema.apply_migration("drop table user cascade").unwrap();
+        assert!(schema.table_arr.is_empty());
+    }
+}
@@ -0,0 +1,1011 @@
+//! 将解析出的字段、索引、约束以及表配置重新输出为 MySQL 的 DDL 片段
+//!
+//! 输出的格式是统一的：关键字大写、标识符用反引号包裹、字符串用单引号包裹，
+//! 因此同一个结构无论原始写法如何，输出的结果都相同
+use super::alter::{AlterSpecEnum, AlterTable, ColumnPositionEnum};
+use super::*;
+
+/// 用反引号包裹标识符，标识符中的反引号会被转义为两个反引号
+pub fn quote_identifier(name: &str) -> String {
+    format!("`{}`", name.replace('`', "``"))
+}
+
+/// 用单引号包裹字符串，并转义其中的特殊字符
+pub fn quote_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('\'');
+    for c in value.chars() {
+        match c {
+            '\'' => quoted.push_str("\\'"),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            '\0' => quoted.push_str("\\0"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// 以逗号分隔的标识符列表，如：`(`a`, `b`)`
+fn identifier_list_sql(name_arr: &[String]) -> String {
+    let name_arr: Vec<String> = name_arr.iter().map(|name| quote_identifier(name)).collect();
+    format!("({})", name_arr.join(", "))
+}
+
+/// 整数类型的完整写法，如：`int(11) unsigned zerofill`
+fn int_type_sql(name: &str, option: &IntTypeOption) -> String {
+    let mut sql = name.to_string();
+    if let Some(width) = option.width {
+        sql += &format!("({})", width);
+    }
+    if option.unsigned {
+        sql += " unsigned";
+    }
+    if option.zerofill {
+        sql += " zerofill";
+    }
+    sql
+}
+
+/// 定点数、浮点数类型的完整写法，如：`decimal(10,2) unsigned`
+fn decimal_type_sql(name: &str, option: &DecimalTypeOption) -> String {
+    let mut sql = name.to_string();
+    match (option.precision, option.scale) {
+        (Some(precision), Some(scale)) => sql += &format!("({},{})", precision, scale),
+        (Some(precision), None) => sql += &format!("({})", precision),
+        _ => {}
+    }
+    if option.unsigned {
+        sql += " unsigned";
+    }
+    if option.zerofill {
+        sql += " zerofill";
+    }
+    sql
+}
+
+/// 带有小数秒精度的时间类型，精度为 0 时省略，如：`datetime(3)`
+fn fsp_type_sql(name: &str, fsp: u32) -> String {
+    if fsp == 0 {
+        name.to_string()
+    } else {
+        format!("{}({})", name, fsp)
+    }
+}
+
+/// 字段类型的完整写法，包括长度、精度、符号以及枚举值
+/// 与 `sql2_render::transfer_type` 不同，这里的输出可以被重新解析为相同的类型
+pub fn data_type_sql(typ: &DataTypeEnum) -> String {
+    match typ {
+        DataTypeEnum::TinyInt(option) => int_type_sql("tinyint", option),
+        DataTypeEnum::SmallInt(option) => int_type_sql("smallint", option),
+        DataTypeEnum::MediumInt(option) => int_type_sql("mediumint", option),
+        DataTypeEnum::Int(option) => int_type_sql("int", option),
+        DataTypeEnum::Bigint(option) => int_type_sql("bigint", option),
+        DataTypeEnum::Bit(n) => format!("bit({})", n),
+        DataTypeEnum::Bool => "bool".to_string(),
+        DataTypeEnum::Float(option) => decimal_type_sql("float", option),
+        DataTypeEnum::Double(option) => decimal_type_sql("double", option),
+        DataTypeEnum::Decimal(option) => decimal_type_sql("decimal", option),
+        DataTypeEnum::Char(n) => format!("char({})", n),
+        DataTypeEnum::VarChar(n) => format!("varchar({})", n),
+        DataTypeEnum::Binary(n) => format!("binary({})", n),
+        DataTypeEnum::VarBinary(n) => format!("varbinary({})", n),
+        DataTypeEnum::TinyText => "tinytext".to_string(),
+        DataTypeEnum::Text => "text".to_string(),
+        DataTypeEnum::MediumText => "mediumtext".to_string(),
+        DataTypeEnum::LongText => "longtext".to_string(),
+        DataTypeEnum::BigText => "bigtext".to_string(),
+        DataTypeEnum::TinyBlob => "tinyblob".to_string(),
+        DataTypeEnum::Blob => "blob".to_string(),
+        DataTypeEnum::MediumBlob => "mediumblob".to_string(),
+        DataTypeEnum::LongBlob => "longblob".to_string(),
+        DataTypeEnum::Date => "date".to_string(),
+        DataTypeEnum::Time(fsp) => fsp_type_sql("time", *fsp),
+        DataTypeEnum::DateTime(fsp) => fsp_type_sql("datetime", *fsp),
+        DataTypeEnum::Timestamp(fsp) => fsp_type_sql("timestamp", *fsp),
+        DataTypeEnum::Year => "year".to_string(),
+        DataTypeEnum::Json => "json".to_string(),
+        DataTypeEnum::Enum(value_arr) | DataTypeEnum::Set(value_arr) => {
+            let name = if matches!(typ, DataTypeEnum::Enum(_)) {
+                "enum"
+            } else {
+                "set"
+            };
+            let value_arr: Vec<String> =
+                value_arr.iter().map(|value| quote_string(value)).collect();
+            format!("{}({})", name, value_arr.join(","))
+        }
+        DataTypeEnum::Geometry => "geometry".to_string(),
+        DataTypeEnum::Point => "point".to_string(),
+        DataTypeEnum::LineString => "linestring".to_string(),
+        DataTypeEnum::Polygon => "polygon".to_string(),
+        DataTypeEnum::MultiPoint => "multipoint".to_string(),
+        DataTypeEnum::MultiLineString => "multilinestring".to_string(),
+        DataTypeEnum::MultiPolygon => "multipolygon".to_string(),
+        DataTypeEnum::GeometryCollection => "geometrycollection".to_string(),
+        DataTypeEnum::Uuid => "uuid".to_string(),
+        DataTypeEnum::Jsonb => "jsonb".to_string(),
+        DataTypeEnum::TimestampTz(fsp) => fsp_type_sql("timestamptz", *fsp),
+        DataTypeEnum::Array(item_typ) => format!("{}[]", data_type_sql(item_typ)),
+        DataTypeEnum::Custom(name) => name.clone(),
+        DataTypeEnum::Unknown => "unknown".to_string(),
+    }
+}
+
+/// 默认值的写法，没有默认值时返回 None
+pub fn default_sql(default: &DefaultEnum) -> Option<String> {
+    match default {
+        DefaultEnum::DefaultNull => Some("NULL".to_string()),
+        DefaultEnum::DefaultInt(val) => Some(val.to_string()),
+        DefaultEnum::DefaultFloat(val) => Some(val.clone()),
+        DefaultEnum::DefaultStr(val) => Some(quote_string(val)),
+        DefaultEnum::DefaultBool(val) => Some(if *val { "TRUE" } else { "FALSE" }.to_string()),
+        // b'0'、x'1F' 这样的字面量原样输出，其余的表达式需要用括号包裹
+        DefaultEnum::DefaultExpr(expr) => {
+            let is_literal = expr.len() > 1
+                && expr.starts_with(|c: char| "bBxX".contains(c))
+                && expr[1..].starts_with('\'');
+            if is_literal {
+                Some(expr.clone())
+            } else {
+                Some(format!("({})", expr))
+            }
+        }
+        DefaultEnum::DefaultCurStamp | DefaultEnum::DefaultCurStampOnUpdateCurStamp => {
+            Some("CURRENT_TIMESTAMP".to_string())
+        }
+        DefaultEnum::DefaultNone
+        | DefaultEnum::DefaultAutoIncrement
+        | DefaultEnum::DefaultOnUpdateCurStamp
+        | DefaultEnum::Unknown => None,
+    }
+}
+
+/// 外键引用动作的写法，如：`SET NULL`
+pub fn reference_option_sql(option: &ReferenceOptionEnum) -> &'static str {
+    match option {
+        ReferenceOptionEnum::Restrict => "RESTRICT",
+        ReferenceOptionEnum::Cascade => "CASCADE",
+        ReferenceOptionEnum::SetNull => "SET NULL",
+        ReferenceOptionEnum::NoAction => "NO ACTION",
+        ReferenceOptionEnum::SetDefault => "SET DEFAULT",
+    }
+}
+
+impl OneReference {
+    /// 输出引用声明，如：`REFERENCES `user` (`id`) ON DELETE CASCADE`
+    pub fn to_sql(&self) -> String {
+        let mut sql = format!(
+            "REFERENCES {} {}",
+            quote_identifier(&self.table_name),
+            identifier_list_sql(&self.column_names)
+        );
+        if let Some(match_type) = &self.match_type {
+            sql += &format!(" MATCH {}", match_type);
+        }
+        if let Some(action) = &self.on_delete {
+            sql += &format!(" ON DELETE {}", reference_option_sql(action));
+        }
+        if let Some(action) = &self.on_update {
+            sql += &format!(" ON UPDATE {}", reference_option_sql(action));
+        }
+        sql
+    }
+}
+
+impl OneCheck {
+    /// 输出 check 约束，如：`CONSTRAINT `chk_age` CHECK (age > 0) NOT ENFORCED`
+    pub fn to_sql(&self) -> String {
+        let mut sql = String::new();
+        if let Some(name) = &self.name {
+            sql += &format!("CONSTRAINT {} ", quote_identifier(name));
+        }
+        sql += &format!("CHECK ({})", self.expr);
+        if !self.enforced {
+            sql += " NOT ENFORCED";
+        }
+        sql
+    }
+}
+
+impl OneColumn {
+    /// 输出字段声明，如：`` `id` bigint(20) unsigned NOT NULL AUTO_INCREMENT COMMENT '主键' ``
+    pub fn to_sql(&self) -> String {
+        let mut part_arr = vec![quote_identifier(&self.name), data_type_sql(&self.typ)];
+        if let Some(charset) = &self.charset {
+            part_arr.push(format!("CHARACTER SET {}", charset));
+        }
+        if let Some(collate) = &self.collate {
+            part_arr.push(format!("COLLATE {}", collate));
+        }
+        if let Some(generated) = &self.generated {
+            part_arr.push(format!(
+                "GENERATED ALWAYS AS ({}) {}",
+                generated.expr,
+                if generated.stored {
+                    "STORED"
+                } else {
+                    "VIRTUAL"
+                }
+            ));
+        }
+        if !self.nullable {
+            part_arr.push("NOT NULL".to_string());
+        }
+        if let Some(default) = default_sql(&self.default) {
+            part_arr.push(format!("DEFAULT {}", default));
+        }
+        if self.on_update_cur_stamp {
+            part_arr.push("ON UPDATE CURRENT_TIMESTAMP".to_string());
+        }
+        if self.auto_increment {
+            part_arr.push("AUTO_INCREMENT".to_string());
+        }
+        if self.invisible {
+            part_arr.push("INVISIBLE".to_string());
+        }
+        if let Some(srid) = self.srid {
+            part_arr.push(format!("SRID {}", srid));
+        }
+        if self.unique_key {
+            part_arr.push("UNIQUE KEY".to_string());
+        }
+        if self.primary_key {
+            part_arr.push("PRIMARY KEY".to_string());
+        }
+        if !self.comment.is_empty() {
+            part_arr.push(format!("COMMENT {}", quote_string(&self.comment)));
+        }
+        if let Some(format) = &self.column_format {
+            part_arr.push(format!("COLUMN_FORMAT {}", format));
+        }
+        if let Some(storage) = &self.storage {
+            part_arr.push(format!("STORAGE {}", storage));
+        }
+        if let Some(value) = &self.engine_attribute {
+            part_arr.push(format!("ENGINE_ATTRIBUTE {}", quote_string(value)));
+        }
+        if let Some(value) = &self.secondary_engine_attribute {
+            part_arr.push(format!(
+                "SECONDARY_ENGINE_ATTRIBUTE {}",
+                quote_string(value)
+            ));
+        }
+        if let Some(reference) = &self.reference {
+            part_arr.push(reference.to_sql());
+        }
+        for check in self.check_arr.iter() {
+            part_arr.push(check.to_sql());
+        }
+        part_arr.join(" ")
+    }
+}
+
+impl OneKeyPart {
+    /// 输出索引中的一部分，如：`` `name`(20) DESC ``、`(lower(email))`
+    pub fn to_sql(&self) -> String {
+        let mut sql = match (&self.column_name, &self.expr) {
+            (Some(name), _) => quote_identifier(name),
+            (None, Some(expr)) => format!("({})", expr),
+            (None, None) => String::new(),
+        };
+        if let Some(len) = self.prefix_len {
+            sql += &format!("({})", len);
+        }
+        match self.order {
+            Some(IndexOrderEnum::Asc) => sql += " ASC",
+            Some(IndexOrderEnum::Desc) => sql += " DESC",
+            None => {}
+        }
+        sql
+    }
+}
+
+impl OneIndex {
+    /// 输出索引声明，如：`` UNIQUE KEY `uk_email` (`email`) USING BTREE COMMENT '邮箱' ``
+    pub fn to_sql(&self) -> String {
+        let head = match self.typ {
+            IndexIdxTyeEnum::Primary => "PRIMARY KEY",
+            IndexIdxTyeEnum::Unique => "UNIQUE KEY",
+            IndexIdxTyeEnum::FullText => "FULLTEXT KEY",
+            IndexIdxTyeEnum::Spatial => "SPATIAL KEY",
+            IndexIdxTyeEnum::Normal | IndexIdxTyeEnum::None => "KEY",
+        };
+        let mut sql = head.to_string();
+        if self.typ == IndexIdxTyeEnum::Primary {
+            // 主键不能命名，只能以约束名的形式保留
+            if !self.name.is_empty() {
+                sql = format!("CONSTRAINT {} {}", quote_identifier(&self.name), sql);
+            }
+        } else if !self.name.is_empty() {
+            sql += &format!(" {}", quote_identifier(&self.name));
+        }
+        let part_arr: Vec<String> = self.key_parts.iter().map(|part| part.to_sql()).collect();
+        sql += &format!(" ({})", part_arr.join(", "));
+        if let Some(using_type) = &self.using_type {
+            sql += &format!(" USING {}", using_type.to_uppercase());
+        }
+        if let Some(size) = self.option.key_block_size {
+            sql += &format!(" KEY_BLOCK_SIZE={}", size);
+        }
+        if let Some(parser) = &self.option.parser {
+            sql += &format!(" WITH PARSER {}", parser);
+        }
+        if let Some(comment) = &self.option.comment {
+            sql += &format!(" COMMENT {}", quote_string(comment));
+        }
+        if self.option.invisible {
+            sql += " INVISIBLE";
+        }
+        if let Some(value) = &self.option.engine_attribute {
+            sql += &format!(" ENGINE_ATTRIBUTE={}", quote_string(value));
+        }
+        if let Some(value) = &self.option.secondary_engine_attribute {
+            sql += &format!(" SECONDARY_ENGINE_ATTRIBUTE={}", quote_string(value));
+        }
+        sql
+    }
+}
+
+impl OneForeignKey {
+    /// 输出外键约束，如：`` CONSTRAINT `fk_user` FOREIGN KEY (`user_id`) REFERENCES `user` (`id`) ``
+    pub fn to_sql(&self) -> String {
+        let mut sql = String::new();
+        if let Some(name) = &self.name {
+            sql += &format!("CONSTRAINT {} ", quote_identifier(name));
+        }
+        sql += "FOREIGN KEY";
+        if let Some(index_name) = &self.index_name {
+            sql += &format!(" {}", quote_identifier(index_name));
+        }
+        sql += &format!(
+            " {} {}",
+            identifier_list_sql(&self.column_names),
+            self.reference.to_sql()
+        );
+        sql
+    }
+}
+
+/// 表配置 extra 中的值，不是简单的单词时需要用引号包裹
+fn option_value_sql(value: &str) -> String {
+    let is_word = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '.' || c == '-');
+    if is_word || value.starts_with('(') {
+        value.to_string()
+    } else {
+        quote_string(value)
+    }
+}
+
+impl TableOption {
+    /// 输出表配置中的各项，如：`["ENGINE=InnoDB", "DEFAULT CHARSET=utf8mb4"]`，分区声明不包含在内
+    pub fn to_sql_arr(&self) -> Vec<String> {
+        let mut part_arr: Vec<String> = vec![];
+        if let Some(engine) = &self.engine {
+            part_arr.push(format!("ENGINE={}", engine));
+        }
+        if let Some(auto_increment) = self.auto_increment {
+            part_arr.push(format!("AUTO_INCREMENT={}", auto_increment));
+        }
+        if let Some(charset) = &self.charset {
+            part_arr.push(format!("DEFAULT CHARSET={}", charset));
+        }
+        if let Some(collate) = &self.collate {
+            part_arr.push(format!("COLLATE={}", collate));
+        }
+        if let Some(row_format) = &self.row_format {
+            part_arr.push(format!("ROW_FORMAT={}", row_format));
+        }
+        for (key, value) in self.extra.iter() {
+            part_arr.push(format!("{}={}", key, option_value_sql(value)));
+        }
+        if let Some(comment) = &self.comment {
+            part_arr.push(format!("COMMENT={}", quote_string(comment)));
+        }
+        part_arr
+    }
+}
+
+impl TableSchema {
+    /// 输出完整的建表语句，字段、索引、外键、check 约束各占一行，如：
+    ///
+    /// ```sql
+    /// CREATE TABLE `user` (
+    ///   `id` bigint(20) unsigned NOT NULL AUTO_INCREMENT,
+    ///   PRIMARY KEY (`id`)
+    /// ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+    /// ```
+    ///
+    /// 输出的语句再次解析后得到的结构与原结构相同
+    pub fn to_sql(&self) -> String {
+        let mut line_arr: Vec<String> = self.column_arr.iter().map(|col| col.to_sql()).collect();
+        line_arr.extend(self.index_arr.iter().map(|index| index.to_sql()));
+        line_arr.extend(self.foreign_key_arr.iter().map(|fk| fk.to_sql()));
+        line_arr.extend(self.check_arr.iter().map(|check| check.to_sql()));
+        let mut sql = format!(
+            "CREATE TABLE {} (\n  {}\n)",
+            quote_identifier(&self.table_name),
+            line_arr.join(",\n  ")
+        );
+        let option_arr = self.option.to_sql_arr();
+        if !option_arr.is_empty() {
+            sql += &format!(" {}", option_arr.join(" "));
+        }
+        if let Some(partition) = &self.option.partition {
+            sql += &format!("\n{}", partition);
+        }
+        sql + ";"
+    }
+}
+
+impl SchemaFile {
+    /// 输出文件中所有表的建表语句，语句之间以空行分隔，跳过的语句不会输出，需要时用 `to_sql_with_skipped`
+    pub fn to_sql(&self) -> String {
+        let sql_arr: Vec<String> = self.table_arr.iter().map(|table| table.to_sql()).collect();
+        sql_arr.join("\n\n")
+    }
+
+    /// 按语句在文件中的顺序输出建表语句以及被跳过的语句，被跳过的语句原样输出
+    /// 建表语句前后以空行分隔，连续的被跳过的语句之间不加空行
+    pub fn to_sql_with_skipped(&self) -> String {
+        // (行号, 是否为建表语句, 语句)，没有行号的表放在最后
+        let mut item_arr: Vec<(usize, bool, String)> = self
+            .table_arr
+            .iter()
+            .enumerate()
+            .map(|(i, table)| {
+                let line = self.table_line_arr.get(i).copied().unwrap_or(usize::MAX);
+                (line, true, table.to_sql())
+            })
+            .collect();
+        item_arr.extend(
+            self.skipped_arr
+                .iter()
+                .map(|skipped| (skipped.line, false, format!("{};", skipped.statement))),
+        );
+        item_arr.sort_by_key(|(line, _, _)| *line);
+        let mut sql = String::new();
+        let mut last_is_table: Option<bool> = None;
+        for (_, is_table, statement) in item_arr {
+            match last_is_table {
+                None => {}
+                Some(false) if !is_table => sql += "\n",
+                Some(_) => sql += "\n\n",
+            }
+            sql += &statement;
+            last_is_table = Some(is_table);
+        }
+        sql
+    }
+}
+
+/// 字段位置的写法，如：` AFTER `name``
+fn column_position_sql(position: &Option<ColumnPositionEnum>) -> String {
+    match position {
+        Some(ColumnPositionEnum::First) => " FIRST".to_string(),
+        Some(ColumnPositionEnum::After(name)) => format!(" AFTER {}", quote_identifier(name)),
+        None => String::new(),
+    }
+}
+
+impl AlterSpecEnum {
+    /// 输出一项修改，如：`` ADD COLUMN `age` int NOT NULL AFTER `name` ``
+    pub fn to_sql(&self) -> String {
+        match self {
+            AlterSpecEnum::AddColumn(column, position) => format!(
+                "ADD COLUMN {}{}",
+                column.to_sql(),
+                column_position_sql(position)
+            ),
+            AlterSpecEnum::ModifyColumn(column, position) => format!(
+                "MODIFY COLUMN {}{}",
+                column.to_sql(),
+                column_position_sql(position)
+            ),
+            AlterSpecEnum::ChangeColumn(old_name, column, position) => format!(
+                "CHANGE COLUMN {} {}{}",
+                quote_identifier(old_name),
+                column.to_sql(),
+                column_position_sql(position)
+            ),
+            AlterSpecEnum::RenameColumn(old_name, new_name) => format!(
+                "RENAME COLUMN {} TO {}",
+                quote_identifier(old_name),
+                quote_identifier(new_name)
+            ),
+            AlterSpecEnum::DropColumn(name) => format!("DROP COLUMN {}", quote_identifier(name)),
+            AlterSpecEnum::AlterColumnDefault(name, default) => match default_sql(default) {
+                Some(default) => format!(
+                    "ALTER COLUMN {} SET DEFAULT {}",
+                    quote_identifier(name),
+                    default
+                ),
+                None => format!("ALTER COLUMN {} DROP DEFAULT", quote_identifier(name)),
+            },
+            AlterSpecEnum::AlterColumnVisible(name, visible) => format!(
+                "ALTER COLUMN {} SET {}",
+                quote_identifier(name),
+                if *visible { "VISIBLE" } else { "INVISIBLE" }
+            ),
+            AlterSpecEnum::AddIndex(index) => format!("ADD {}", index.to_sql()),
+            AlterSpecEnum::DropIndex(name) => format!("DROP INDEX {}", quote_identifier(name)),
+            AlterSpecEnum::DropPrimaryKey => "DROP PRIMARY KEY".to_string(),
+            AlterSpecEnum::RenameIndex(old_name, new_name) => format!(
+                "RENAME INDEX {} TO {}",
+                quote_identifier(old_name),
+                quote_identifier(new_name)
+            ),
+            AlterSpecEnum::AlterIndexVisible(name, visible) => format!(
+                "ALTER INDEX {} {}",
+                quote_identifier(name),
+                if *visible { "VISIBLE" } else { "INVISIBLE" }
+            ),
+            AlterSpecEnum::AddForeignKey(foreign_key) => format!("ADD {}", foreign_key.to_sql()),
+            AlterSpecEnum::DropForeignKey(name) => {
+                format!("DROP FOREIGN KEY {}", quote_identifier(name))
+            }
+            AlterSpecEnum::AddCheck(check) => format!("ADD {}", check.to_sql()),
+            AlterSpecEnum::DropConstraint(name) => {
+                format!("DROP CONSTRAINT {}", quote_identifier(name))
+            }
+            AlterSpecEnum::RenameTable(name) => format!("RENAME TO {}", quote_identifier(name)),
+            AlterSpecEnum::ConvertCharset(charset, collate) => match collate {
+                Some(collate) => {
+                    format!("CONVERT TO CHARACTER SET {} COLLATE {}", charset, collate)
+                }
+                None => format!("CONVERT TO CHARACTER SET {}", charset),
+            },
+            AlterSpecEnum::TableOption(option) => {
+                let mut part_arr = option.to_sql_arr();
+                if let Some(partition) = &option.partition {
+                    part_arr.push(partition.clone());
+                }
+                part_arr.join(" ")
+            }
+        }
+    }
+}
+
+impl AlterTable {
+    /// 输出整个 `ALTER TABLE` 语句，每项修改占一行
+    pub fn to_sql(&self) -> String {
+        let spec_arr: Vec<String> = self.spec_arr.iter().map(|spec| spec.to_sql()).collect();
+        format!(
+            "ALTER TABLE {}\n  {};",
+            quote_identifier(&self.table_name),
+            spec_arr.join(",\n  ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_to_sql() {
+        let sql = "`price` decimal(10,2) unsigned NOT NULL DEFAULT '0.00' COMMENT 'price'";
+        let (_, column) = parse_column_definition(sql).unwrap();
+        assert_eq!(
+            column.to_sql(),
+            "`price` decimal(10,2) unsigned NOT NULL DEFAULT '0.00' COMMENT 'price'"
+        );
+        let sql = "`status` enum('on','off') DEFAULT 'on' COLLATE utf8mb4_bin";
+        let (_, column) = parse_column_definition(sql).unwrap();
+        assert_eq!(
+            column.to_sql(),
+            "`status` enum('on','off') COLLATE utf8mb4_bin DEFAULT 'on'"
+        );
+        assert_eq!(parse_column_definition(&column.to_sql()).unwrap().1, column);
+    }
+
+    #[test]
+    fn test_index_to_sql() {
+        let sql = "UNIQUE INDEX uk_name (`name`(20) DESC, (lower(email))) COMMENT 'name' INVISIBLE";
+        let (_, line) = parse_idx_line2(sql).unwrap();
+        let index = match line {
+            OneLineEnum::Index(index) => index,
+            _ => panic!("not an index"),
+        };
+        assert_eq!(
+            index.to_sql(),
+            "UNIQUE KEY `uk_name` (`name`(20) DESC, (lower(email))) COMMENT 'name' INVISIBLE"
+        );
+    }
+
+    #[test]
+    fn test_table_to_sql() {
+        let sql = r#"create table if not exists user (
+  id bigint unsigned not null auto_increment comment '主键',
+  name varchar(64) not null default '' comment 'it''s name',
+  flag bool default true,
+  updated_at datetime(3) default current_timestamp on update current_timestamp,
+  primary key (id),
+  unique index uk_name using btree (name),
+  constraint fk_team foreign key (id) references team (id) on delete cascade,
+  check (id > 0)
+) comment 'user' engine innodb charset utf8mb4 PARTITION BY HASH (id) PARTITIONS 4"#;
+        let (_, table) = parse_create_sql(sql).unwrap();
+        assert_eq!(
+            table.to_sql(),
+            r#"CREATE TABLE `user` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '主键',
+  `name` varchar(64) NOT NULL DEFAULT '' COMMENT 'it\'s name',
+  `flag` bool DEFAULT TRUE,
+  `updated_at` datetime(3) DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `uk_name` (`name`) USING BTREE,
+  CONSTRAINT `fk_team` FOREIGN KEY (`id`) REFERENCES `team` (`id`) ON DELETE CASCADE,
+  CHECK (id > 0)
+) ENGINE=innodb DEFAULT CHARSET=utf8mb4 COMMENT='user'
+PARTITION BY HASH (id) PARTITIONS 4;"#
+        );
+        assert_eq!(parse_create_sql(&table.to_sql()).unwrap().1, table);
+    }
+
+    #[test]
+    fn test_schema_file_to_sql_with_skipped() {
+        let sql = r#"/*!40101 SET NAMES utf8mb4 */;
+DROP TABLE IF EXISTS `user`;
+create table user (id bigint not null, primary key (id));
+SET @a = ';' ;
+create table role (id int not null);
+"#;
+        let (_, schema) = parse_schema_file(sql).unwrap();
+        assert_eq!(
+            schema.to_sql_with_skipped(),
+            r#"/*!40101 SET NAMES utf8mb4 */;
+DROP TABLE IF EXISTS `user`;
+
+CREATE TABLE `user` (
+  `id` bigint NOT NULL,
+  PRIMARY KEY (`id`)
+);
+
+SET @a = ';';
+
+CREATE TABLE `role` (
+  `id` int NOT NULL
+);"#
+        );
+    }
+
+    mod round_trip {
+        use super::*;
+        use proptest::collection::vec;
+        use proptest::option;
+        use proptest::prelude::*;
+
+        fn name() -> impl Strategy<Value = String> {
+            "[a-z][a-z0-9_]{0,8}"
+        }
+
+        /// 包含引号、反斜杠、换行等需要转义的字符
+        fn text() -> impl Strategy<Value = String> {
+            "[a-zA-Z0-9 _%'\"\\\\\n\r\t\0中文]{0,16}"
+        }
+
+        fn expr() -> impl Strategy<Value = String> {
+            prop_oneof![
+                Just("id > 0".to_string()),
+                Just("price * num".to_string()),
+                Just("lower(name)".to_string()),
+                Just("concat(first_name, ' ', last_name)".to_string()),
+            ]
+        }
+
+        fn int_option() -> impl Strategy<Value = IntTypeOption> {
+            (option::of(1u32..=255), any::<bool>(), any::<bool>()).prop_map(
+                |(width, unsigned, zerofill)| IntTypeOption {
+                    width,
+                    // zerofill 隐含了 unsigned
+                    unsigned: unsigned || zerofill,
+                    zerofill,
+                },
+            )
+        }
+
+        fn decimal_option() -> impl Strategy<Value = DecimalTypeOption> {
+            (
+                option::of((1u32..=65, option::of(0u32..=30))),
+                any::<bool>(),
+                any::<bool>(),
+            )
+                .prop_map(|(size, unsigned, zerofill)| DecimalTypeOption {
+                    precision: size.map(|(precision, _)| precision),
+                    scale: size.and_then(|(_, scale)| scale),
+                    unsigned: unsigned || zerofill,
+                    zerofill,
+                })
+        }
+
+        fn data_type() -> impl Strategy<Value = DataTypeEnum> {
+            prop_oneof![
+                int_option().prop_map(DataTypeEnum::TinyInt),
+                int_option().prop_map(DataTypeEnum::SmallInt),
+                int_option().prop_map(DataTypeEnum::MediumInt),
+                int_option().prop_map(DataTypeEnum::Int),
+                int_option().prop_map(DataTypeEnum::Bigint),
+                (1u32..=64).prop_map(DataTypeEnum::Bit),
+                Just(DataTypeEnum::Bool),
+                decimal_option().prop_map(DataTypeEnum::Float),
+                decimal_option().prop_map(DataTypeEnum::Double),
+                decimal_option().prop_map(DataTypeEnum::Decimal),
+                (1u32..=255).prop_map(DataTypeEnum::Char),
+                (1u32..=65535).prop_map(DataTypeEnum::VarChar),
+                (1u32..=255).prop_map(DataTypeEnum::Binary),
+                (1u32..=65535).prop_map(DataTypeEnum::VarBinary),
+                Just(DataTypeEnum::TinyText),
+                Just(DataTypeEnum::Text),
+                Just(DataTypeEnum::LongText),
+                Just(DataTypeEnum::Blob),
+                Just(DataTypeEnum::Date),
+                (0u32..=6).prop_map(DataTypeEnum::Time),
+                (0u32..=6).prop_map(DataTypeEnum::DateTime),
+                (0u32..=6).prop_map(DataTypeEnum::Timestamp),
+                Just(DataTypeEnum::Year),
+                Just(DataTypeEnum::Json),
+                vec(text(), 1..4).prop_map(DataTypeEnum::Enum),
+                vec(text(), 1..4).prop_map(DataTypeEnum::Set),
+                Just(DataTypeEnum::Point),
+            ]
+        }
+
+        fn default_value() -> impl Strategy<Value = DefaultEnum> {
+            prop_oneof![
+                Just(DefaultEnum::DefaultNone),
+                Just(DefaultEnum::DefaultNull),
+                any::<i64>().prop_map(DefaultEnum::DefaultInt),
+                Just(DefaultEnum::DefaultFloat("0.5".to_string())),
+                text().prop_map(DefaultEnum::DefaultStr),
+                any::<bool>().prop_map(DefaultEnum::DefaultBool),
+                Just(DefaultEnum::DefaultCurStamp),
+                Just(DefaultEnum::DefaultExpr("b'1'".to_string())),
+                expr().prop_map(DefaultEnum::DefaultExpr),
+            ]
+        }
+
+        fn reference_option() -> impl Strategy<Value = ReferenceOptionEnum> {
+            prop_oneof![
+                Just(ReferenceOptionEnum::Restrict),
+                Just(ReferenceOptionEnum::Cascade),
+                Just(ReferenceOptionEnum::SetNull),
+                Just(ReferenceOptionEnum::NoAction),
+                Just(ReferenceOptionEnum::SetDefault),
+            ]
+        }
+
+        fn reference() -> impl Strategy<Value = OneReference> {
+            (
+                name(),
+                vec(name(), 1..3),
+                option::of(reference_option()),
+                option::of(reference_option()),
+            )
+                .prop_map(|(table_name, column_names, on_delete, on_update)| {
+                    OneReference {
+                        table_name,
+                        column_names,
+                        match_type: None,
+                        on_delete,
+                        on_update,
+                    }
+                })
+        }
+
+        fn check() -> impl Strategy<Value = OneCheck> {
+            (option::of(name()), expr(), any::<bool>()).prop_map(|(name, expr, enforced)| {
+                OneCheck {
+                    name,
+                    expr,
+                    enforced,
+                }
+            })
+        }
+
+        fn column() -> impl Strategy<Value = OneColumn> {
+            (
+                (name(), data_type(), text(), any::<bool>(), default_value()),
+                (any::<bool>(), any::<bool>(), option::of("utf8mb4|latin1")),
+                (
+                    option::of("utf8mb4_bin|latin1_swedish_ci"),
+                    option::of((expr(), any::<bool>())),
+                    any::<bool>(),
+                    any::<bool>(),
+                    any::<bool>(),
+                ),
+                (vec(check(), 0..2), option::of(reference())),
+            )
+                .prop_map(
+                    |(
+                        (name, typ, comment, nullable, default),
+                        (auto_increment, on_update_cur_stamp, charset),
+                        (collate, generated, invisible, primary_key, unique_key),
+                        (check_arr, reference),
+                    )| OneColumn {
+                        name,
+                        typ,
+                        comment,
+                        nullable: nullable && !primary_key,
+                        default,
+                        auto_increment,
+                        on_update_cur_stamp,
+                        charset,
+                        collate,
+                        generated: generated.map(|(expr, stored)| GeneratedColumn { expr, stored }),
+                        invisible,
+                        primary_key,
+                        unique_key,
+                        check_arr,
+                        reference,
+                        ..Default::default()
+                    },
+                )
+        }
+
+        fn key_part(column_name_arr: Vec<String>) -> impl Strategy<Value = OneKeyPart> {
+            (
+                proptest::sample::select(column_name_arr),
+                option::of(1u32..=255),
+                option::of(prop_oneof![
+                    Just(IndexOrderEnum::Asc),
+                    Just(IndexOrderEnum::Desc)
+                ]),
+            )
+                .prop_map(|(column_name, prefix_len, order)| OneKeyPart {
+                    column_name: Some(column_name),
+                    prefix_len,
+                    order,
+                    expr: None,
+                })
+        }
+
+        fn index(column_name_arr: Vec<String>) -> impl Strategy<Value = OneIndex> {
+            (
+                name(),
+                prop_oneof![
+                    Just(IndexIdxTyeEnum::Unique),
+                    Just(IndexIdxTyeEnum::Normal),
+                    Just(IndexIdxTyeEnum::FullText),
+                ],
+                vec(key_part(column_name_arr), 1..3),
+                option::of("BTREE|HASH"),
+                option::of(text()),
+                any::<bool>(),
+            )
+                .prop_map(|(name, typ, key_parts, using_type, comment, invisible)| {
+                    OneIndex {
+                        name,
+                        using_type,
+                        typ,
+                        key_parts,
+                        option: IndexOption {
+                            comment,
+                            invisible,
+                            ..Default::default()
+                        },
+                    }
+                })
+        }
+
+        fn foreign_key(column_name_arr: Vec<String>) -> impl Strategy<Value = OneForeignKey> {
+            (
+                option::of(name()),
+                vec(proptest::sample::select(column_name_arr), 1..3),
+                reference(),
+            )
+                .prop_map(|(name, column_names, reference)| OneForeignKey {
+                    name,
+                    index_name: None,
+                    column_names,
+                    reference,
+                })
+        }
+
+        fn table_option() -> impl Strategy<Value = TableOption> {
+            (
+                option::of("InnoDB|MyISAM"),
+                option::of("utf8mb4|latin1"),
+                option::of("utf8mb4_bin|latin1_swedish_ci"),
+                option::of(text()),
+                option::of(any::<u64>()),
+                option::of("DYNAMIC|COMPRESSED"),
+                option::of(Just("PARTITION BY HASH (id) PARTITIONS 4".to_string())),
+                option::of(1u32..=16),
+            )
+                .prop_map(
+                    |(
+                        engine,
+                        charset,
+                        collate,
+                        comment,
+                        auto_increment,
+                        row_format,
+                        partition,
+                        size,
+                    )| {
+                        let mut extra = BTreeMap::new();
+                        if let Some(size) = size {
+                            extra.insert("KEY_BLOCK_SIZE".to_string(), size.to_string());
+                        }
+                        TableOption {
+                            engine,
+                            charset,
+                            collate,
+                            comment,
+                            auto_increment,
+                            row_format,
+                            partition,
+                            extra,
+                        }
+                    },
+                )
+        }
+
+        fn table() -> impl Strategy<Value = TableSchema> {
+            (name(), vec(column(), 1..6))
+                .prop_flat_map(|(table_name, column_arr)| {
+                    let column_name_arr: Vec<String> =
+                        column_arr.iter().map(|col| col.name.clone()).collect();
+                    (
+                        Just(table_name),
+                        Just(column_arr),
+                        option::of(vec(proptest::sample::select(column_name_arr.clone()), 1..3)),
+                        vec(index(column_name_arr.clone()), 0..3),
+                        vec(foreign_key(column_name_arr), 0..2),
+                        vec(check(), 0..2),
+                        table_option(),
+                    )
+                })
+                .prop_map(
+                    |(table_name, column_arr, primary, index_arr, fk_arr, check_arr, option)| {
+                        let mut line_arr: Vec<OneLineEnum> =
+                            column_arr.into_iter().map(OneLineEnum::Column).collect();
+                        if let Some(name_arr) = primary {
+                            line_arr.push(OneLineEnum::Index(OneIndex {
+                                name: String::new(),
+                                using_type: None,
+                                typ: IndexIdxTyeEnum::Primary,
+                                key_parts: name_arr
+                                    .iter()
+                                    .map(|name| OneKeyPart::new(name))
+                                    .collect(),
+                                option: IndexOption::default(),
+                            }));
+                        }
+                        line_arr.extend(index_arr.into_iter().map(OneLineEnum::Index));
+                        line_arr.extend(fk_arr.into_iter().map(OneLineEnum::ForeignKey));
+                        line_arr.extend(check_arr.into_iter().map(OneLineEnum::Check));
+                        TableSchema::new(table_name, line_arr, option)
+                    },
+                )
+        }
+
+        proptest! {
+            #[test]
+            fn test_column_round_trip(column in column()) {
+                let sql = column.to_sql();
+                let (remain, parsed) = parse_column_definition(&sql).unwrap();
+                prop_assert_eq!(remain, "");
+                prop_assert_eq!(parsed, column);
+            }
+
+            #[test]
+            fn test_table_round_trip(table in table()) {
+                let sql = table.to_sql();
+                let (_, parsed) = parse_create_sql(&sql).map_err(|err| {
+                    TestCaseError::fail(format_parse_error(&sql, &err))
+                })?;
+                prop_assert_eq!(parsed.to_sql(), sql);
+                prop_assert_eq!(parsed, table);
+            }
+        }
+    }
+}
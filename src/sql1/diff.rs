@@ -0,0 +1,482 @@
+//! 比较同一张表的两个版本，列出字段、索引、约束以及表配置的差异，并生成对应的 MySQL `ALTER TABLE` 语句
+//!
+//! 字段、索引都按名字（不区分大小写）对应，无法识别重命名，重命名的字段会被视为删除旧字段、新增新字段
+use super::alter::{AlterSpecEnum, AlterTable, ColumnPositionEnum};
+use super::*;
+
+/// 一个新增或者被修改的字段
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnChange {
+    /// 修改前的字段，新增的字段为 None
+    pub old: Option<OneColumn>,
+    /// 修改后的字段
+    pub new: OneColumn,
+    /// 字段需要移动到的位置，位置没有变化或者新增的字段追加到末尾时为 None
+    pub position: Option<ColumnPositionEnum>,
+}
+
+/// 一个被修改的表配置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptionChange {
+    /// 配置名，如：ENGINE、COMMENT
+    pub name: String,
+    /// 修改前的值
+    pub old_value: Option<String>,
+    /// 修改后的值
+    pub new_value: Option<String>,
+}
+
+/// 一张表两个版本之间的差异
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableDiff {
+    /// 修改前的表名
+    pub table_name: String,
+    /// 表名有变化时，修改后的表名
+    pub new_table_name: Option<String>,
+    /// 删除的字段
+    pub removed_column_arr: Vec<OneColumn>,
+    /// 新增以及有变化的字段，按新版本中的顺序排列
+    pub column_change_arr: Vec<ColumnChange>,
+    /// 新增的索引
+    pub added_index_arr: Vec<OneIndex>,
+    /// 删除的索引
+    pub removed_index_arr: Vec<OneIndex>,
+    /// 有变化的索引，内容为 (修改前, 修改后)
+    pub changed_index_arr: Vec<(OneIndex, OneIndex)>,
+    /// 新增的外键
+    pub added_foreign_key_arr: Vec<OneForeignKey>,
+    /// 删除的外键，内容为 (外键名, 外键)
+    pub removed_foreign_key_arr: Vec<(String, OneForeignKey)>,
+    /// 新增的表级别 check 约束
+    pub added_check_arr: Vec<OneCheck>,
+    /// 删除的表级别 check 约束，内容为 (约束名, 约束)
+    pub removed_check_arr: Vec<(String, OneCheck)>,
+    /// 有变化的表配置
+    pub changed_option_arr: Vec<OptionChange>,
+}
+
+/// 没有名字的外键、check 约束在 MySQL 中会被自动命名为 `表名_ibfk_序号`、`表名_chk_序号`
+fn constraint_name(table_name: &str, infix: &str, name: &Option<String>, seq: usize) -> String {
+    match name {
+        Some(name) => name.clone(),
+        None => format!("{}_{}_{}", table_name, infix, seq),
+    }
+}
+
+/// 比较两个版本的表配置，自增起始值只是数据状态，不参与比较
+fn diff_option(old: &TableOption, new: &TableOption) -> Vec<OptionChange> {
+    let mut name_arr: Vec<(String, Option<String>, Option<String>)> = vec![
+        ("ENGINE".to_string(), old.engine.clone(), new.engine.clone()),
+        (
+            "DEFAULT CHARSET".to_string(),
+            old.charset.clone(),
+            new.charset.clone(),
+        ),
+        (
+            "COLLATE".to_string(),
+            old.collate.clone(),
+            new.collate.clone(),
+        ),
+        (
+            "COMMENT".to_string(),
+            old.comment.clone(),
+            new.comment.clone(),
+        ),
+        (
+            "ROW_FORMAT".to_string(),
+            old.row_format.clone(),
+            new.row_format.clone(),
+        ),
+        (
+            "PARTITION".to_string(),
+            old.partition.clone(),
+            new.partition.clone(),
+        ),
+    ];
+    let mut key_arr: Vec<&String> = old.extra.keys().chain(new.extra.keys()).collect();
+    key_arr.sort();
+    key_arr.dedup();
+    for key in key_arr {
+        name_arr.push((
+            key.clone(),
+            old.extra.get(key).cloned(),
+            new.extra.get(key).cloned(),
+        ));
+    }
+    name_arr
+        .into_iter()
+        .filter(|(_, old_value, new_value)| old_value != new_value)
+        .map(|(name, old_value, new_value)| OptionChange {
+            name,
+            old_value,
+            new_value,
+        })
+        .collect()
+}
+
+/// 比较同一张表的两个版本
+pub fn diff_table(old: &TableSchema, new: &TableSchema) -> TableDiff {
+    let mut diff = TableDiff {
+        table_name: old.table_name.clone(),
+        ..Default::default()
+    };
+    if old.table_name != new.table_name {
+        diff.new_table_name = Some(new.table_name.clone());
+    }
+
+    // 字段：先去掉删除的字段，再按新版本的顺序逐个确定位置
+    let has_column = |table: &TableSchema, name: &str| {
+        table
+            .column_arr
+            .iter()
+            .any(|column| column.name.eq_ignore_ascii_case(name))
+    };
+    for column in old.column_arr.iter() {
+        if !has_column(new, &column.name) {
+            diff.removed_column_arr.push(column.clone());
+        }
+    }
+    let mut order_arr: Vec<String> = old
+        .column_arr
+        .iter()
+        .filter(|column| has_column(new, &column.name))
+        .map(|column| column.name.to_lowercase())
+        .collect();
+    for (idx, column) in new.column_arr.iter().enumerate() {
+        let key = column.name.to_lowercase();
+        let position = match idx {
+            0 => ColumnPositionEnum::First,
+            _ => ColumnPositionEnum::After(new.column_arr[idx - 1].name.clone()),
+        };
+        let cur_pos = order_arr.iter().position(|name| *name == key);
+        let in_place = cur_pos == Some(idx);
+        let old_column = old
+            .column_arr
+            .iter()
+            .find(|old_column| old_column.name.eq_ignore_ascii_case(&column.name));
+        match old_column {
+            None => {
+                // 追加到末尾时不需要指定位置
+                let position = if idx == order_arr.len() {
+                    None
+                } else {
+                    Some(position)
+                };
+                order_arr.insert(idx, key);
+                diff.column_change_arr.push(ColumnChange {
+                    old: None,
+                    new: column.clone(),
+                    position,
+                });
+            }
+            Some(old_column) => {
+                if let Some(cur_pos) = cur_pos {
+                    order_arr.remove(cur_pos);
+                }
+                order_arr.insert(idx, key);
+                if !in_place || old_column != column {
+                    diff.column_change_arr.push(ColumnChange {
+                        old: Some(old_column.clone()),
+                        new: column.clone(),
+                        position: if in_place { None } else { Some(position) },
+                    });
+                }
+            }
+        }
+    }
+
+    // 索引：主键按类型对应，其余的按索引名对应。索引无法直接修改，有变化时先删除再新增
+    let same_index = |a: &OneIndex, b: &OneIndex| {
+        if a.typ == IndexIdxTyeEnum::Primary || b.typ == IndexIdxTyeEnum::Primary {
+            a.typ == b.typ
+        } else {
+            a.name.eq_ignore_ascii_case(&b.name)
+        }
+    };
+    for old_index in old.index_arr.iter() {
+        match new
+            .index_arr
+            .iter()
+            .find(|index| same_index(old_index, index))
+        {
+            Some(new_index) if new_index != old_index => {
+                diff.changed_index_arr
+                    .push((old_index.clone(), new_index.clone()));
+            }
+            Some(_) => {}
+            None => diff.removed_index_arr.push(old_index.clone()),
+        }
+    }
+    for new_index in new.index_arr.iter() {
+        if !old
+            .index_arr
+            .iter()
+            .any(|index| same_index(index, new_index))
+        {
+            diff.added_index_arr.push(new_index.clone());
+        }
+    }
+
+    // 外键、check 约束：完全相同的视为没有变化，其余的都是先删除再新增
+    let mut unnamed_seq = 0;
+    for foreign_key in old.foreign_key_arr.iter() {
+        if foreign_key.name.is_none() {
+            unnamed_seq += 1;
+        }
+        if !new.foreign_key_arr.contains(foreign_key) {
+            let name = constraint_name(&old.table_name, "ibfk", &foreign_key.name, unnamed_seq);
+            diff.removed_foreign_key_arr
+                .push((name, foreign_key.clone()));
+        }
+    }
+    for foreign_key in new.foreign_key_arr.iter() {
+        if !old.foreign_key_arr.contains(foreign_key) {
+            diff.added_foreign_key_arr.push(foreign_key.clone());
+        }
+    }
+    let mut unnamed_seq = 0;
+    for check in old.check_arr.iter() {
+        if check.name.is_none() {
+            unnamed_seq += 1;
+        }
+        if !new.check_arr.contains(check) {
+            let name = constraint_name(&old.table_name, "chk", &check.name, unnamed_seq);
+            diff.removed_check_arr.push((name, check.clone()));
+        }
+    }
+    for check in new.check_arr.iter() {
+        if !old.check_arr.contains(check) {
+            diff.added_check_arr.push(check.clone());
+        }
+    }
+
+    diff.changed_option_arr = diff_option(&old.option, &new.option);
+    diff
+}
+
+impl TableDiff {
+    /// 两个版本是否完全相同
+    pub fn is_empty(&self) -> bool {
+        *self
+            == TableDiff {
+                table_name: self.table_name.clone(),
+                ..Default::default()
+            }
+    }
+
+    /// 删除外键的修改项
+    fn drop_foreign_key_spec_arr(&self) -> Vec<AlterSpecEnum> {
+        self.removed_foreign_key_arr
+            .iter()
+            .map(|(name, _)| AlterSpecEnum::DropForeignKey(name.clone()))
+            .collect()
+    }
+
+    /// 除删除外键以外的修改项，顺序为：删除约束、索引、字段，修改、新增字段，新增索引、约束，修改表配置，最后重命名表
+    fn other_spec_arr(&self) -> Vec<AlterSpecEnum> {
+        let mut spec_arr: Vec<AlterSpecEnum> = vec![];
+        for (name, _) in self.removed_check_arr.iter() {
+            spec_arr.push(AlterSpecEnum::DropConstraint(name.clone()));
+        }
+        let removed_index_arr = self
+            .removed_index_arr
+            .iter()
+            .chain(self.changed_index_arr.iter().map(|(old, _)| old));
+        for index in removed_index_arr {
+            if index.typ == IndexIdxTyeEnum::Primary {
+                spec_arr.push(AlterSpecEnum::DropPrimaryKey);
+            } else {
+                spec_arr.push(AlterSpecEnum::DropIndex(index.name.clone()));
+            }
+        }
+        for column in self.removed_column_arr.iter() {
+            spec_arr.push(AlterSpecEnum::DropColumn(column.name.clone()));
+        }
+        // 新增、修改的字段按照新版本中的顺序输出，保证 AFTER 引用的字段已经就位
+        for change in self.column_change_arr.iter() {
+            let (new, position) = (change.new.clone(), change.position.clone());
+            spec_arr.push(match &change.old {
+                None => AlterSpecEnum::AddColumn(new, position),
+                Some(old) if old.name == new.name => AlterSpecEnum::ModifyColumn(new, position),
+                Some(old) => AlterSpecEnum::ChangeColumn(old.name.clone(), new, position),
+            });
+        }
+        let added_index_arr = self
+            .changed_index_arr
+            .iter()
+            .map(|(_, new)| new)
+            .chain(self.added_index_arr.iter());
+        for index in added_index_arr {
+            spec_arr.push(AlterSpecEnum::AddIndex(index.clone()));
+        }
+        for foreign_key in self.added_foreign_key_arr.iter() {
+            spec_arr.push(AlterSpecEnum::AddForeignKey(foreign_key.clone()));
+        }
+        for check in self.added_check_arr.iter() {
+            spec_arr.push(AlterSpecEnum::AddCheck(check.clone()));
+        }
+        let mut option = TableOption::default();
+        for change in self.changed_option_arr.iter() {
+            // 被删除的配置大多无法通过 ALTER TABLE 恢复默认值，注释除外
+            let value = match (&change.new_value, change.name.as_str()) {
+                (Some(value), _) => value.clone(),
+                (None, "COMMENT") => String::new(),
+                (None, _) => continue,
+            };
+            match change.name.as_str() {
+                "ENGINE" => option.engine = Some(value),
+                "DEFAULT CHARSET" => option.charset = Some(value),
+                "COLLATE" => option.collate = Some(value),
+                "COMMENT" => option.comment = Some(value),
+                "ROW_FORMAT" => option.row_format = Some(value),
+                "PARTITION" => option.partition = Some(value),
+                _ => {
+                    option.extra.insert(change.name.clone(), value);
+                }
+            }
+        }
+        if option != TableOption::default() {
+            spec_arr.push(AlterSpecEnum::TableOption(option));
+        }
+        if let Some(name) = &self.new_table_name {
+            spec_arr.push(AlterSpecEnum::RenameTable(name.clone()));
+        }
+        spec_arr
+    }
+
+    /// 将差异转换为 `ALTER TABLE` 语句，没有差异时返回空数组
+    /// 外键的删除单独作为一条语句，避免与同名外键的新增冲突
+    pub fn to_alter_table_arr(&self) -> Vec<AlterTable> {
+        let mut alter_arr: Vec<AlterTable> = vec![];
+        let drop_arr = self.drop_foreign_key_spec_arr();
+        if !drop_arr.is_empty() {
+            alter_arr.push(AlterTable {
+                table_name: self.table_name.clone(),
+                spec_arr: drop_arr,
+            });
+        }
+        let other_arr = self.other_spec_arr();
+        if !other_arr.is_empty() {
+            alter_arr.push(AlterTable {
+                table_name: self.table_name.clone(),
+                spec_arr: other_arr,
+            });
+        }
+        alter_arr
+    }
+
+    /// 将差异输出为 MySQL 的 `ALTER TABLE` 语句，多条语句之间以空行分隔
+    pub fn to_sql(&self) -> String {
+        self.to_alter_table_arr()
+            .iter()
+            .map(|alter| alter.to_sql())
+            .collect::<Vec<String>>()
+            .join("\n\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD_SQL: &str = r#"CREATE TABLE `user` (
+  `id` bigint(20) unsigned NOT NULL AUTO_INCREMENT,
+  `name` varchar(64) NOT NULL DEFAULT '',
+  `email` varchar(128) NOT NULL DEFAULT '',
+  `nick` varchar(32) DEFAULT NULL,
+  `group_id` int(11) NOT NULL,
+  PRIMARY KEY (`id`),
+  KEY `idx_name` (`name`),
+  KEY `idx_nick` (`nick`),
+  CONSTRAINT `fk_group` FOREIGN KEY (`group_id`) REFERENCES `group` (`id`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8 COMMENT='用户';"#;
+
+    const NEW_SQL: &str = r#"CREATE TABLE `user` (
+  `id` bigint(20) unsigned NOT NULL AUTO_INCREMENT,
+  `email` varchar(255) NOT NULL DEFAULT '' COMMENT '邮箱',
+  `name` varchar(64) NOT NULL DEFAULT '',
+  `age` int(11) NOT NULL DEFAULT 0,
+  `group_id` int(11) NOT NULL,
+  PRIMARY KEY (`id`),
+  KEY `idx_name` (`name`, `email`),
+  UNIQUE KEY `uk_email` (`email`),
+  CONSTRAINT `fk_group` FOREIGN KEY (`group_id`) REFERENCES `group` (`id`) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='用户';"#;
+
+    #[test]
+    fn test_diff_table() {
+        let old = parse_create_sql(OLD_SQL).unwrap().1;
+        let new = parse_create_sql(NEW_SQL).unwrap().1;
+        let diff = diff_table(&old, &new);
+        assert_eq!(diff.removed_column_arr.len(), 1);
+        assert_eq!(diff.removed_column_arr[0].name, "nick");
+        assert_eq!(diff.column_change_arr.len(), 2);
+        let email = &diff.column_change_arr[0];
+        assert_eq!(
+            email.old.as_ref().map(|column| &column.typ),
+            Some(&DataTypeEnum::VarChar(128))
+        );
+        assert_eq!(email.new.typ, DataTypeEnum::VarChar(255));
+        assert_eq!(
+            email.position,
+            Some(ColumnPositionEnum::After("id".to_string()))
+        );
+        let age = &diff.column_change_arr[1];
+        assert_eq!(age.old, None);
+        assert_eq!(age.new.name, "age");
+        assert_eq!(
+            age.position,
+            Some(ColumnPositionEnum::After("name".to_string()))
+        );
+        assert_eq!(diff.removed_index_arr[0].name, "idx_nick");
+        assert_eq!(diff.changed_index_arr[0].1.name, "idx_name");
+        assert_eq!(diff.added_index_arr[0].name, "uk_email");
+        assert_eq!(diff.removed_foreign_key_arr[0].0, "fk_group");
+        assert_eq!(
+            diff.changed_option_arr,
+            vec![OptionChange {
+                name: "DEFAULT CHARSET".to_string(),
+                old_value: Some("utf8".to_string()),
+                new_value: Some("utf8mb4".to_string()),
+            }]
+        );
+        assert!(diff_table(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn test_diff_to_sql() {
+        let old = parse_create_sql(OLD_SQL).unwrap().1;
+        let new = parse_create_sql(NEW_SQL).unwrap().1;
+        assert_eq!(
+            diff_table(&old, &new).to_sql(),
+            "ALTER TABLE `user`
+  DROP FOREIGN KEY `fk_group`;
+
+ALTER TABLE `user`
+  DROP INDEX `idx_nick`,
+  DROP INDEX `idx_name`,
+  DROP COLUMN `nick`,
+  MODIFY COLUMN `email` varchar(255) NOT NULL DEFAULT '' COMMENT '邮箱' AFTER `id`,
+  ADD COLUMN `age` int(11) NOT NULL DEFAULT 0 AFTER `name`,
+  ADD KEY `idx_name` (`name`, `email`),
+  ADD UNIQUE KEY `uk_email` (`email`),
+  ADD CONSTRAINT `fk_group` FOREIGN KEY (`group_id`) REFERENCES `group` (`id`) ON DELETE CASCADE,
+  DEFAULT CHARSET=utf8mb4;"
+        );
+    }
+
+    #[test]
+    fn test_diff_apply_gives_new_table() {
+        let mut table = parse_create_sql(OLD_SQL).unwrap().1;
+        let new = parse_create_sql(NEW_SQL).unwrap().1;
+        let sql = diff_table(&table, &new).to_sql();
+        let mut schema = SchemaFile {
+            table_arr: vec![table],
+            table_line_arr: vec![1],
+            skipped_arr: vec![],
+        };
+        schema.apply_migration(&sql).unwrap();
+        table = schema.table_arr.pop().unwrap();
+        assert_eq!(table, new);
+    }
+}
@@ -0,0 +1,485 @@
+//! 检查建表语句中的常见问题，如缺少主键、字段没有注释、重复的索引等
+//!
+//! 每个问题都带有规则 id、级别、表名、字段名以及所在的行号，可以直接输出为 json 供 CI 使用
+use std::fmt;
+
+use super::*;
+
+/// 问题的严重程度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverityEnum {
+    /// 建议修改
+    Warning,
+    /// 必须修改
+    Error,
+}
+
+/// 检查规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintRuleEnum {
+    /// 表没有主键
+    MissingPrimaryKey,
+    /// 字段没有注释
+    MissingColumnComment,
+    /// varchar 的长度超过了限制
+    VarcharTooLong,
+    /// 允许为 NULL 的字段没有默认值
+    NullableWithoutDefault,
+    /// 索引与另一个索引完全相同
+    DuplicateIndex,
+    /// 普通索引是另一个索引的前缀
+    RedundantIndex,
+    /// 表或字段的字符集不是 utf8mb4
+    NonUtf8mb4Charset,
+    /// 金额字段使用了浮点数
+    FloatForMoney,
+}
+
+impl LintRuleEnum {
+    /// 规则 id，如：`missing-primary-key`
+    pub fn id(&self) -> &'static str {
+        match self {
+            LintRuleEnum::MissingPrimaryKey => "missing-primary-key",
+            LintRuleEnum::MissingColumnComment => "missing-column-comment",
+            LintRuleEnum::VarcharTooLong => "varchar-too-long",
+            LintRuleEnum::NullableWithoutDefault => "nullable-without-default",
+            LintRuleEnum::DuplicateIndex => "duplicate-index",
+            LintRuleEnum::RedundantIndex => "redundant-index",
+            LintRuleEnum::NonUtf8mb4Charset => "non-utf8mb4-charset",
+            LintRuleEnum::FloatForMoney => "float-for-money",
+        }
+    }
+
+    /// 规则的严重程度
+    pub fn severity(&self) -> LintSeverityEnum {
+        match self {
+            LintRuleEnum::MissingPrimaryKey | LintRuleEnum::FloatForMoney => {
+                LintSeverityEnum::Error
+            }
+            _ => LintSeverityEnum::Warning,
+        }
+    }
+}
+
+/// 检查的配置
+#[derive(Debug, Clone)]
+pub struct LintSetting {
+    /// varchar 允许的最大长度，超过时需要改用 text 或者说明原因
+    pub max_varchar_len: u32,
+    /// 金额字段名中的单词，如 `order_price` 中的 `price`
+    pub money_word_arr: Vec<String>,
+}
+
+impl Default for LintSetting {
+    fn default() -> Self {
+        LintSetting {
+            max_varchar_len: 1024,
+            money_word_arr: [
+                "price", "amount", "money", "cost", "fee", "balance", "salary",
+            ]
+            .iter()
+            .map(|word| word.to_string())
+            .collect(),
+        }
+    }
+}
+
+/// 检查出的一个问题
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LintIssue {
+    /// 规则 id，如：`missing-primary-key`
+    pub rule_id: &'static str,
+    /// 严重程度
+    pub severity: LintSeverityEnum,
+    /// 表名
+    pub table_name: String,
+    /// 字段名，表级别的问题为 None
+    pub column_name: Option<String>,
+    /// 索引名，只有索引相关的问题才有
+    pub index_name: Option<String>,
+    /// 问题所在的行号，从 1 开始。只检查表结构时为 None
+    pub line: Option<usize>,
+    /// 问题描述
+    pub message: String,
+}
+
+impl LintIssue {
+    fn new(rule: LintRuleEnum, table_name: &str, message: String) -> Self {
+        LintIssue {
+            rule_id: rule.id(),
+            severity: rule.severity(),
+            table_name: table_name.to_string(),
+            column_name: None,
+            index_name: None,
+            line: None,
+            message,
+        }
+    }
+
+    fn column(mut self, column_name: &str) -> Self {
+        self.column_name = Some(column_name.to_string());
+        self
+    }
+
+    fn index(mut self, index_name: &str) -> Self {
+        self.index_name = Some(index_name.to_string());
+        self
+    }
+}
+
+/// 输出为一行，如：`line 3: warning[missing-column-comment] user.name: column has no comment`
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+        let severity = match self.severity {
+            LintSeverityEnum::Warning => "warning",
+            LintSeverityEnum::Error => "error",
+        };
+        write!(f, "{}[{}] {}", severity, self.rule_id, self.table_name)?;
+        if let Some(column_name) = &self.column_name {
+            write!(f, ".{}", column_name)?;
+        }
+        if let Some(index_name) = &self.index_name {
+            write!(f, " ({})", index_name)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// 由字符集或排序规则得到字符集，如 `utf8_general_ci` 对应 `utf8`
+fn charset_of(charset: &Option<String>, collate: &Option<String>) -> Option<String> {
+    match (charset, collate) {
+        (Some(charset), _) => Some(charset.to_lowercase()),
+        (None, Some(collate)) => collate.split('_').next().map(|name| name.to_lowercase()),
+        (None, None) => None,
+    }
+}
+
+/// 索引能否用于前缀匹配，全文索引和空间索引不参与重复索引的检查
+fn is_btree_index(index: &OneIndex) -> bool {
+    matches!(
+        index.typ,
+        IndexIdxTyeEnum::Primary | IndexIdxTyeEnum::Unique | IndexIdxTyeEnum::Normal
+    )
+}
+
+/// 索引的名字，主键没有名字时为 `PRIMARY`
+fn index_display_name(index: &OneIndex) -> &str {
+    if index.name.is_empty() && index.typ == IndexIdxTyeEnum::Primary {
+        "PRIMARY"
+    } else {
+        &index.name
+    }
+}
+
+fn lint_column(table_name: &str, column: &OneColumn, setting: &LintSetting) -> Vec<LintIssue> {
+    let mut issue_arr: Vec<LintIssue> = vec![];
+    let mut push = |rule: LintRuleEnum, message: String| {
+        issue_arr.push(LintIssue::new(rule, table_name, message).column(&column.name));
+    };
+    if column.comment.is_empty() {
+        push(
+            LintRuleEnum::MissingColumnComment,
+            "column has no comment".to_string(),
+        );
+    }
+    if let DataTypeEnum::VarChar(len) = column.typ {
+        if len > setting.max_varchar_len {
+            push(
+                LintRuleEnum::VarcharTooLong,
+                format!(
+                    "varchar({}) is longer than {}, use text or document the limit",
+                    len, setting.max_varchar_len
+                ),
+            );
+        }
+    }
+    if column.nullable && column.default == DefaultEnum::DefaultNone && column.generated.is_none() {
+        push(
+            LintRuleEnum::NullableWithoutDefault,
+            "nullable column has no default value".to_string(),
+        );
+    }
+    if let Some(charset) = charset_of(&column.charset, &column.collate) {
+        if charset != "utf8mb4" {
+            push(
+                LintRuleEnum::NonUtf8mb4Charset,
+                format!("column charset is {}, expected utf8mb4", charset),
+            );
+        }
+    }
+    if matches!(column.typ, DataTypeEnum::Float(_) | DataTypeEnum::Double(_)) {
+        let lower_name = column.name.to_lowercase();
+        let is_money = lower_name
+            .split('_')
+            .any(|word| setting.money_word_arr.iter().any(|money| money == word));
+        if is_money {
+            push(
+                LintRuleEnum::FloatForMoney,
+                "money column uses a floating point type, use decimal instead".to_string(),
+            );
+        }
+    }
+    issue_arr
+}
+
+fn lint_index(table: &TableSchema) -> Vec<LintIssue> {
+    let mut issue_arr: Vec<LintIssue> = vec![];
+    for (i, index) in table.index_arr.iter().enumerate() {
+        if !is_btree_index(index) {
+            continue;
+        }
+        let other_arr = table
+            .index_arr
+            .iter()
+            .enumerate()
+            .filter(|(j, other)| *j != i && is_btree_index(other));
+        // 完全相同的两个索引中，唯一索引、主键有约束的作用，报告普通索引；类型相同时报告后声明的那一个
+        let duplicate = other_arr.clone().find(|(j, other)| {
+            index.key_parts == other.key_parts
+                && match (
+                    index.typ == IndexIdxTyeEnum::Normal,
+                    other.typ == IndexIdxTyeEnum::Normal,
+                ) {
+                    (true, false) => true,
+                    (false, true) => false,
+                    _ => *j < i,
+                }
+        });
+        if let Some((_, other)) = duplicate {
+            issue_arr.push(
+                LintIssue::new(
+                    LintRuleEnum::DuplicateIndex,
+                    &table.table_name,
+                    format!("index is the same as {}", index_display_name(other)),
+                )
+                .index(index_display_name(index)),
+            );
+            continue;
+        }
+        // 唯一索引即使是前缀也有约束的作用，不算多余
+        if index.typ != IndexIdxTyeEnum::Normal {
+            continue;
+        }
+        let prefix_of = other_arr.clone().find(|(_, other)| {
+            index.key_parts.len() < other.key_parts.len()
+                && other.key_parts.starts_with(&index.key_parts)
+        });
+        if let Some((_, other)) = prefix_of {
+            issue_arr.push(
+                LintIssue::new(
+                    LintRuleEnum::RedundantIndex,
+                    &table.table_name,
+                    format!("index is a prefix of {}", index_display_name(other)),
+                )
+                .index(index_display_name(index)),
+            );
+        }
+    }
+    issue_arr
+}
+
+/// 检查一个表结构，返回的问题中没有行号
+pub fn lint_table(table: &TableSchema, setting: &LintSetting) -> Vec<LintIssue> {
+    let mut issue_arr: Vec<LintIssue> = vec![];
+    let has_primary_key = table
+        .index_arr
+        .iter()
+        .any(|index| index.typ == IndexIdxTyeEnum::Primary)
+        || table.column_arr.iter().any(|column| column.primary_key);
+    if !has_primary_key {
+        issue_arr.push(LintIssue::new(
+            LintRuleEnum::MissingPrimaryKey,
+            &table.table_name,
+            "table has no primary key".to_string(),
+        ));
+    }
+    if let Some(charset) = charset_of(&table.option.charset, &table.option.collate) {
+        if charset != "utf8mb4" {
+            issue_arr.push(LintIssue::new(
+                LintRuleEnum::NonUtf8mb4Charset,
+                &table.table_name,
+                format!("table charset is {}, expected utf8mb4", charset),
+            ));
+        }
+    }
+    for column in table.column_arr.iter() {
+        issue_arr.extend(lint_column(&table.table_name, column, setting));
+    }
+    issue_arr.extend(lint_index(table));
+    issue_arr
+}
+
+/// 是否为标识符中的字符
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// 在建表语句中查找字段或索引声明所在的行，返回相对于语句首行的偏移
+/// 字段声明以字段名开头，索引声明中包含索引名
+fn definition_line_offset(statement: &str, issue: &LintIssue) -> Option<usize> {
+    let is_name = |word: &str, name: &str| {
+        word.trim_matches(|c| c == '`' || c == '"')
+            .eq_ignore_ascii_case(name)
+    };
+    statement
+        .lines()
+        .enumerate()
+        .skip(1)
+        .find_map(|(offset, line)| {
+            let matched = match (&issue.index_name, &issue.column_name) {
+                (Some(index_name), _) => line
+                    .split(|c: char| !is_ident_char(c) && c != '`' && c != '"')
+                    .any(|word| is_name(word, index_name)),
+                (None, Some(column_name)) => line
+                    .trim_start()
+                    .split(|c: char| c.is_whitespace() || c == '(')
+                    .next()
+                    .is_some_and(|word| is_name(word, column_name)),
+                (None, None) => false,
+            };
+            if matched {
+                Some(offset)
+            } else {
+                None
+            }
+        })
+}
+
+/// 从第 `line` 行开始、到下一条语句所在行之前的文本，用于查找字段或索引声明所在的行
+fn statement_text(input: &str, line: usize, next_line: Option<usize>) -> String {
+    let count = next_line.map_or(usize::MAX, |next_line| {
+        next_line.saturating_sub(line).max(1)
+    });
+    let line_arr: Vec<&str> = input.lines().skip(line - 1).take(count).collect();
+    line_arr.join("\n")
+}
+
+/// 检查整个 sql 文件中的所有建表语句，问题的行号为在文件中的行号
+/// 不是建表语句的语句会被跳过，解析出错时返回可读的错误信息
+pub fn lint_sql(input: &str, setting: &LintSetting) -> Result<Vec<LintIssue>, String> {
+    let (_, schema) = parse_schema_file(input).map_err(|err| format_parse_error(input, &err))?;
+    let mut issue_arr: Vec<LintIssue> = vec![];
+    for (table, &line) in schema.table_arr.iter().zip(schema.table_line_arr.iter()) {
+        let next_line = schema
+            .table_line_arr
+            .iter()
+            .chain(schema.skipped_arr.iter().map(|skipped| &skipped.line))
+            .filter(|&&other| other > line)
+            .min()
+            .copied();
+        let statement = statement_text(input, line, next_line);
+        for mut issue in lint_table(table, setting) {
+            issue.line = Some(line + definition_line_offset(&statement, &issue).unwrap_or(0));
+            issue_arr.push(issue);
+        }
+    }
+    Ok(issue_arr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule_arr(issue_arr: &[LintIssue]) -> Vec<(&str, Option<&str>)> {
+        issue_arr
+            .iter()
+            .map(|issue| {
+                (
+                    issue.rule_id,
+                    issue.column_name.as_deref().or(issue.index_name.as_deref()),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_lint_table() {
+        let sql = r#"CREATE TABLE `order` (
+  `id` bigint NOT NULL COMMENT 'id',
+  `user_id` bigint NOT NULL COMMENT 'user',
+  `total_price` double NOT NULL DEFAULT 0 COMMENT 'price',
+  `remark` varchar(2048) CHARACTER SET latin1
+) ENGINE=InnoDB DEFAULT CHARSET=utf8"#;
+        let (_, table) = parse_create_sql(sql).unwrap();
+        let issue_arr = lint_table(&table, &LintSetting::default());
+        assert_eq!(
+            rule_arr(&issue_arr),
+            vec![
+                ("missing-primary-key", None),
+                ("non-utf8mb4-charset", None),
+                ("float-for-money", Some("total_price")),
+                ("missing-column-comment", Some("remark")),
+                ("varchar-too-long", Some("remark")),
+                ("nullable-without-default", Some("remark")),
+                ("non-utf8mb4-charset", Some("remark")),
+            ]
+        );
+        assert_eq!(issue_arr[0].severity, LintSeverityEnum::Error);
+        assert_eq!(issue_arr[3].severity, LintSeverityEnum::Warning);
+    }
+
+    #[test]
+    fn test_lint_index() {
+        let sql = r#"CREATE TABLE `user` (
+  `id` bigint NOT NULL COMMENT 'id',
+  `name` varchar(64) NOT NULL COMMENT 'name',
+  `email` varchar(64) NOT NULL COMMENT 'email',
+  PRIMARY KEY (`id`),
+  KEY `idx_name` (`name`),
+  KEY `idx_name_email` (`name`, `email`),
+  UNIQUE KEY `uk_name` (`name`),
+  KEY `idx_id` (`id`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4"#;
+        let (_, table) = parse_create_sql(sql).unwrap();
+        let issue_arr = lint_table(&table, &LintSetting::default());
+        assert_eq!(
+            rule_arr(&issue_arr),
+            vec![
+                ("duplicate-index", Some("idx_name")),
+                ("duplicate-index", Some("idx_id")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_sql() {
+        let sql = r#"-- 用户表
+DROP TABLE IF EXISTS `user`;
+CREATE TABLE `user` (
+  `id` bigint NOT NULL COMMENT 'id',
+  `name` varchar(64) DEFAULT NULL,
+  PRIMARY KEY (`id`),
+  KEY `idx_name` (`name`),
+  KEY `idx_name_2` (`name`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE log (id int COMMENT 'id');
+"#;
+        let issue_arr = lint_sql(sql, &LintSetting::default()).unwrap();
+        let line_arr: Vec<(&str, Option<usize>)> = issue_arr
+            .iter()
+            .map(|issue| (issue.rule_id, issue.line))
+            .collect();
+        assert_eq!(
+            line_arr,
+            vec![
+                ("missing-column-comment", Some(5)),
+                ("duplicate-index", Some(8)),
+                ("missing-primary-key", Some(11)),
+                ("nullable-without-default", Some(11)),
+            ]
+        );
+        assert_eq!(
+            issue_arr[1].to_string(),
+            "line 8: warning[duplicate-index] user (idx_name_2): index is the same as idx_name"
+        );
+        assert_eq!(
+            serde_json::to_string(&issue_arr[0]).unwrap(),
+            r#"{"rule_id":"missing-column-comment","severity":"warning","table_name":"user","column_name":"name","index_name":null,"line":5,"message":"column has no comment"}"#
+        );
+    }
+}
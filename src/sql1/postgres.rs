@@ -0,0 +1,1173 @@
+//! 使用 nom 解析 PostgreSQL 的建表语句，解析结果与 MySQL 一样是 `TableSchema`，因此 `sql2_render` 可以直接使用
+//!
+//! 与 MySQL 的主要差异：
+//! * 标识符使用双引号包裹，不带引号的标识符不区分大小写，统一转为小写
+//! * 表名可以带上 schema，如：`public.user`
+//! * 字段、表的注释通过单独的 `COMMENT ON` 语句声明
+//! * 自增通过 `serial`、`GENERATED ... AS IDENTITY` 或 `nextval(...)` 默认值实现
+use super::*;
+use nom::multi::separated_list1;
+
+/// 解析 PostgreSQL 中的标识符。双引号包裹的标识符保留原样，其中的 `""` 表示一个双引号；不带引号的标识符转为小写
+pub fn pg_identifier(input: &str) -> IResult<&str, String> {
+    if let Some(body) = input.strip_prefix('"') {
+        let mut name = String::new();
+        let mut chars = body.char_indices().peekable();
+        while let Some((idx, c)) = chars.next() {
+            if c != '"' {
+                name.push(c);
+                continue;
+            }
+            if matches!(chars.peek(), Some((_, '"'))) {
+                chars.next();
+                name.push('"');
+                continue;
+            }
+            if name.is_empty() {
+                break;
+            }
+            return Ok((&body[idx + 1..], name));
+        }
+        return Err(Err::Error(VerboseError::from_error_kind(
+            input,
+            ErrorKind::Char,
+        )));
+    }
+    let first_char_ok = input
+        .chars()
+        .next()
+        .map(|c| c.is_alphabetic() || c == '_')
+        .unwrap_or(false);
+    if !first_char_ok {
+        return Err(Err::Error(VerboseError::from_error_kind(
+            input,
+            ErrorKind::AlphaNumeric,
+        )));
+    }
+    match take_while1(|c: char| c.is_alphanumeric() || c == '_' || c == '$')(input) {
+        Ok((remain, name)) => Ok((remain, name.to_lowercase())),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析以 `.` 分隔的名字，如：`public.user`、`public.user.id`
+pub fn pg_dotted_name(input: &str) -> IResult<&str, Vec<String>> {
+    separated_list1(tuple((space0, tag("."), space0)), pg_identifier)(input)
+}
+
+/// 解析可能带有 schema 的表名，如：`public.user`，返回 (schema, 表名)
+pub fn pg_table_name(input: &str) -> IResult<&str, (Option<String>, String)> {
+    match tuple((
+        pg_identifier,
+        opt(preceded(tuple((space0, tag("."), space0)), pg_identifier)),
+    ))(input)
+    {
+        Ok((remain, (first, Some(second)))) => Ok((remain, (Some(first), second))),
+        Ok((remain, (first, None))) => Ok((remain, (None, first))),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析括号包裹的字段列表，如：`("id", name)`
+pub fn pg_column_name_list(input: &str) -> IResult<&str, Vec<String>> {
+    let mut parser = tuple((
+        space0,
+        tag("("),
+        multispace0,
+        separated_list1(tuple((multispace0, tag(","), multispace0)), pg_identifier),
+        multispace0,
+        tag(")"),
+    ));
+    match parser(input) {
+        Ok((remain, (_, _, _, name_arr, _, _))) => Ok((remain, name_arr)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析类型后面可选的长度、精度，如：`(20)`
+fn pg_type_size(input: &str) -> IResult<&str, u32> {
+    match tuple((space0, tag("("), space0, nom_u32, space0, tag(")")))(input) {
+        Ok((remain, (_, _, _, size, _, _))) => Ok((remain, size)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析时间类型后面的 `with time zone`、`without time zone`，返回是否带时区
+fn pg_time_zone(input: &str) -> IResult<&str, bool> {
+    let mut parser = tuple((
+        space1,
+        alt((sql_keyword("with"), sql_keyword("without"))),
+        space1,
+        sql_keyword("time"),
+        space1,
+        sql_keyword("zone"),
+    ));
+    match parser(input) {
+        Ok((remain, (_, with, _, _, _, _))) => Ok((remain, with.eq_ignore_ascii_case("with"))),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析整数类型，包括 serial 类型。返回类型以及是否为 serial
+fn pg_type_int(input: &str) -> IResult<&str, (DataTypeEnum, bool)> {
+    let int_option = IntTypeOption::default;
+    alt((
+        map(
+            alt((sql_keyword("smallserial"), sql_keyword("serial2"))),
+            move |_| (DataTypeEnum::SmallInt(int_option()), true),
+        ),
+        map(
+            alt((sql_keyword("bigserial"), sql_keyword("serial8"))),
+            move |_| (DataTypeEnum::Bigint(int_option()), true),
+        ),
+        map(
+            alt((sql_keyword("serial"), sql_keyword("serial4"))),
+            move |_| (DataTypeEnum::Int(int_option()), true),
+        ),
+        map(
+            alt((sql_keyword("smallint"), sql_keyword("int2"))),
+            move |_| (DataTypeEnum::SmallInt(int_option()), false),
+        ),
+        map(
+            alt((sql_keyword("bigint"), sql_keyword("int8"))),
+            move |_| (DataTypeEnum::Bigint(int_option()), false),
+        ),
+        map(
+            alt((
+                sql_keyword("integer"),
+                sql_keyword("int4"),
+                sql_keyword("int"),
+            )),
+            move |_| (DataTypeEnum::Int(int_option()), false),
+        ),
+    ))(input)
+}
+
+/// 解析浮点数、定点数类型
+fn pg_type_number(input: &str) -> IResult<&str, DataTypeEnum> {
+    let numeric = tuple((
+        alt((sql_keyword("numeric"), sql_keyword("decimal"))),
+        opt(preceded(space0, type_float_size)),
+    ));
+    alt((
+        map(alt((sql_keyword("real"), sql_keyword("float4"))), |_| {
+            DataTypeEnum::Float(DecimalTypeOption::default())
+        }),
+        map(
+            alt((
+                recognize(tuple((
+                    sql_keyword("double"),
+                    space1,
+                    sql_keyword("precision"),
+                ))),
+                sql_keyword("float8"),
+            )),
+            |_| DataTypeEnum::Double(DecimalTypeOption::default()),
+        ),
+        map(
+            tuple((sql_keyword("float"), opt(pg_type_size))),
+            |(_, precision)| match precision {
+                Some(precision) if precision <= 24 => {
+                    DataTypeEnum::Float(DecimalTypeOption::default())
+                }
+                _ => DataTypeEnum::Double(DecimalTypeOption::default()),
+            },
+        ),
+        map(numeric, |(_, size)| {
+            DataTypeEnum::Decimal(DecimalTypeOption {
+                precision: size.map(|(precision, _)| precision),
+                scale: size.and_then(|(_, scale)| scale),
+                ..Default::default()
+            })
+        }),
+    ))(input)
+}
+
+/// 解析字符串、二进制类型
+fn pg_type_string(input: &str) -> IResult<&str, DataTypeEnum> {
+    let varchar = alt((
+        recognize(tuple((
+            sql_keyword("character"),
+            space1,
+            sql_keyword("varying"),
+        ))),
+        sql_keyword("varchar"),
+    ));
+    alt((
+        // 没有长度限制的 varchar 与 text 等价
+        map(
+            tuple((varchar, opt(pg_type_size))),
+            |(_, size)| match size {
+                Some(size) => DataTypeEnum::VarChar(size),
+                None => DataTypeEnum::Text,
+            },
+        ),
+        map(
+            tuple((
+                alt((
+                    sql_keyword("character"),
+                    sql_keyword("char"),
+                    sql_keyword("bpchar"),
+                )),
+                opt(pg_type_size),
+            )),
+            |(_, size)| DataTypeEnum::Char(size.unwrap_or(1)),
+        ),
+        map(sql_keyword("text"), |_| DataTypeEnum::Text),
+        map(sql_keyword("bytea"), |_| DataTypeEnum::LongBlob),
+        map(
+            tuple((
+                sql_keyword("bit"),
+                not(tuple((space1, sql_keyword("varying")))),
+                opt(pg_type_size),
+            )),
+            |(_, _, size)| DataTypeEnum::Bit(size.unwrap_or(1)),
+        ),
+    ))(input)
+}
+
+/// 解析日期、时间类型
+fn pg_type_time(input: &str) -> IResult<&str, DataTypeEnum> {
+    alt((
+        map(
+            tuple((sql_keyword("timestamptz"), opt(pg_type_size))),
+            |(_, fsp)| DataTypeEnum::TimestampTz(fsp.unwrap_or(0)),
+        ),
+        map(
+            tuple((
+                sql_keyword("timestamp"),
+                opt(pg_type_size),
+                opt(pg_time_zone),
+            )),
+            |(_, fsp, with_tz)| {
+                if with_tz.unwrap_or(false) {
+                    DataTypeEnum::TimestampTz(fsp.unwrap_or(0))
+                } else {
+                    DataTypeEnum::DateTime(fsp.unwrap_or(0))
+                }
+            },
+        ),
+        map(
+            tuple((
+                alt((sql_keyword("timetz"), sql_keyword("time"))),
+                opt(pg_type_size),
+                opt(pg_time_zone),
+            )),
+            |(_, fsp, _)| DataTypeEnum::Time(fsp.unwrap_or(0)),
+        ),
+        map(sql_keyword("date"), |_| DataTypeEnum::Date),
+    ))(input)
+}
+
+/// 解析没有专门对应的类型，保存类型名，如：`inet`、`interval`、`public.mood`、`geometry(Point,4326)`
+fn pg_type_custom(input: &str) -> IResult<&str, DataTypeEnum> {
+    let mut parser = tuple((
+        pg_dotted_name,
+        opt(alt((
+            recognize(tuple((space1, sql_keyword("varying")))),
+            recognize(pg_time_zone),
+        ))),
+        opt(parse_paren_expr),
+    ));
+    match parser(input) {
+        Ok((remain, (name_arr, suffix, modifier))) => {
+            let mut name = name_arr.join(".");
+            if let Some(suffix) = suffix {
+                name += &suffix.to_lowercase();
+            }
+            if let Some(modifier) = modifier {
+                name += &format!("({})", modifier);
+            }
+            Ok((remain, DataTypeEnum::Custom(name)))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析 PostgreSQL 的字段类型，返回类型以及是否为 serial 类型
+/// 类型后面可以跟上数组声明，如：`text[]`、`integer[3][3]`、`integer ARRAY`
+pub fn parse_pg_data_type(input: &str) -> IResult<&str, (DataTypeEnum, bool)> {
+    let base_type = alt((
+        pg_type_int,
+        map(
+            alt((
+                pg_type_number,
+                pg_type_string,
+                pg_type_time,
+                map(alt((sql_keyword("boolean"), sql_keyword("bool"))), |_| {
+                    DataTypeEnum::Bool
+                }),
+                map(sql_keyword("jsonb"), |_| DataTypeEnum::Jsonb),
+                map(sql_keyword("json"), |_| DataTypeEnum::Json),
+                map(sql_keyword("uuid"), |_| DataTypeEnum::Uuid),
+                pg_type_custom,
+            )),
+            |typ| (typ, false),
+        ),
+    ));
+    let array_dimension = alt((
+        recognize(tuple((space0, tag("["), space0, digit0, space0, tag("]")))),
+        recognize(tuple((
+            space1,
+            sql_keyword("array"),
+            opt(tuple((space0, tag("["), space0, digit0, space0, tag("]")))),
+        ))),
+    ));
+    match tuple((base_type, many0(array_dimension)))(input) {
+        Ok((remain, ((mut typ, serial), dimension_arr))) => {
+            for _ in dimension_arr {
+                typ = DataTypeEnum::Array(Box::new(typ));
+            }
+            Ok((remain, (typ, serial)))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析类型转换，如：`::character varying`、`::regclass`
+fn pg_type_cast(input: &str) -> IResult<&str, ()> {
+    match many1(tuple((space0, tag("::"), space0, parse_pg_data_type)))(input) {
+        Ok((remain, _)) => Ok((remain, ())),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析 PostgreSQL 的默认值，如：`0`、`'abc'::text`、`now()`、`nextval('user_id_seq'::regclass)`
+/// `nextval(...)` 视为自增
+pub fn parse_pg_default_value(input: &str) -> IResult<&str, DefaultEnum> {
+    let float_literal = recognize(tuple((
+        opt(one_of("+-")),
+        digit0,
+        opt(tuple((tag("."), digit0))),
+        opt(tuple((one_of("eE"), opt(one_of("+-")), digit1))),
+    )));
+    let float_value = map(
+        tuple((float_literal, not(tag("(")))),
+        |(float_str, _): (&str, _)| float_str.to_string(),
+    );
+    let func_call = map(
+        recognize(tuple((pg_dotted_name, space0, parse_paren_expr))),
+        |call: &str| call.to_string(),
+    );
+    let nextval = tuple((sql_keyword("nextval"), space0, parse_paren_expr));
+    let value = alt((
+        map(sql_keyword("null"), |_| DefaultEnum::DefaultNull),
+        map(
+            alt((sql_keyword("true"), sql_keyword("false"))),
+            |bool_str: &str| DefaultEnum::DefaultBool(bool_str.eq_ignore_ascii_case("true")),
+        ),
+        map(tuple((nom_i64, not(one_of(".eE")))), |(int_val, _)| {
+            DefaultEnum::DefaultInt(int_val)
+        }),
+        map(
+            nom::combinator::verify(float_value, |float_str: &String| {
+                float_str.chars().any(|c| c.is_ascii_digit())
+            }),
+            DefaultEnum::DefaultFloat,
+        ),
+        map(
+            tuple((opt(one_of("eE")), parse_sql_string)),
+            |(_, str_val)| DefaultEnum::DefaultStr(str_val),
+        ),
+        map(nextval, |_| DefaultEnum::DefaultAutoIncrement),
+        map(parse_current_timestamp, |_| DefaultEnum::DefaultCurStamp),
+        map(func_call, DefaultEnum::DefaultExpr),
+        map(
+            alt((
+                sql_keyword("current_date"),
+                sql_keyword("current_time"),
+                sql_keyword("current_user"),
+                sql_keyword("session_user"),
+            )),
+            |expr: &str| DefaultEnum::DefaultExpr(expr.to_uppercase()),
+        ),
+        map(parse_paren_expr, DefaultEnum::DefaultExpr),
+    ));
+    match tuple((value, opt(pg_type_cast)))(input) {
+        Ok((remain, (default, _))) => Ok((remain, default)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析引用声明，如：`REFERENCES public.user (id) ON DELETE CASCADE DEFERRABLE`
+pub fn parse_pg_reference(input: &str) -> IResult<&str, OneReference> {
+    let match_type = preceded(
+        tuple((multispace1, sql_keyword("match"), space1)),
+        alt((
+            sql_keyword("full"),
+            sql_keyword("partial"),
+            sql_keyword("simple"),
+        )),
+    );
+    let on_action = tuple((
+        multispace1,
+        sql_keyword("on"),
+        space1,
+        alt((sql_keyword("delete"), sql_keyword("update"))),
+        space1,
+        parse_reference_option,
+    ));
+    let deferrable = tuple((
+        multispace1,
+        alt((
+            recognize(tuple((
+                opt(tuple((sql_keyword("not"), space1))),
+                sql_keyword("deferrable"),
+            ))),
+            recognize(tuple((
+                sql_keyword("initially"),
+                space1,
+                alt((sql_keyword("deferred"), sql_keyword("immediate"))),
+            ))),
+        )),
+    ));
+    let mut parser = tuple((
+        sql_keyword("references"),
+        space1,
+        pg_table_name,
+        opt(pg_column_name_list),
+        opt(match_type),
+        many_m_n(0, 2, on_action),
+        many0(deferrable),
+    ));
+    match parser(input) {
+        Ok((remain, (_, _, (_, table_name), column_names, match_type, action_arr, _))) => {
+            let mut reference = OneReference {
+                table_name,
+                column_names: column_names.unwrap_or_default(),
+                match_type: match_type.map(|typ| typ.to_uppercase()),
+                ..Default::default()
+            };
+            for (_, _, _, event, _, action) in action_arr {
+                if event.eq_ignore_ascii_case("delete") {
+                    reference.on_delete = Some(action);
+                } else {
+                    reference.on_update = Some(action);
+                }
+            }
+            Ok((remain, reference))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析约束名，如：`CONSTRAINT user_pkey `
+fn pg_constraint_name(input: &str) -> IResult<&str, String> {
+    match tuple((
+        sql_keyword("constraint"),
+        space1,
+        pg_identifier,
+        multispace1,
+    ))(input)
+    {
+        Ok((remain, (_, _, name, _))) => Ok((remain, name)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析 check 约束，如：`CHECK (age > 0) NO INHERIT`
+fn pg_check(input: &str) -> IResult<&str, String> {
+    let no_inherit = tuple((space1, sql_keyword("no"), space1, sql_keyword("inherit")));
+    match tuple((
+        sql_keyword("check"),
+        space0,
+        parse_paren_expr,
+        opt(no_inherit),
+    ))(input)
+    {
+        Ok((remain, (_, _, expr, _))) => Ok((remain, expr)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 字段上的一个属性，`identity` 表示是否为 `GENERATED ... AS IDENTITY`
+fn parse_pg_column_attr(input: &str) -> IResult<&str, (ColumnAttrEnum, bool)> {
+    let not_null = tuple((sql_keyword("not"), space1, sql_keyword("null")));
+    let identity = tuple((
+        sql_keyword("generated"),
+        space1,
+        alt((
+            recognize(sql_keyword("always")),
+            recognize(tuple((sql_keyword("by"), space1, sql_keyword("default")))),
+        )),
+        space1,
+        sql_keyword("as"),
+        space1,
+        sql_keyword("identity"),
+        opt(preceded(space0, parse_paren_expr)),
+    ));
+    let generated = tuple((
+        sql_keyword("generated"),
+        space1,
+        sql_keyword("always"),
+        space1,
+        sql_keyword("as"),
+        space0,
+        parse_paren_expr,
+        space1,
+        sql_keyword("stored"),
+    ));
+    let unique = tuple((
+        sql_keyword("unique"),
+        opt(tuple((
+            space1,
+            sql_keyword("nulls"),
+            opt(tuple((space1, sql_keyword("not")))),
+            space1,
+            sql_keyword("distinct"),
+        ))),
+    ));
+    let attr = alt((
+        map(not_null, |_| ColumnAttrEnum::Nullable(false)),
+        map(sql_keyword("null"), |_| ColumnAttrEnum::Nullable(true)),
+        map(
+            preceded(
+                tuple((sql_keyword("default"), space1)),
+                parse_pg_default_value,
+            ),
+            ColumnAttrEnum::Default,
+        ),
+        map(
+            tuple((sql_keyword("primary"), space1, sql_keyword("key"))),
+            |_| ColumnAttrEnum::PrimaryKey,
+        ),
+        map(unique, |_| ColumnAttrEnum::UniqueKey),
+        map(pg_check, |expr| {
+            ColumnAttrEnum::Check(OneCheck {
+                name: None,
+                expr,
+                enforced: true,
+            })
+        }),
+        map(parse_pg_reference, ColumnAttrEnum::References),
+        map(generated, |(_, _, _, _, _, _, expr, _, _)| {
+            ColumnAttrEnum::Generated(GeneratedColumn { expr, stored: true })
+        }),
+        map(
+            preceded(tuple((sql_keyword("collate"), space1)), pg_dotted_name),
+            |name_arr| ColumnAttrEnum::Collate(name_arr.join(".")),
+        ),
+    ));
+    let attr = alt((
+        map(identity, |_| {
+            (
+                ColumnAttrEnum::Default(DefaultEnum::DefaultAutoIncrement),
+                true,
+            )
+        }),
+        map(attr, |attr| (attr, false)),
+    ));
+    match tuple((opt(pg_constraint_name), attr))(input) {
+        Ok((remain, (constraint_name, (attr, is_identity)))) => {
+            let attr = match attr {
+                ColumnAttrEnum::Check(check) => ColumnAttrEnum::Check(OneCheck {
+                    name: constraint_name,
+                    ..check
+                }),
+                _ => attr,
+            };
+            Ok((remain, (attr, is_identity)))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析一个字段声明，不包含结尾的逗号。如：`"id" bigserial PRIMARY KEY`
+pub fn parse_pg_column_definition(input: &str) -> IResult<&str, OneColumn> {
+    let mut parser = context(
+        "column definition",
+        tuple((
+            pg_identifier,
+            space1,
+            context("data type", cut(parse_pg_data_type)),
+            many0(preceded(multispace1, parse_pg_column_attr)),
+        )),
+    );
+    match parser(input) {
+        Ok((remain, (name, _, (typ, serial), attr_arr))) => {
+            let mut column = OneColumn {
+                name,
+                typ,
+                auto_increment: serial,
+                ..Default::default()
+            };
+            let mut identity = false;
+            for (attr, is_identity) in attr_arr {
+                identity |= is_identity;
+                column.set_attr(attr);
+            }
+            // serial、identity 字段隐式地不允许为 NULL
+            if serial || identity {
+                column.nullable = false;
+            }
+            Ok((remain, column))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析表级别的约束，如：`CONSTRAINT user_pkey PRIMARY KEY (id)`
+pub fn parse_pg_table_constraint(input: &str) -> IResult<&str, OneLineEnum> {
+    let index = tuple((
+        alt((
+            recognize(tuple((sql_keyword("primary"), space1, sql_keyword("key")))),
+            sql_keyword("unique"),
+        )),
+        pg_column_name_list,
+    ));
+    let foreign_key = tuple((
+        sql_keyword("foreign"),
+        space1,
+        sql_keyword("key"),
+        pg_column_name_list,
+        multispace1,
+        parse_pg_reference,
+    ));
+    let constraint = alt((
+        map(index, |(typ, column_names)| {
+            (Some(typ), column_names, None, None)
+        }),
+        map(foreign_key, |(_, _, _, column_names, _, reference)| {
+            (None, column_names, Some(reference), None)
+        }),
+        map(pg_check, |expr| (None, vec![], None, Some(expr))),
+    ));
+    match tuple((opt(pg_constraint_name), constraint))(input) {
+        Ok((remain, (name, (index_typ, column_names, reference, check_expr)))) => {
+            let line = if let Some(reference) = reference {
+                OneLineEnum::ForeignKey(OneForeignKey {
+                    name,
+                    index_name: None,
+                    column_names,
+                    reference,
+                })
+            } else if let Some(expr) = check_expr {
+                OneLineEnum::Check(OneCheck {
+                    name,
+                    expr,
+                    enforced: true,
+                })
+            } else {
+                let typ = if index_typ
+                    .map(|typ| typ.to_lowercase().starts_with("primary"))
+                    .unwrap_or(false)
+                {
+                    IndexIdxTyeEnum::Primary
+                } else {
+                    IndexIdxTyeEnum::Unique
+                };
+                OneLineEnum::Index(OneIndex {
+                    name: name.unwrap_or_default(),
+                    using_type: None,
+                    typ,
+                    key_parts: column_names
+                        .iter()
+                        .map(|name| OneKeyPart::new(name))
+                        .collect(),
+                    option: IndexOption::default(),
+                })
+            };
+            Ok((remain, line))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析建表语句体中的一行，无论是字段声明还是约束声明
+pub fn parse_pg_one_define_line(input: &str) -> IResult<&str, OneLineEnum> {
+    let column_line = map(parse_pg_column_definition, OneLineEnum::Column);
+    let mut parser = tuple((
+        multispace0,
+        alt((parse_pg_table_constraint, column_line)),
+        context("column attribute", cut(parse_define_line_end)),
+    ));
+    match parser(input) {
+        Ok((remain, (_, line, _))) => Ok((remain, line)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析建表语句的开头，返回 (schema, 表名)
+/// 如：CREATE UNLOGGED TABLE IF NOT EXISTS public.user
+pub fn parse_pg_create_table(input: &str) -> IResult<&str, (Option<String>, String)> {
+    let table_kind = tuple((
+        opt(tuple((
+            alt((sql_keyword("global"), sql_keyword("local"))),
+            space1,
+        ))),
+        alt((
+            sql_keyword("temporary"),
+            sql_keyword("temp"),
+            sql_keyword("unlogged"),
+        )),
+        space1,
+    ));
+    let if_not_exists = tuple((
+        space1,
+        sql_keyword("if"),
+        space1,
+        sql_keyword("not"),
+        space1,
+        sql_keyword("exists"),
+    ));
+    let mut parser = tuple((
+        sql_keyword("create"),
+        space1,
+        opt(table_kind),
+        sql_keyword("table"),
+        opt(if_not_exists),
+        space1,
+        pg_table_name,
+        multispace0,
+    ));
+    match parser(input) {
+        Ok((remain, (_, _, _, _, _, _, table_name, _))) => Ok((remain, table_name)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析建表语句体之后的表配置，如：`INHERITS (base)`、`PARTITION BY RANGE (created_at)`、`WITH (fillfactor=70)`
+pub fn parse_pg_table_option(input: &str) -> IResult<&str, TableOption> {
+    let paren_option = tuple((
+        multispace0,
+        alt((sql_keyword("inherits"), sql_keyword("with"))),
+        space0,
+        parse_paren_expr,
+    ));
+    let name_option = tuple((
+        multispace0,
+        alt((sql_keyword("tablespace"), sql_keyword("using"))),
+        space1,
+        pg_identifier,
+    ));
+    let item = alt((
+        map(table_option_partition, |partition| {
+            ("PARTITION".to_string(), partition)
+        }),
+        map(paren_option, |(_, key, _, value)| {
+            (key.to_uppercase(), format!("({})", value))
+        }),
+        map(name_option, |(_, key, _, value)| {
+            (key.to_uppercase(), value)
+        }),
+    ));
+    match many0(item)(input) {
+        Ok((remain, item_arr)) => {
+            let mut option = TableOption::default();
+            for (key, value) in item_arr {
+                if key == "PARTITION" {
+                    option.partition = Some(value);
+                } else {
+                    option.extra.insert(key, value);
+                }
+            }
+            Ok((remain, option))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析整个 PostgreSQL 建表语句
+/// 如：CREATE TABLE public."user" (id bigserial PRIMARY KEY, name text NOT NULL);
+pub fn parse_create_sql(input: &str) -> IResult<&str, TableSchema> {
+    let body = tuple((
+        tag("("),
+        multispace0,
+        many1(parse_pg_one_define_line),
+        multispace0,
+        tag(")"),
+    ));
+    let mut parser = context(
+        "create table",
+        tuple((
+            parse_pg_create_table,
+            context("table body", cut(body)),
+            context("table option", cut(parse_pg_table_option)),
+            opt(tuple((multispace0, tag(";")))),
+        )),
+    );
+    match parser(input) {
+        Ok((remain, ((schema_name, table_name), (_, _, line_arr, _, _), option, _))) => {
+            let mut table = TableSchema::new(table_name, line_arr, option);
+            table.dialect = DialectEnum::Postgres;
+            table.schema_name = schema_name;
+            Ok((remain, table))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// `COMMENT ON` 语句
+#[derive(Debug, PartialEq, Eq)]
+pub enum PgCommentEnum {
+    /// COMMENT ON TABLE schema.table IS '...'，内容为 (schema, 表名, 注释)
+    Table(Option<String>, String, Option<String>),
+    /// COMMENT ON COLUMN schema.table.column IS '...'，内容为 (schema, 表名, 字段名, 注释)
+    Column(Option<String>, String, String, Option<String>),
+}
+
+/// 解析 `COMMENT ON TABLE`、`COMMENT ON COLUMN` 语句，注释为 NULL 时表示删除注释
+pub fn parse_pg_comment_on(input: &str) -> IResult<&str, PgCommentEnum> {
+    let comment_value = alt((
+        map(sql_keyword("null"), |_| None),
+        map(parse_sql_string, Some),
+    ));
+    let mut parser = tuple((
+        sql_keyword("comment"),
+        space1,
+        sql_keyword("on"),
+        space1,
+        alt((sql_keyword("table"), sql_keyword("column"))),
+        space1,
+        pg_dotted_name,
+        multispace1,
+        sql_keyword("is"),
+        multispace1,
+        comment_value,
+        opt(tuple((multispace0, tag(";")))),
+    ));
+    let (remain, (_, _, _, _, target, _, mut name_arr, _, _, _, comment, _)) = parser(input)?;
+    let is_column = target.eq_ignore_ascii_case("column");
+    let min_len = if is_column { 2 } else { 1 };
+    if name_arr.len() < min_len || name_arr.len() > min_len + 1 {
+        return Err(Err::Error(VerboseError::from_error_kind(
+            input,
+            ErrorKind::Verify,
+        )));
+    }
+    let column_name = if is_column { name_arr.pop() } else { None };
+    let table_name = name_arr.pop().unwrap_or_default();
+    let schema_name = name_arr.pop();
+    let comment_on = match column_name {
+        Some(column_name) => PgCommentEnum::Column(schema_name, table_name, column_name, comment),
+        None => PgCommentEnum::Table(schema_name, table_name, comment),
+    };
+    Ok((remain, comment_on))
+}
+
+/// 解析一条其他语句，直到语句结尾的 `;`，返回语句原文
+/// 与 MySQL 不同，需要处理函数体中常见的 `$$ ... $$` 形式的字符串
+pub fn parse_pg_other_statement(input: &str) -> IResult<&str, String> {
+    let mut end = input.len();
+    let mut idx = 0;
+    while idx < input.len() {
+        let rest = &input[idx..];
+        let c = rest.chars().next().unwrap_or_default();
+        if c == '\'' || c == '"' {
+            let close = rest[1..].find(c).map(|pos| pos + 2).unwrap_or(rest.len());
+            idx += close;
+            continue;
+        }
+        if rest.starts_with("--") {
+            idx += rest.find('\n').unwrap_or(rest.len());
+            continue;
+        }
+        if rest.starts_with("/*") {
+            idx += rest.find("*/").map(|pos| pos + 2).unwrap_or(rest.len());
+            continue;
+        }
+        if c == '$' {
+            // $tag$ ... $tag$
+            let tag_len = rest[1..]
+                .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+                .map(|pos| pos + 1);
+            if let Some(tag_len) = tag_len {
+                if rest[tag_len..].starts_with('$') {
+                    let dollar_tag = &rest[..tag_len + 1];
+                    let body_start = dollar_tag.len();
+                    idx += rest[body_start..]
+                        .find(dollar_tag)
+                        .map(|pos| body_start + pos + dollar_tag.len())
+                        .unwrap_or(rest.len());
+                    continue;
+                }
+            }
+        }
+        if c == ';' {
+            end = idx;
+            break;
+        }
+        idx += c.len_utf8();
+    }
+    let statement = input[..end].trim();
+    if statement.is_empty() && end == input.len() {
+        return Err(Err::Error(VerboseError::from_error_kind(
+            input,
+            ErrorKind::Eof,
+        )));
+    }
+    let remain = if end < input.len() {
+        &input[end + 1..]
+    } else {
+        &input[end..]
+    };
+    Ok((remain, statement.to_string()))
+}
+
+/// 解析整个 PostgreSQL schema 文件，如 `pg_dump --schema-only` 导出的文件
+/// 建表语句被解析成 `TableSchema`，`COMMENT ON` 语句中的注释会合并到对应的表和字段上，其余语句会被跳过并记录下来
+pub fn parse_schema_file(input: &str) -> IResult<&str, SchemaFile> {
+    let mut table_arr: Vec<TableSchema> = vec![];
+    let mut table_line_arr: Vec<usize> = vec![];
+    let mut skipped_arr: Vec<SkippedStatement> = vec![];
+    let mut comment_arr: Vec<PgCommentEnum> = vec![];
+    let mut remain = input;
+    loop {
+        remain = parse_space_or_comment(remain)?.0;
+        if remain.is_empty() {
+            break;
+        }
+        let line = input[..input.len() - remain.len()].matches('\n').count() + 1;
+        match parse_create_sql(remain) {
+            Ok((next, table)) => {
+                table_arr.push(table);
+                table_line_arr.push(line);
+                remain = next;
+                continue;
+            }
+            // 已经确定是建表语句但内容有误，不能当作其他语句跳过
+            Err(err @ Err::Failure(_)) => return Err(err),
+            Err(_) => {}
+        }
+        if let Ok((next, comment_on)) = parse_pg_comment_on(remain) {
+            comment_arr.push(comment_on);
+            remain = next;
+            continue;
+        }
+        let (next, statement) = parse_pg_other_statement(remain)?;
+        if !statement.is_empty() {
+            skipped_arr.push(SkippedStatement { line, statement });
+        }
+        remain = next;
+    }
+    for comment_on in comment_arr {
+        let (schema_name, table_name) = match &comment_on {
+            PgCommentEnum::Table(schema_name, table_name, _) => (schema_name, table_name),
+            PgCommentEnum::Column(schema_name, table_name, _, _) => (schema_name, table_name),
+        };
+        let table = table_arr.iter_mut().find(|table| {
+            &table.table_name == table_name
+                && (schema_name.is_none() || &table.schema_name == schema_name)
+        });
+        let table = match table {
+            Some(table) => table,
+            None => continue,
+        };
+        match comment_on {
+            PgCommentEnum::Table(_, _, comment) => table.option.comment = comment,
+            PgCommentEnum::Column(_, _, column_name, comment) => {
+                if let Some(column) = table.column_mut(&column_name) {
+                    column.comment = comment.unwrap_or_default();
+                }
+            }
+        }
+    }
+    Ok((
+        remain,
+        SchemaFile {
+            table_arr,
+            table_line_arr,
+            skipped_arr,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pg_identifier() {
+        assert_eq!(pg_identifier("UserName"), Ok(("", "username".to_string())));
+        assert_eq!(
+            pg_identifier(r#""User""Name" text"#),
+            Ok((" text", r#"User"Name"#.to_string()))
+        );
+        assert_eq!(
+            pg_table_name(r#"public."user""#),
+            Ok(("", (Some("public".to_string()), "user".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_parse_pg_data_type() {
+        assert_eq!(
+            parse_pg_data_type("bigserial"),
+            Ok(("", (DataTypeEnum::Bigint(IntTypeOption::default()), true)))
+        );
+        assert_eq!(
+            parse_pg_data_type("timestamp(3) with time zone"),
+            Ok(("", (DataTypeEnum::TimestampTz(3), false)))
+        );
+        assert_eq!(
+            parse_pg_data_type("character varying(64)"),
+            Ok(("", (DataTypeEnum::VarChar(64), false)))
+        );
+        assert_eq!(
+            parse_pg_data_type("text[]"),
+            Ok((
+                "",
+                (DataTypeEnum::Array(Box::new(DataTypeEnum::Text)), false)
+            ))
+        );
+        assert_eq!(
+            parse_pg_data_type("numeric(10,2)"),
+            Ok((
+                "",
+                (
+                    DataTypeEnum::Decimal(DecimalTypeOption {
+                        precision: Some(10),
+                        scale: Some(2),
+                        ..Default::default()
+                    }),
+                    false
+                )
+            ))
+        );
+        assert_eq!(
+            parse_pg_data_type("public.mood"),
+            Ok(("", (DataTypeEnum::Custom("public.mood".to_string()), false)))
+        );
+    }
+
+    #[test]
+    fn test_parse_pg_default_value() {
+        assert_eq!(
+            parse_pg_default_value("'draft'::character varying"),
+            Ok(("", DefaultEnum::DefaultStr("draft".to_string())))
+        );
+        assert_eq!(
+            parse_pg_default_value("nextval('user_id_seq'::regclass)"),
+            Ok(("", DefaultEnum::DefaultAutoIncrement))
+        );
+        assert_eq!(
+            parse_pg_default_value("now()"),
+            Ok(("", DefaultEnum::DefaultCurStamp))
+        );
+        assert_eq!(
+            parse_pg_default_value("gen_random_uuid()"),
+            Ok((
+                "",
+                DefaultEnum::DefaultExpr("gen_random_uuid()".to_string())
+            ))
+        );
+        assert_eq!(
+            parse_pg_default_value("0.5"),
+            Ok(("", DefaultEnum::DefaultFloat("0.5".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_parse_create_sql() {
+        let sql = r#"CREATE TABLE IF NOT EXISTS public."order" (
+    id bigint GENERATED ALWAYS AS IDENTITY,
+    uid uuid DEFAULT gen_random_uuid() NOT NULL,
+    user_id integer NOT NULL REFERENCES public.users (id) ON DELETE CASCADE,
+    tags text[] DEFAULT '{}'::text[],
+    payload jsonb,
+    amount numeric(10,2) CONSTRAINT amount_positive CHECK (amount > 0),
+    created_at timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT order_pkey PRIMARY KEY (id),
+    CONSTRAINT order_uid_key UNIQUE (uid)
+);"#;
+        let (remain, table) = parse_create_sql(sql).unwrap();
+        assert_eq!(remain, "");
+        assert_eq!(table.dialect, DialectEnum::Postgres);
+        assert_eq!(table.schema_name, Some("public".to_string()));
+        assert_eq!(table.table_name, "order");
+        assert_eq!(table.column_arr.len(), 7);
+
+        let id = &table.column_arr[0];
+        assert!(id.auto_increment);
+        assert!(!id.nullable);
+
+        let uid = &table.column_arr[1];
+        assert_eq!(uid.typ, DataTypeEnum::Uuid);
+        assert_eq!(
+            uid.default,
+            DefaultEnum::DefaultExpr("gen_random_uuid()".to_string())
+        );
+        assert!(!uid.nullable);
+
+        let user_id = &table.column_arr[2];
+        assert_eq!(
+            user_id.reference,
+            Some(OneReference {
+                table_name: "users".to_string(),
+                column_names: vec!["id".to_string()],
+                on_delete: Some(ReferenceOptionEnum::Cascade),
+                ..Default::default()
+            })
+        );
+
+        assert_eq!(
+            table.column_arr[3].typ,
+            DataTypeEnum::Array(Box::new(DataTypeEnum::Text))
+        );
+        assert_eq!(table.column_arr[4].typ, DataTypeEnum::Jsonb);
+        assert_eq!(
+            table.column_arr[5].check_arr,
+            vec![OneCheck {
+                name: Some("amount_positive".to_string()),
+                expr: "amount > 0".to_string(),
+                enforced: true,
+            }]
+        );
+        assert_eq!(table.column_arr[6].typ, DataTypeEnum::TimestampTz(0));
+        assert_eq!(table.column_arr[6].default, DefaultEnum::DefaultCurStamp);
+
+        assert_eq!(table.index_arr.len(), 2);
+        assert_eq!(table.index_arr[0].typ, IndexIdxTyeEnum::Primary);
+        assert_eq!(table.index_arr[1].name, "order_uid_key");
+        assert_eq!(table.index_arr[1].column_names(), vec!["uid".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_schema_file() {
+        let sql = r#"
+SET statement_timeout = 0;
+
+CREATE FUNCTION public.touch() RETURNS trigger
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    NEW.updated_at := now();
+    RETURN NEW;
+END;
+$$;
+
+CREATE TABLE public.users (
+    id serial NOT NULL,
+    name character varying(50) NOT NULL,
+    PRIMARY KEY (id)
+);
+
+COMMENT ON TABLE public.users IS '用户表';
+COMMENT ON COLUMN public.users.name IS '用户名';
+"#;
+        let (remain, schema) = parse_schema_file(sql).unwrap();
+        assert_eq!(remain, "");
+        assert_eq!(schema.table_arr.len(), 1);
+        assert_eq!(schema.table_line_arr, vec![13]);
+        assert_eq!(schema.skipped_arr.len(), 2);
+        assert_eq!(schema.skipped_arr[1].line, 4);
+        let table = &schema.table_arr[0];
+        assert_eq!(table.option.comment, Some("用户表".to_string()));
+        assert_eq!(table.column_arr[1].comment, "用户名");
+        assert!(table.column_arr[0].auto_increment);
+    }
+
+    #[test]
+    fn test_parse_schema_file_malformed_table() {
+        let sql = r#"
+SET client_encoding = 'UTF8';
+
+CREATE TABLE public.users (
+    id bigint NOT NULL,
+    name character varying(64) BOGUS
+);
+"#;
+        let err = parse_schema_file(sql).unwrap_err();
+        assert!(matches!(err, Err::Failure(_)));
+    }
+}
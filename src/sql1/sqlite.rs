@@ -0,0 +1,816 @@
+//! 使用 nom 解析 SQLite 的建表语句，解析结果与 MySQL 一样是 `TableSchema`
+//!
+//! 与 MySQL 的主要差异：
+//! * 标识符可以用双引号、反引号或方括号包裹
+//! * 字段类型可以是任意名字，甚至可以省略，按照 SQLite 的类型亲和性（type affinity）规则映射到 `DataTypeEnum`
+//! * `INTEGER PRIMARY KEY` 是 rowid 的别名，视为自增字段
+//! * 表配置只有 `WITHOUT ROWID` 和 `STRICT`
+use super::*;
+use nom::multi::separated_list1;
+
+/// 解析 SQLite 中的标识符，支持 `"name"`、`` `name` ``、`[name]` 以及不带引号的写法，保留原始大小写
+pub fn sqlite_identifier(input: &str) -> IResult<&str, String> {
+    let quote_pair = match input.chars().next() {
+        Some('"') => Some('"'),
+        Some('`') => Some('`'),
+        Some('[') => Some(']'),
+        _ => None,
+    };
+    if let Some(close) = quote_pair {
+        let body = &input[1..];
+        let mut name = String::new();
+        let mut chars = body.char_indices().peekable();
+        while let Some((idx, c)) = chars.next() {
+            if c != close {
+                name.push(c);
+                continue;
+            }
+            // 引号中连续的两个引号表示一个引号本身
+            if close != ']' && matches!(chars.peek(), Some((_, next)) if *next == close) {
+                chars.next();
+                name.push(c);
+                continue;
+            }
+            if name.is_empty() {
+                break;
+            }
+            return Ok((&body[idx + 1..], name));
+        }
+        return Err(Err::Error(VerboseError::from_error_kind(
+            input,
+            ErrorKind::Char,
+        )));
+    }
+    let first_char_ok = input
+        .chars()
+        .next()
+        .map(|c| c.is_alphabetic() || c == '_')
+        .unwrap_or(false);
+    if !first_char_ok {
+        return Err(Err::Error(VerboseError::from_error_kind(
+            input,
+            ErrorKind::AlphaNumeric,
+        )));
+    }
+    match take_while1(|c: char| c.is_alphanumeric() || c == '_' || c == '$')(input) {
+        Ok((remain, name)) => Ok((remain, name.to_string())),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析可能带有 schema 的表名，如：`main.user`，返回 (schema, 表名)
+pub fn sqlite_table_name(input: &str) -> IResult<&str, (Option<String>, String)> {
+    match tuple((
+        sqlite_identifier,
+        opt(preceded(tag("."), sqlite_identifier)),
+    ))(input)
+    {
+        Ok((remain, (first, Some(second)))) => Ok((remain, (Some(first), second))),
+        Ok((remain, (first, None))) => Ok((remain, (None, first))),
+        Err(err) => Err(err),
+    }
+}
+
+/// 字段约束中的关键字，类型名中不能出现这些关键字
+fn sqlite_constraint_keyword(input: &str) -> IResult<&str, &str> {
+    alt((
+        sql_keyword("constraint"),
+        sql_keyword("primary"),
+        sql_keyword("not"),
+        sql_keyword("null"),
+        sql_keyword("unique"),
+        sql_keyword("check"),
+        sql_keyword("default"),
+        sql_keyword("collate"),
+        sql_keyword("references"),
+        sql_keyword("generated"),
+        sql_keyword("as"),
+    ))(input)
+}
+
+/// 根据类型名以及长度、精度映射到 `DataTypeEnum`
+/// 不认识的类型名按 SQLite 的类型亲和性规则处理：
+/// 包含 INT 为整数；包含 CHAR、CLOB、TEXT 为文本；包含 BLOB 为二进制；包含 REAL、FLOA、DOUB 为浮点数；其余为 NUMERIC
+pub fn sqlite_type_from_name(name: &str, size: Option<(u32, Option<u32>)>) -> DataTypeEnum {
+    let name = name.to_lowercase();
+    let int_option = IntTypeOption {
+        width: size.map(|(width, _)| width),
+        ..Default::default()
+    };
+    let decimal_option = DecimalTypeOption {
+        precision: size.map(|(precision, _)| precision),
+        scale: size.and_then(|(_, scale)| scale),
+        ..Default::default()
+    };
+    match name.as_str() {
+        "tinyint" => DataTypeEnum::TinyInt(int_option),
+        "smallint" | "int2" => DataTypeEnum::SmallInt(int_option),
+        "mediumint" => DataTypeEnum::MediumInt(int_option),
+        "int" => DataTypeEnum::Int(int_option),
+        // SQLite 的 INTEGER 是 64 位整数
+        "integer" | "bigint" | "int8" => DataTypeEnum::Bigint(int_option),
+        "unsigned big int" => DataTypeEnum::Bigint(IntTypeOption {
+            unsigned: true,
+            ..int_option
+        }),
+        "boolean" | "bool" => DataTypeEnum::Bool,
+        "float" => DataTypeEnum::Float(decimal_option),
+        "real" | "double" | "double precision" => DataTypeEnum::Double(decimal_option),
+        "numeric" | "decimal" => DataTypeEnum::Decimal(decimal_option),
+        "char" | "character" | "nchar" | "native character" => {
+            DataTypeEnum::Char(size.map(|(len, _)| len).unwrap_or(1))
+        }
+        "text" | "clob" => DataTypeEnum::Text,
+        "blob" => DataTypeEnum::Blob,
+        "date" => DataTypeEnum::Date,
+        "datetime" => DataTypeEnum::DateTime(0),
+        "timestamp" => DataTypeEnum::Timestamp(0),
+        "time" => DataTypeEnum::Time(0),
+        "json" => DataTypeEnum::Json,
+        _ if name.contains("int") => DataTypeEnum::Bigint(int_option),
+        _ if name.contains("char") || name.contains("clob") || name.contains("text") => {
+            match size {
+                Some((len, _)) => DataTypeEnum::VarChar(len),
+                None => DataTypeEnum::Text,
+            }
+        }
+        _ if name.contains("blob") => DataTypeEnum::Blob,
+        _ if name.contains("real") || name.contains("floa") || name.contains("doub") => {
+            DataTypeEnum::Double(decimal_option)
+        }
+        _ => DataTypeEnum::Decimal(decimal_option),
+    }
+}
+
+/// 解析 SQLite 的字段类型，如：`INTEGER`、`VARCHAR(255)`、`UNSIGNED BIG INT`、`DECIMAL(10,5)`
+/// 返回类型以及原始的类型名
+pub fn parse_sqlite_data_type(input: &str) -> IResult<&str, (DataTypeEnum, String)> {
+    let type_word = preceded(
+        not(sqlite_constraint_keyword),
+        take_while1(|c: char| c.is_alphanumeric() || c == '_'),
+    );
+    let mut parser = tuple((
+        separated_list1(space1, type_word),
+        opt(preceded(space0, type_float_size)),
+    ));
+    match parser(input) {
+        Ok((remain, (word_arr, size))) => {
+            let name = word_arr.join(" ");
+            Ok((remain, (sqlite_type_from_name(&name, size), name)))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析冲突处理子句，如：`ON CONFLICT REPLACE`
+fn sqlite_conflict_clause(input: &str) -> IResult<&str, &str> {
+    let mut parser = tuple((
+        space1,
+        sql_keyword("on"),
+        space1,
+        sql_keyword("conflict"),
+        space1,
+        alt((
+            sql_keyword("rollback"),
+            sql_keyword("abort"),
+            sql_keyword("fail"),
+            sql_keyword("ignore"),
+            sql_keyword("replace"),
+        )),
+    ));
+    match parser(input) {
+        Ok((remain, (_, _, _, _, _, action))) => Ok((remain, action)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析括号包裹的字段列表，字段后面可以有 COLLATE、ASC、DESC，如：`("id", name COLLATE NOCASE DESC)`
+pub fn sqlite_key_parts(input: &str) -> IResult<&str, Vec<OneKeyPart>> {
+    let key_part = tuple((
+        sqlite_identifier,
+        opt(tuple((
+            space1,
+            sql_keyword("collate"),
+            space1,
+            sqlite_identifier,
+        ))),
+        opt(preceded(
+            space1,
+            alt((sql_keyword("asc"), sql_keyword("desc"))),
+        )),
+    ));
+    let mut parser = tuple((
+        space0,
+        tag("("),
+        multispace0,
+        separated_list1(tuple((multispace0, tag(","), multispace0)), key_part),
+        multispace0,
+        tag(")"),
+    ));
+    match parser(input) {
+        Ok((remain, (_, _, _, part_arr, _, _))) => Ok((
+            remain,
+            part_arr
+                .into_iter()
+                .map(|(column_name, _, order)| OneKeyPart {
+                    column_name: Some(column_name),
+                    order: order.map(|order| {
+                        if order.eq_ignore_ascii_case("desc") {
+                            IndexOrderEnum::Desc
+                        } else {
+                            IndexOrderEnum::Asc
+                        }
+                    }),
+                    ..Default::default()
+                })
+                .collect(),
+        )),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析引用声明，如：`REFERENCES "user" (id) ON DELETE CASCADE DEFERRABLE INITIALLY DEFERRED`
+pub fn parse_sqlite_reference(input: &str) -> IResult<&str, OneReference> {
+    let column_list = tuple((
+        space0,
+        tag("("),
+        multispace0,
+        separated_list1(
+            tuple((multispace0, tag(","), multispace0)),
+            sqlite_identifier,
+        ),
+        multispace0,
+        tag(")"),
+    ));
+    let on_action = tuple((
+        multispace1,
+        sql_keyword("on"),
+        space1,
+        alt((sql_keyword("delete"), sql_keyword("update"))),
+        space1,
+        parse_reference_option,
+    ));
+    let match_name = tuple((multispace1, sql_keyword("match"), space1, sqlite_identifier));
+    let deferrable = tuple((
+        multispace1,
+        opt(tuple((sql_keyword("not"), space1))),
+        sql_keyword("deferrable"),
+        opt(tuple((
+            space1,
+            sql_keyword("initially"),
+            space1,
+            alt((sql_keyword("deferred"), sql_keyword("immediate"))),
+        ))),
+    ));
+    let mut parser = tuple((
+        sql_keyword("references"),
+        space1,
+        sqlite_identifier,
+        opt(column_list),
+        many0(alt((
+            map(on_action, |(_, _, _, event, _, action)| {
+                Some((event, action))
+            }),
+            map(match_name, |_| None),
+        ))),
+        opt(deferrable),
+    ));
+    match parser(input) {
+        Ok((remain, (_, _, table_name, column_list, action_arr, _))) => {
+            let mut reference = OneReference {
+                table_name,
+                column_names: column_list
+                    .map(|(_, _, _, name_arr, _, _)| name_arr)
+                    .unwrap_or_default(),
+                ..Default::default()
+            };
+            for (event, action) in action_arr.into_iter().flatten() {
+                if event.eq_ignore_ascii_case("delete") {
+                    reference.on_delete = Some(action);
+                } else {
+                    reference.on_update = Some(action);
+                }
+            }
+            Ok((remain, reference))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// 字段上的一个约束
+enum SqliteColumnAttrEnum {
+    /// 可以直接记录到 `OneColumn` 上的属性
+    Attr(ColumnAttrEnum),
+    /// PRIMARY KEY [ASC | DESC] [conflict-clause] [AUTOINCREMENT]，内容为是否为 DESC 以及是否声明了 AUTOINCREMENT
+    PrimaryKey(bool, bool),
+}
+
+/// 解析字段上的一个约束，约束前面必须有空白
+fn parse_sqlite_column_attr(input: &str) -> IResult<&str, SqliteColumnAttrEnum> {
+    let constraint_name = tuple((
+        multispace1,
+        sql_keyword("constraint"),
+        space1,
+        sqlite_identifier,
+    ));
+    let primary_key = tuple((
+        sql_keyword("primary"),
+        space1,
+        sql_keyword("key"),
+        opt(preceded(
+            space1,
+            alt((sql_keyword("asc"), sql_keyword("desc"))),
+        )),
+        opt(sqlite_conflict_clause),
+        opt(preceded(space1, sql_keyword("autoincrement"))),
+    ));
+    let not_null = tuple((
+        sql_keyword("not"),
+        space1,
+        sql_keyword("null"),
+        opt(sqlite_conflict_clause),
+    ));
+    let unique = tuple((sql_keyword("unique"), opt(sqlite_conflict_clause)));
+    let current_date = preceded(
+        tuple((sql_keyword("default"), space1)),
+        alt((sql_keyword("current_date"), sql_keyword("current_time"))),
+    );
+    let attr = alt((
+        map(primary_key, |(_, _, _, order, _, autoincrement)| {
+            let is_desc = order.is_some_and(|order| order.eq_ignore_ascii_case("desc"));
+            SqliteColumnAttrEnum::PrimaryKey(is_desc, autoincrement.is_some())
+        }),
+        map(not_null, |_| {
+            SqliteColumnAttrEnum::Attr(ColumnAttrEnum::Nullable(false))
+        }),
+        map(sql_keyword("null"), |_| {
+            SqliteColumnAttrEnum::Attr(ColumnAttrEnum::Nullable(true))
+        }),
+        map(unique, |_| {
+            SqliteColumnAttrEnum::Attr(ColumnAttrEnum::UniqueKey)
+        }),
+        map(parse_check, |check| {
+            SqliteColumnAttrEnum::Attr(ColumnAttrEnum::Check(check))
+        }),
+        map(current_date, |expr: &str| {
+            SqliteColumnAttrEnum::Attr(ColumnAttrEnum::Default(DefaultEnum::DefaultExpr(
+                expr.to_uppercase(),
+            )))
+        }),
+        map(
+            alt((
+                parse_default_int,
+                parse_default_float,
+                parse_default_str,
+                parse_default_null,
+                parse_default_bool,
+                parse_default_current_timestamp,
+                parse_default_expr,
+            )),
+            |default| SqliteColumnAttrEnum::Attr(ColumnAttrEnum::Default(default)),
+        ),
+        map(
+            preceded(tuple((sql_keyword("collate"), space1)), sqlite_identifier),
+            |collate| SqliteColumnAttrEnum::Attr(ColumnAttrEnum::Collate(collate)),
+        ),
+        map(parse_sqlite_reference, |reference| {
+            SqliteColumnAttrEnum::Attr(ColumnAttrEnum::References(reference))
+        }),
+    ));
+    let generated = map(parse_column_generated, |generated| {
+        SqliteColumnAttrEnum::Attr(ColumnAttrEnum::Generated(generated))
+    });
+    match tuple((
+        opt(constraint_name),
+        alt((preceded(multispace1, attr), generated)),
+    ))(input)
+    {
+        Ok((remain, (_, attr))) => Ok((remain, attr)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析一个字段声明，不包含结尾的逗号。如：`id INTEGER PRIMARY KEY AUTOINCREMENT`
+/// 字段类型可以省略，此时按 SQLite 的规则视为 BLOB
+pub fn parse_sqlite_column_definition(input: &str) -> IResult<&str, OneColumn> {
+    match sqlite_column_definition(input) {
+        Ok((remain, (column, _))) => Ok((remain, column)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析一个字段声明，同时返回原始的类型名，用于判断字段是否为 rowid 的别名
+fn sqlite_column_definition(input: &str) -> IResult<&str, (OneColumn, String)> {
+    let mut parser = context(
+        "column definition",
+        tuple((
+            sqlite_identifier,
+            opt(preceded(space1, parse_sqlite_data_type)),
+            many0(parse_sqlite_column_attr),
+        )),
+    );
+    match parser(input) {
+        Ok((remain, (name, typ, attr_arr))) => {
+            let (typ, type_name) = typ.unwrap_or((DataTypeEnum::Blob, String::new()));
+            let mut column = OneColumn {
+                name,
+                typ,
+                ..Default::default()
+            };
+            for attr in attr_arr {
+                match attr {
+                    SqliteColumnAttrEnum::Attr(attr) => column.set_attr(attr),
+                    SqliteColumnAttrEnum::PrimaryKey(is_desc, autoincrement) => {
+                        column.set_attr(ColumnAttrEnum::PrimaryKey);
+                        // INTEGER PRIMARY KEY 是 rowid 的别名，插入时会自动分配值，
+                        // 但 INTEGER PRIMARY KEY DESC 不是
+                        if autoincrement || (is_integer_type(&type_name) && !is_desc) {
+                            column.auto_increment = true;
+                        }
+                    }
+                }
+            }
+            Ok((remain, (column, type_name)))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析表级别的约束，如：`CONSTRAINT pk PRIMARY KEY (a, b)`、`UNIQUE (email) ON CONFLICT REPLACE`
+pub fn parse_sqlite_table_constraint(input: &str) -> IResult<&str, OneLineEnum> {
+    let constraint_name = tuple((
+        sql_keyword("constraint"),
+        space1,
+        sqlite_identifier,
+        multispace1,
+    ));
+    let index = tuple((
+        alt((
+            recognize(tuple((sql_keyword("primary"), space1, sql_keyword("key")))),
+            sql_keyword("unique"),
+        )),
+        sqlite_key_parts,
+        opt(sqlite_conflict_clause),
+    ));
+    let foreign_key = tuple((
+        sql_keyword("foreign"),
+        space1,
+        sql_keyword("key"),
+        sqlite_key_parts,
+        multispace1,
+        parse_sqlite_reference,
+    ));
+    let check = tuple((sql_keyword("check"), space0, parse_paren_expr));
+    let constraint = alt((
+        map(index, |(typ, key_parts, _)| {
+            (Some((typ, key_parts)), None, None)
+        }),
+        map(foreign_key, |(_, _, _, key_parts, _, reference)| {
+            (None, Some((key_parts, reference)), None)
+        }),
+        map(check, |(_, _, expr)| (None, None, Some(expr))),
+    ));
+    match tuple((opt(constraint_name), constraint))(input) {
+        Ok((remain, (name, (index, foreign_key, check_expr)))) => {
+            let name = name.map(|(_, _, name, _)| name);
+            let line = if let Some((typ, key_parts)) = index {
+                let typ = if typ.to_lowercase().starts_with("primary") {
+                    IndexIdxTyeEnum::Primary
+                } else {
+                    IndexIdxTyeEnum::Unique
+                };
+                OneLineEnum::Index(OneIndex {
+                    name: name.unwrap_or_default(),
+                    using_type: None,
+                    typ,
+                    key_parts,
+                    option: IndexOption::default(),
+                })
+            } else if let Some((key_parts, reference)) = foreign_key {
+                OneLineEnum::ForeignKey(OneForeignKey {
+                    name,
+                    index_name: None,
+                    column_names: key_parts
+                        .into_iter()
+                        .filter_map(|part| part.column_name)
+                        .collect(),
+                    reference,
+                })
+            } else {
+                OneLineEnum::Check(OneCheck {
+                    name,
+                    expr: check_expr.unwrap_or_default(),
+                    enforced: true,
+                })
+            };
+            Ok((remain, line))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析建表语句体中的一行，无论是字段声明还是约束声明
+pub fn parse_sqlite_one_define_line(input: &str) -> IResult<&str, OneLineEnum> {
+    match sqlite_one_define_line(input) {
+        Ok((remain, (line, _))) => Ok((remain, line)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析一行声明，字段声明同时返回原始的类型名，表级别的约束返回空的类型名
+fn sqlite_one_define_line(input: &str) -> IResult<&str, (OneLineEnum, String)> {
+    let constraint_line = map(parse_sqlite_table_constraint, |line| (line, String::new()));
+    let column_line = map(sqlite_column_definition, |(column, type_name)| {
+        (OneLineEnum::Column(column), type_name)
+    });
+    let mut parser = tuple((
+        multispace0,
+        alt((constraint_line, column_line)),
+        context("column attribute", cut(parse_define_line_end)),
+    ));
+    match parser(input) {
+        Ok((remain, (_, line, _))) => Ok((remain, line)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 类型名是否为 `INTEGER`，只有这个类型名的主键才是 rowid 的别名，`INT`、`BIGINT` 都不是
+fn is_integer_type(type_name: &str) -> bool {
+    type_name.eq_ignore_ascii_case("integer")
+}
+
+/// 解析建表语句的开头，返回 (schema, 表名)
+/// 如：CREATE TEMP TABLE IF NOT EXISTS main.user
+pub fn parse_sqlite_create_table(input: &str) -> IResult<&str, (Option<String>, String)> {
+    let if_not_exists = tuple((
+        space1,
+        sql_keyword("if"),
+        space1,
+        sql_keyword("not"),
+        space1,
+        sql_keyword("exists"),
+    ));
+    let mut parser = tuple((
+        sql_keyword("create"),
+        space1,
+        opt(tuple((
+            alt((sql_keyword("temporary"), sql_keyword("temp"))),
+            space1,
+        ))),
+        sql_keyword("table"),
+        opt(if_not_exists),
+        multispace1,
+        sqlite_table_name,
+        multispace0,
+    ));
+    match parser(input) {
+        Ok((remain, (_, _, _, _, _, _, table_name, _))) => Ok((remain, table_name)),
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析表配置 `WITHOUT ROWID`、`STRICT`，多个配置以逗号分隔
+/// 声明了的配置记录在 `TableOption::extra` 中，值为 `true`
+pub fn parse_sqlite_table_option(input: &str) -> IResult<&str, TableOption> {
+    let item = alt((
+        map(
+            tuple((sql_keyword("without"), space1, sql_keyword("rowid"))),
+            |_| "WITHOUT ROWID",
+        ),
+        map(sql_keyword("strict"), |_| "STRICT"),
+    ));
+    let mut parser = opt(preceded(
+        multispace0,
+        separated_list1(tuple((multispace0, tag(","), multispace0)), item),
+    ));
+    match parser(input) {
+        Ok((remain, item_arr)) => {
+            let mut option = TableOption::default();
+            for key in item_arr.unwrap_or_default() {
+                option.extra.insert(key.to_string(), "true".to_string());
+            }
+            Ok((remain, option))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// 解析整个 SQLite 建表语句
+/// 如：CREATE TABLE "user" (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL) STRICT;
+pub fn parse_create_sql(input: &str) -> IResult<&str, TableSchema> {
+    let body = tuple((
+        tag("("),
+        multispace0,
+        many1(sqlite_one_define_line),
+        multispace0,
+        tag(")"),
+    ));
+    let mut parser = context(
+        "create table",
+        tuple((
+            parse_sqlite_create_table,
+            context("table body", cut(body)),
+            context("table option", cut(parse_sqlite_table_option)),
+            opt(tuple((multispace0, tag(";")))),
+        )),
+    );
+    match parser(input) {
+        Ok((remain, ((schema_name, table_name), (_, _, line_arr, _, _), option, _))) => {
+            let integer_name_arr: Vec<String> = line_arr
+                .iter()
+                .filter_map(|(line, type_name)| match line {
+                    OneLineEnum::Column(column) if is_integer_type(type_name) => {
+                        Some(column.name.clone())
+                    }
+                    _ => None,
+                })
+                .collect();
+            let line_arr = line_arr.into_iter().map(|(line, _)| line).collect();
+            let mut table = TableSchema::new(table_name, line_arr, option);
+            table.dialect = DialectEnum::Sqlite;
+            table.schema_name = schema_name;
+            // WITHOUT ROWID 的表没有 rowid，INTEGER PRIMARY KEY 只是普通的主键，也不允许 AUTOINCREMENT
+            if table.option.extra.contains_key("WITHOUT ROWID") {
+                for column in table.column_arr.iter_mut() {
+                    column.auto_increment = false;
+                }
+                return Ok((remain, table));
+            }
+            // 表级别的 PRIMARY KEY 只有一个 INTEGER 字段时，该字段同样是 rowid 的别名，DESC 也不影响
+            let key_name_arr = table
+                .index_arr
+                .iter()
+                .find(|index| index.typ == IndexIdxTyeEnum::Primary)
+                .map(|index| index.column_names())
+                .unwrap_or_default();
+            if let [key_name] = key_name_arr.as_slice() {
+                if integer_name_arr
+                    .iter()
+                    .any(|name| name.eq_ignore_ascii_case(key_name))
+                {
+                    for column in table.column_arr.iter_mut() {
+                        if column.name.eq_ignore_ascii_case(key_name) {
+                            column.auto_increment = true;
+                        }
+                    }
+                }
+            }
+            Ok((remain, table))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sqlite_identifier() {
+        assert_eq!(
+            sqlite_identifier("[order item] TEXT"),
+            Ok((" TEXT", "order item".to_string()))
+        );
+        assert_eq!(
+            sqlite_identifier(r#""Name""x""#),
+            Ok(("", r#"Name"x"#.to_string()))
+        );
+        assert_eq!(sqlite_identifier("UserId"), Ok(("", "UserId".to_string())));
+    }
+
+    #[test]
+    fn test_parse_sqlite_data_type() {
+        assert_eq!(
+            parse_sqlite_data_type("UNSIGNED BIG INT NOT NULL"),
+            Ok((
+                " NOT NULL",
+                (
+                    DataTypeEnum::Bigint(IntTypeOption {
+                        unsigned: true,
+                        ..Default::default()
+                    }),
+                    "UNSIGNED BIG INT".to_string()
+                )
+            ))
+        );
+        assert_eq!(
+            parse_sqlite_data_type("NVARCHAR(100)"),
+            Ok(("", (DataTypeEnum::VarChar(100), "NVARCHAR".to_string())))
+        );
+        // 按照亲和性规则，先匹配到 INT，因此 FLOATING POINT 是整数
+        assert_eq!(
+            parse_sqlite_data_type("FLOATING POINT"),
+            Ok((
+                "",
+                (
+                    DataTypeEnum::Bigint(IntTypeOption::default()),
+                    "FLOATING POINT".to_string()
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_create_sql() {
+        let sql = r#"CREATE TABLE IF NOT EXISTS "order" (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id INTEGER NOT NULL REFERENCES user (id) ON DELETE CASCADE,
+    code TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    amount REAL DEFAULT 0.0 CHECK (amount >= 0),
+    status,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (user_id, code DESC) ON CONFLICT REPLACE
+);"#;
+        let (remain, table) = parse_create_sql(sql).unwrap();
+        assert_eq!(remain, "");
+        assert_eq!(table.dialect, DialectEnum::Sqlite);
+        assert_eq!(table.table_name, "order");
+        assert_eq!(table.column_arr.len(), 6);
+
+        let id = &table.column_arr[0];
+        assert_eq!(id.typ, DataTypeEnum::Bigint(IntTypeOption::default()));
+        assert!(id.primary_key);
+        assert!(id.auto_increment);
+        assert!(!id.nullable);
+
+        let user_id = &table.column_arr[1];
+        assert!(!user_id.nullable);
+        assert_eq!(
+            user_id
+                .reference
+                .as_ref()
+                .map(|reference| &reference.on_delete),
+            Some(&Some(ReferenceOptionEnum::Cascade))
+        );
+
+        let code = &table.column_arr[2];
+        assert!(code.unique_key);
+        assert_eq!(code.collate, Some("NOCASE".to_string()));
+
+        let amount = &table.column_arr[3];
+        assert_eq!(amount.default, DefaultEnum::DefaultFloat("0.0".to_string()));
+        assert_eq!(amount.check_arr[0].expr, "amount >= 0");
+
+        assert_eq!(table.column_arr[4].typ, DataTypeEnum::Blob);
+        assert_eq!(table.column_arr[5].default, DefaultEnum::DefaultCurStamp);
+
+        assert_eq!(table.index_arr.len(), 1);
+        assert_eq!(table.index_arr[0].typ, IndexIdxTyeEnum::Unique);
+        assert_eq!(
+            table.index_arr[0].key_parts[1].order,
+            Some(IndexOrderEnum::Desc)
+        );
+        assert!(table.option.extra.is_empty());
+    }
+
+    #[test]
+    fn test_parse_create_sql_without_rowid() {
+        let sql = r#"CREATE TABLE "user_tag" (
+    id INTEGER PRIMARY KEY,
+    tag TEXT NOT NULL
+) WITHOUT ROWID, STRICT;"#;
+        let (remain, table) = parse_create_sql(sql).unwrap();
+        assert_eq!(remain, "");
+        assert_eq!(
+            table.option.extra.keys().collect::<Vec<&String>>(),
+            vec!["STRICT", "WITHOUT ROWID"]
+        );
+        let id = &table.column_arr[0];
+        assert!(id.primary_key);
+        assert!(!id.auto_increment);
+    }
+
+    #[test]
+    fn test_parse_create_sql_primary_key_without_autoincrement() {
+        let sql = "CREATE TABLE t (a INT PRIMARY KEY, b TEXT)";
+        let (_, table) = parse_create_sql(sql).unwrap();
+        assert!(table.column_arr[0].primary_key);
+        assert!(!table.column_arr[0].auto_increment);
+
+        // INTEGER PRIMARY KEY DESC 不是 rowid 的别名
+        let sql = "CREATE TABLE t (a INTEGER PRIMARY KEY DESC, b TEXT)";
+        let (_, table) = parse_create_sql(sql).unwrap();
+        assert!(table.column_arr[0].primary_key);
+        assert!(!table.column_arr[0].auto_increment);
+    }
+
+    #[test]
+    fn test_parse_create_sql_table_primary_key_rowid() {
+        let sql = "CREATE TABLE t (id integer, b TEXT, PRIMARY KEY (id DESC))";
+        let (_, table) = parse_create_sql(sql).unwrap();
+        assert!(table.column_arr[0].auto_increment);
+        assert!(!table.column_arr[0].nullable);
+
+        let sql = "CREATE TABLE t (id BIGINT, b TEXT, PRIMARY KEY (id))";
+        let (_, table) = parse_create_sql(sql).unwrap();
+        assert!(!table.column_arr[0].auto_increment);
+
+        let sql = "CREATE TABLE t (a INTEGER, b INTEGER, PRIMARY KEY (a, b))";
+        let (_, table) = parse_create_sql(sql).unwrap();
+        assert!(table.column_arr.iter().all(|column| !column.auto_increment));
+
+        let sql = "CREATE TABLE t (id INTEGER, b TEXT, PRIMARY KEY (id)) WITHOUT ROWID";
+        let (_, table) = parse_create_sql(sql).unwrap();
+        assert!(!table.column_arr[0].auto_increment);
+    }
+}
@@ -12,9 +12,20 @@
 
 extern crate tera;
 
+pub mod go;
+pub mod java;
+pub mod json_schema;
+pub mod proto;
+pub mod python;
+pub mod rust;
+pub mod type_map;
+pub mod typescript;
+
 use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 
-use crate::sql1::{DataTypeEnum, OneColumn};
+use crate::sql1::{DataTypeEnum, DecimalTypeOption, IntTypeOption, OneColumn, TableSchema};
 use serde::Serialize;
 use serde_json::from_value;
 use serde_json::to_string;
@@ -22,6 +33,7 @@ use serde_json::Error;
 use serde_json::Value;
 use tera::Context;
 use tera::Tera;
+use type_map::{match_builtin_rule, match_type_rule, TypeLangEnum, TypeRule};
 
 // 字段名
 // 类型
@@ -48,12 +60,7 @@ pub fn lowercase_first(input: &str) -> String {
 
 /// 标识符转为**小**驼峰
 pub fn to_small_case_camel(input: &str) -> String {
-    let str_arr: Vec<&str> = input.split('_').collect();
-    let mut new_arr: Vec<String> = Vec::with_capacity(str_arr.len());
-    for item in str_arr {
-        new_arr.push(lowercase_first(item));
-    }
-    return new_arr.join("");
+    lowercase_first(&to_big_case_camel(input))
 }
 
 /// 标识符转为**大**驼峰
@@ -66,6 +73,34 @@ pub fn to_big_case_camel(input: &str) -> String {
     return new_arr.join("");
 }
 
+/// 标识符转为下划线风格，如：`UserName` -> `user_name`
+pub fn to_underline(input: &str) -> String {
+    let mut result = String::with_capacity(input.len() + 4);
+    let mut prev_lower = false;
+    for c in input.chars() {
+        if c.is_uppercase() {
+            if prev_lower {
+                result.push('_');
+            }
+            result.extend(c.to_lowercase());
+            prev_lower = false;
+        } else {
+            result.push(c);
+            prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        }
+    }
+    result
+}
+
+/// 按指定的风格转换标识符
+pub fn to_name_style(input: &str, style: FieldNameStyleEnum) -> String {
+    match style {
+        FieldNameStyleEnum::SmallCaseCamel => to_small_case_camel(input),
+        FieldNameStyleEnum::BigCaseCamel => to_big_case_camel(input),
+        FieldNameStyleEnum::Underline => to_underline(input),
+    }
+}
+
 pub fn to_big_case_camel_helper(args: &HashMap<String, Value>) -> tera::Result<Value> {
     let str1 = match args.get("word") {
         Some(val) => match from_value::<String>(val.clone()) {
@@ -89,41 +124,136 @@ pub fn to_small_case_camel_helper(args: &HashMap<String, Value>) -> tera::Result
     Ok(serde_json::json!(str1))
 }
 
+/// 整数类型的完整写法，如：`int(11) unsigned zerofill`
+fn int_type_str(name: &str, option: &IntTypeOption) -> String {
+    let mut type_str = name.to_string();
+    if let Some(width) = option.width {
+        type_str += &format!("({})", width);
+    }
+    if option.unsigned {
+        type_str += " unsigned";
+    }
+    if option.zerofill {
+        type_str += " zerofill";
+    }
+    type_str
+}
+
+/// 定点数、浮点数类型的完整写法，如：`decimal(10,2) unsigned`
+fn decimal_type_str(name: &str, option: &DecimalTypeOption) -> String {
+    let mut type_str = name.to_string();
+    match (option.precision, option.scale) {
+        (Some(precision), Some(scale)) => type_str += &format!("({},{})", precision, scale),
+        (Some(precision), None) => type_str += &format!("({})", precision),
+        _ => {}
+    }
+    if option.unsigned {
+        type_str += " unsigned";
+    }
+    if option.zerofill {
+        type_str += " zerofill";
+    }
+    type_str
+}
+
 pub fn transfer_type(typ: DataTypeEnum) -> String {
     match typ {
-        DataTypeEnum::TinyInt => "tinyint".to_string(),
-        DataTypeEnum::SmallInt => "smallint".to_string(),
-        DataTypeEnum::Int => "int".to_string(),
-        DataTypeEnum::Bigint => "bigint".to_string(),
+        DataTypeEnum::TinyInt(option) => int_type_str("tinyint", &option),
+        DataTypeEnum::SmallInt(option) => int_type_str("smallint", &option),
+        DataTypeEnum::MediumInt(option) => int_type_str("mediumint", &option),
+        DataTypeEnum::Int(option) => int_type_str("int", &option),
+        DataTypeEnum::Bigint(option) => int_type_str("bigint", &option),
+        DataTypeEnum::Bit(n) => format!("bit({})", n),
+        DataTypeEnum::Bool => "tinyint(1)".to_string(),
+        DataTypeEnum::Float(option) => decimal_type_str("float", &option),
+        DataTypeEnum::Double(option) => decimal_type_str("double", &option),
+        DataTypeEnum::Char(n) => format!("char({})", n),
         DataTypeEnum::VarChar(n) => {
             format!("varchar({})", n)
         }
-        DataTypeEnum::DateTime(u32) => "datetime".to_string(),
+        DataTypeEnum::Binary(n) => format!("binary({})", n),
+        DataTypeEnum::VarBinary(n) => format!("varbinary({})", n),
+        DataTypeEnum::Date => "date".to_string(),
+        DataTypeEnum::Time(_) => "time".to_string(),
+        DataTypeEnum::DateTime(_) => "datetime".to_string(),
+        DataTypeEnum::Timestamp(_) => "timestamp".to_string(),
+        DataTypeEnum::Year => "year".to_string(),
+        DataTypeEnum::TinyText => "tinytext".to_string(),
         DataTypeEnum::Text => "text".to_string(),
+        DataTypeEnum::MediumText => "mediumtext".to_string(),
+        DataTypeEnum::LongText => "longtext".to_string(),
         DataTypeEnum::BigText => "bigtext".to_string(),
-        DataTypeEnum::Decimal(n) => {
-            format!("decimal({})", n)
-        }
+        DataTypeEnum::TinyBlob => "tinyblob".to_string(),
+        DataTypeEnum::Blob => "blob".to_string(),
+        DataTypeEnum::MediumBlob => "mediumblob".to_string(),
+        DataTypeEnum::LongBlob => "longblob".to_string(),
+        DataTypeEnum::Json => "json".to_string(),
+        DataTypeEnum::Enum(_) => "enum".to_string(),
+        DataTypeEnum::Set(_) => "set".to_string(),
+        DataTypeEnum::Geometry => "geometry".to_string(),
+        DataTypeEnum::Point => "point".to_string(),
+        DataTypeEnum::LineString => "linestring".to_string(),
+        DataTypeEnum::Polygon => "polygon".to_string(),
+        DataTypeEnum::MultiPoint => "multipoint".to_string(),
+        DataTypeEnum::MultiLineString => "multilinestring".to_string(),
+        DataTypeEnum::MultiPolygon => "multipolygon".to_string(),
+        DataTypeEnum::GeometryCollection => "geometrycollection".to_string(),
+        DataTypeEnum::Decimal(option) => decimal_type_str("decimal", &option),
+        DataTypeEnum::Uuid => "uuid".to_string(),
+        DataTypeEnum::Jsonb => "jsonb".to_string(),
+        DataTypeEnum::TimestampTz(_) => "timestamptz".to_string(),
+        DataTypeEnum::Array(item_typ) => format!("{}[]", transfer_type(*item_typ)),
+        DataTypeEnum::Custom(name) => name,
+        _ => "Unknown".to_string(),
+    }
+}
+
+/// 数据库类型对应到 Go 结构体中的类型，先按内置规则映射，decimal 按精度区分
+fn go_type_str(typ: DataTypeEnum) -> String {
+    if let Some(rule) = match_builtin_rule(TypeLangEnum::Go, &typ) {
+        return rule.target.clone();
+    }
+    match typ {
+        // 没有小数位的 decimal 可以用整数精确表示
+        DataTypeEnum::Decimal(DecimalTypeOption {
+            precision: Some(precision),
+            scale: None,
+            ..
+        })
+        | DataTypeEnum::Decimal(DecimalTypeOption {
+            precision: Some(precision),
+            scale: Some(0),
+            ..
+        }) if precision <= 18 => "int64".to_string(),
+        DataTypeEnum::Decimal(_) => "float64".to_string(),
+        DataTypeEnum::Custom(_) => "string".to_string(),
+        DataTypeEnum::Array(item_typ) => format!("[]{}", go_type_str(*item_typ)),
         _ => "Unknown".to_string(),
     }
 }
 
-// 数据库类型对应到结构体中的类型映射 todo
+// 数据库类型对应到结构体中的类型映射，传入完整的字段 `column=field` 时可以按列名、注释等匹配类型映射规则
 pub fn transfer_type_helper(args: &HashMap<String, Value>) -> tera::Result<Value> {
+    transfer_type_by_rule(args, &[])
+}
+
+/// 与 `transfer_type_helper` 相同，传入完整的字段时先按 `rule_arr` 匹配
+fn transfer_type_by_rule(
+    args: &HashMap<String, Value>,
+    rule_arr: &[TypeRule],
+) -> tera::Result<Value> {
+    if let Some(val) = args.get("column") {
+        let column = from_value::<OneColumn>(val.clone())
+            .map_err(|err| tera::Error::msg(format!("column 不是完整的字段：{}", err)))?;
+        let typ = match match_type_rule(rule_arr, &column) {
+            Some(rule) => rule.target.clone(),
+            None => go_type_str(column.typ),
+        };
+        return Ok(serde_json::json!(typ));
+    }
     let typ = match args.get("typ") {
         Some(val) => match from_value::<DataTypeEnum>(val.clone()) {
-            Ok(v) => match v {
-                DataTypeEnum::TinyInt => "int".to_string(),
-                DataTypeEnum::SmallInt => "int".to_string(),
-                DataTypeEnum::Int => "int".to_string(),
-                DataTypeEnum::Bigint => "int64".to_string(),
-                DataTypeEnum::VarChar(_) => "string".to_string(),
-                DataTypeEnum::DateTime(_) => "time.Time".to_string(),
-                DataTypeEnum::Text => "string".to_string(),
-                DataTypeEnum::BigText => "string".to_string(),
-                DataTypeEnum::Decimal(_) => "float64".to_string(),
-                _ => "Unknown".to_string(),
-            },
+            Ok(v) => go_type_str(v),
             Err(_) => "Unknown".to_string(),
         },
         None => "Unknown".to_string(),
@@ -131,7 +261,15 @@ pub fn transfer_type_helper(args: &HashMap<String, Value>) -> tera::Result<Value
     Ok(serde_json::json!(typ))
 }
 
+/// 编译进库中的内置模板，`(模板名, 内容)`
+///
+/// `struct_tpl.tpl` 生成 Go 结构体，变量 `struct_name` 为结构体名，`field_arr` 为表的字段，即 `TableSchema::column_arr`
+const BUILTIN_TPL_ARR: [(&str, &str); 1] =
+    [("struct_tpl.tpl", include_str!("../data/struct_tpl.tpl"))];
+
 /// 定义一个渲染器，通过“渲染器”可以将解析好的 create sql 转换成需要的结构和数据。
+///
+/// 默认只包含内置模板，可以再加入用户的模板目录或单个模板，同名模板会覆盖内置模板。
 #[derive(Debug)]
 pub struct TypeRender {
     tera: Tera,
@@ -141,34 +279,59 @@ pub struct TypeRender {
 }
 
 impl TypeRender {
+    /// 只包含内置模板的渲染器
     pub fn new() -> Self {
-        let mut tera = match Tera::new("./data/*.tpl") {
-            Ok(t) => t,
-            Err(e) => {
-                println!("Parsing error(s): {}", e);
-                ::std::process::exit(1);
-            }
-        };
+        let mut tera = Tera::default();
+        tera.add_raw_templates(BUILTIN_TPL_ARR.iter().copied())
+            .expect("内置模板应当可以被解析");
         tera.register_function("transfer_type_helper", transfer_type_helper);
         tera.register_function("to_big_case_camel_helper", to_big_case_camel_helper);
         tera.register_function("to_small_case_camel_helper", to_small_case_camel_helper);
 
-        let setting = RenderSetting {
-            field_name_style: FieldNameStyleEnum::SmallCaseCamel,
-            need_json_tag: true,
-            need_form_tag: false,
-            need_gorm_tag: false,
-        };
-
-        let mut ctx = Context::new();
         TypeRender {
             tera,
-            setting,
-            tera_ctx: ctx,
+            setting: RenderSetting::default(),
+            tera_ctx: Context::new(),
             raw_tpl: None,
         }
     }
 
+    /// 包含内置模板以及 `dir` 中模板的渲染器，目录不存在或模板解析失败时返回错误
+    pub fn with_template_dir<P: AsRef<Path>>(dir: P) -> tera::Result<Self> {
+        let mut render = Self::new();
+        render.add_template_dir(dir)?;
+        Ok(render)
+    }
+
+    /// 加入目录（包括子目录）中所有 `.tpl` 文件作为模板，模板名为相对于 `dir` 的路径，如：`go/struct.tpl`
+    /// 任何一个模板解析失败时返回错误，已有的模板保持不变
+    pub fn add_template_dir<P: AsRef<Path>>(&mut self, dir: P) -> tera::Result<&mut Self> {
+        let dir = dir.as_ref();
+        let mut file_arr: Vec<(PathBuf, Option<String>)> = vec![];
+        collect_tpl_file(dir, dir, &mut file_arr).map_err(|err| {
+            tera::Error::chain(format!("读取模板目录 {} 失败", dir.display()), err)
+        })?;
+        let mut tera = self.tera.clone();
+        tera.add_template_files(file_arr)?;
+        self.tera = tera;
+        Ok(self)
+    }
+
+    /// 加入一个命名的模板，解析失败时返回错误，已有的模板保持不变
+    pub fn add_template(&mut self, name: &str, content: &str) -> tera::Result<&mut Self> {
+        let mut tera = self.tera.clone();
+        tera.add_raw_template(name, content)?;
+        self.tera = tera;
+        Ok(self)
+    }
+
+    /// 所有模板的名字，按名字排序
+    pub fn template_names(&self) -> Vec<&str> {
+        let mut name_arr: Vec<&str> = self.tera.get_template_names().collect();
+        name_arr.sort_unstable();
+        name_arr
+    }
+
     /// 设置字符串模板
     pub fn set_raw_tpl(&mut self, raw_tpl: String) -> &mut Self {
         self.raw_tpl = Some(raw_tpl);
@@ -185,11 +348,57 @@ impl TypeRender {
         self
     }
 
+    /// 设置渲染配置
+    pub fn set_setting(&mut self, setting: RenderSetting) -> &mut Self {
+        let rule_arr = setting.type_rule_arr.clone();
+        self.tera.register_function(
+            "transfer_type_helper",
+            move |args: &HashMap<String, Value>| transfer_type_by_rule(args, &rule_arr),
+        );
+        self.setting = setting;
+        self
+    }
+
+    /// 按当前的渲染配置，将表结构渲染为完整的 Go 文件
+    pub fn render_go_struct(&self, table: &TableSchema) -> tera::Result<String> {
+        go::render_go_struct(table, &self.setting)
+    }
+
     /// 渲染
     pub fn render(&mut self) -> tera::Result<String> {
         let tpl = self.raw_tpl.as_deref().unwrap_or("");
         return self.tera.render_str(tpl, &self.tera_ctx);
     }
+
+    /// 用当前设置的变量渲染指定名字的模板
+    pub fn render_template(&self, name: &str) -> tera::Result<String> {
+        self.tera.render(name, &self.tera_ctx)
+    }
+}
+
+/// 递归收集目录中的 `.tpl` 文件，模板名中的路径分隔符统一为 `/`
+fn collect_tpl_file(
+    root: &Path,
+    dir: &Path,
+    file_arr: &mut Vec<(PathBuf, Option<String>)>,
+) -> std::io::Result<()> {
+    let mut entry_arr = fs::read_dir(dir)?.collect::<std::io::Result<Vec<_>>>()?;
+    entry_arr.sort_by_key(|entry| entry.path());
+    for entry in entry_arr {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_tpl_file(root, &path, file_arr)?;
+        } else if path.extension().is_some_and(|ext| ext == "tpl") {
+            let name_arr: Vec<String> = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy().to_string())
+                .collect();
+            file_arr.push((path, Some(name_arr.join("/"))));
+        }
+    }
+    Ok(())
 }
 
 impl Default for TypeRender {
@@ -209,10 +418,111 @@ pub struct RenderSetting {
     pub need_form_tag: bool,
     /// 是否需要 gorm tag
     pub need_gorm_tag: bool,
+    /// 生成的 Go 文件的包名
+    pub package_name: String,
+    /// json、form、gorm 之外的 tag，按顺序输出在这三个 tag 之后
+    pub tag_arr: Vec<TagSetting>,
+    /// 覆盖默认类型映射的规则，模板中的 `transfer_type_helper(column=field)` 同样使用这些规则
+    pub type_rule_arr: Vec<TypeRule>,
+}
+
+impl Default for RenderSetting {
+    fn default() -> Self {
+        RenderSetting {
+            field_name_style: FieldNameStyleEnum::SmallCaseCamel,
+            need_json_tag: true,
+            need_form_tag: false,
+            need_gorm_tag: false,
+            package_name: "model".to_string(),
+            tag_arr: vec![],
+            type_rule_arr: vec![],
+        }
+    }
+}
+
+/// 自定义的 tag，如 sqlx 的 `db:"user_name"`、go-playground 的 `validate:"required,max=50"`
+///
+/// `template` 是 tera 模板，渲染结果即 tag 的值，结果为空时不输出这个 tag。模板中可用的变量：
+/// * `name`：按 `name_style` 转换后的字段名
+/// * `column`：字段的完整信息，即 `OneColumn`
+/// * `sql_type`：字段类型的 DDL 写法，如：`varchar(50)`
+/// * `max_len`：char、varchar 的长度，其他类型没有这个变量
+/// * `enum_arr`：enum、set 的可选值
+/// * `required`：不允许为 NULL、没有默认值也不是自增字段
+/// * `primary_key`：是否为主键字段
+/// * `comment`：字段注释，换行符已替换为空格
+///
+/// 模板中还可以使用过滤器 `sql_escape`，将单引号转义为两个单引号，如：`comment('{{ comment | sql_escape }}')`
+#[derive(Debug, Clone)]
+pub struct TagSetting {
+    /// tag 名，如：db、validate
+    pub name: String,
+    /// tag 值中字段名的风格
+    pub name_style: FieldNameStyleEnum,
+    /// tag 值的模板，为 None 时 tag 的值就是转换后的字段名
+    pub template: Option<String>,
+}
+
+impl TagSetting {
+    /// 值为字段名的 tag
+    pub fn new(name: &str, name_style: FieldNameStyleEnum) -> Self {
+        TagSetting {
+            name: name.to_string(),
+            name_style,
+            template: None,
+        }
+    }
+
+    /// 设置 tag 值的模板
+    pub fn with_template(mut self, template: &str) -> Self {
+        self.template = Some(template.to_string());
+        self
+    }
+
+    /// sqlx 使用的 `db` tag，如：`db:"user_name"`
+    pub fn db() -> Self {
+        Self::new("db", FieldNameStyleEnum::Underline)
+    }
+
+    /// xorm tag，如：`xorm:"'user_name' varchar(50) notnull comment('用户名')"`
+    pub fn xorm() -> Self {
+        Self::new("xorm", FieldNameStyleEnum::Underline).with_template(
+            "'{{ column.name }}' {{ sql_type }}\
+             {% if primary_key %} pk{% endif %}\
+             {% if column.auto_increment %} autoincr{% endif %}\
+             {% if not column.nullable %} notnull{% endif %}\
+             {% if comment %} comment('{{ comment | sql_escape }}'){% endif %}",
+        )
+    }
+
+    /// mongo 使用的 `bson` tag，允许为 NULL 的字段加上 omitempty
+    pub fn bson() -> Self {
+        Self::new("bson", FieldNameStyleEnum::Underline)
+            .with_template("{{ name }}{% if column.nullable %},omitempty{% endif %}")
+    }
+
+    /// `yaml` tag
+    pub fn yaml() -> Self {
+        Self::new("yaml", FieldNameStyleEnum::Underline)
+    }
+
+    /// mapstructure 使用的 tag
+    pub fn mapstructure() -> Self {
+        Self::new("mapstructure", FieldNameStyleEnum::Underline)
+    }
+
+    /// go-playground validator 使用的 `validate` tag，规则由字段的长度、可选值以及是否允许为 NULL 得出
+    pub fn validate() -> Self {
+        Self::new("validate", FieldNameStyleEnum::Underline).with_template(
+            "{% if required %}required{% else %}omitempty{% endif %}\
+             {% if max_len %},max={{ max_len }}{% endif %}\
+             {% if enum_arr %},oneof={{ enum_arr | join(sep=\" \") }}{% endif %}",
+        )
+    }
 }
 
 // field name 风格
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FieldNameStyleEnum {
     // 小驼峰
     SmallCaseCamel,
@@ -238,6 +548,75 @@ mod tests {
         assert_eq!(to_big_case_camel("Hello_world"), "HelloWorld".to_string());
         assert_eq!(to_big_case_camel("Hello__world"), "HelloWorld".to_string());
         assert_eq!(to_big_case_camel("aaaaa"), "Aaaaa".to_string());
+        assert_eq!(to_small_case_camel("user_name"), "userName".to_string());
+        assert_eq!(
+            to_small_case_camel("Hello__world"),
+            "helloWorld".to_string()
+        );
+        assert_eq!(to_underline("UserName"), "user_name".to_string());
+        assert_eq!(to_underline("user_name"), "user_name".to_string());
+    }
+
+    #[test]
+    fn test_transfer_type() {
+        assert_eq!(
+            transfer_type(DataTypeEnum::Int(IntTypeOption {
+                width: Some(11),
+                unsigned: true,
+                zerofill: false,
+            })),
+            "int(11) unsigned".to_string()
+        );
+        assert_eq!(
+            transfer_type(DataTypeEnum::Decimal(DecimalTypeOption {
+                precision: Some(10),
+                scale: Some(2),
+                ..Default::default()
+            })),
+            "decimal(10,2)".to_string()
+        );
+        let mut args = HashMap::new();
+        args.insert(
+            "typ".to_string(),
+            serde_json::to_value(DataTypeEnum::Bigint(IntTypeOption {
+                unsigned: true,
+                ..Default::default()
+            }))
+            .unwrap(),
+        );
+        assert_eq!(
+            transfer_type_helper(&args).unwrap(),
+            serde_json::json!("uint64")
+        );
+        let tags_typ = DataTypeEnum::Array(Box::new(DataTypeEnum::Text));
+        assert_eq!(transfer_type(tags_typ.clone()), "text[]".to_string());
+        args.insert("typ".to_string(), serde_json::to_value(tags_typ).unwrap());
+        assert_eq!(
+            transfer_type_helper(&args).unwrap(),
+            serde_json::json!("[]string")
+        );
+
+        let (_, table) = crate::sql1::parse_create_sql(
+            "CREATE TABLE `t` (`created_at` bigint NOT NULL, `deleted_at` bigint NOT NULL)",
+        )
+        .unwrap();
+        let mut tr = TypeRender::new();
+        tr.set_setting(RenderSetting {
+            type_rule_arr: vec![TypeRule {
+                column: Some("created_*".to_string()),
+                ..TypeRule::new("Timestamp")
+            }],
+            ..Default::default()
+        });
+        let rendered = tr
+            .set_raw_tpl(
+                "{% for field in field_arr %}{{transfer_type_helper(column=field)}} {% endfor %}"
+                    .to_string(),
+            )
+            .set_var("field_arr", &table.column_arr)
+            .render()
+            .unwrap();
+        assert_eq!(rendered, "Timestamp int64 ");
     }
 
     #[test]
@@ -256,11 +635,55 @@ mod tests {
         assert!(&rendered_res.is_ok());
     }
 
+    #[test]
+    fn test_template_dir() {
+        let mut tr = TypeRender::new();
+        assert_eq!(tr.template_names(), vec!["struct_tpl.tpl"]);
+        let (_, table) = crate::sql1::parse_create_sql(
+            "CREATE TABLE `user` (`user_id` bigint NOT NULL, `name` varchar(20))",
+        )
+        .unwrap();
+        tr.set_var("struct_name", "User")
+            .set_var("field_arr", &table.column_arr);
+        assert_eq!(
+            tr.render_template("struct_tpl.tpl").unwrap(),
+            "type User struct {
+\tUserId int64 `gorm:\"column:user_id\" json:\"userId\" form:\"userId\"`
+\tName string `gorm:\"column:name\" json:\"name\" form:\"name\"`
+}
+"
+        );
+
+        let dir = std::env::temp_dir().join(format!("type_render_tpl_{}", std::process::id()));
+        fs::create_dir_all(dir.join("go")).unwrap();
+        fs::write(dir.join("struct_tpl.tpl"), "struct {{ struct_name }}").unwrap();
+        fs::write(dir.join("go").join("model.tpl"), "package {{ package }}").unwrap();
+        fs::write(dir.join("readme.md"), "{{ not a template").unwrap();
+        let mut tr = TypeRender::with_template_dir(&dir).unwrap();
+        assert_eq!(tr.template_names(), vec!["go/model.tpl", "struct_tpl.tpl"]);
+        tr.set_var("struct_name", "User")
+            .set_var("package", "model");
+        assert_eq!(tr.render_template("struct_tpl.tpl").unwrap(), "struct User");
+        assert_eq!(tr.render_template("go/model.tpl").unwrap(), "package model");
+
+        // 解析失败时不影响已有的模板
+        fs::write(dir.join("bad.tpl"), "{% if %}").unwrap();
+        assert!(tr.add_template_dir(&dir).is_err());
+        assert!(tr.add_template("bad", "{{ a").is_err());
+        assert_eq!(tr.template_names(), vec!["go/model.tpl", "struct_tpl.tpl"]);
+        tr.add_template("hello", "hello {{ struct_name }}").unwrap();
+        assert_eq!(tr.render_template("hello").unwrap(), "hello User");
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(TypeRender::with_template_dir(dir.join("not_exist")).is_err());
+    }
+
     fn get_test_field_arr() -> Vec<OneColumn> {
         let f1 = OneColumn {
             name: "id".to_string(),
-            typ: DataTypeEnum::Bigint,
+            typ: DataTypeEnum::Bigint(IntTypeOption::default()),
             comment: "主键".to_string(),
+            ..Default::default()
         };
         let field_arr = vec![f1];
         return field_arr;
@@ -0,0 +1,534 @@
+//! 由解析出的表结构生成完整的 Go 文件，包括 package、import、结构体以及 `TableName()` 方法
+//!
+//! 输出的代码与 gofmt 格式化后的结果一致：缩进使用 tab，字段名、类型、tag 以及注释按列对齐
+use super::type_map::{match_type_rule, TypeRule};
+use super::{go_type_str, to_big_case_camel, to_name_style, RenderSetting, TagSetting};
+use crate::sql1::ddl::{data_type_sql, default_sql};
+use crate::sql1::{DataTypeEnum, DefaultEnum, IndexIdxTyeEnum, OneColumn, TableSchema};
+use serde_json::Value;
+use std::collections::HashMap;
+use tera::{Context, Tera};
+
+/// 允许为 NULL 的字段在 Go 中的类型，`database/sql` 中有对应的 Null 类型时优先使用，否则使用指针
+fn go_nullable_type_str(typ: &str) -> String {
+    match typ {
+        "string" => "sql.NullString".to_string(),
+        "int" => "sql.NullInt32".to_string(),
+        "int64" => "sql.NullInt64".to_string(),
+        "float64" => "sql.NullFloat64".to_string(),
+        "bool" => "sql.NullBool".to_string(),
+        "time.Time" => "sql.NullTime".to_string(),
+        // 切片本身可以为 nil
+        typ if typ.starts_with("[]") => typ.to_string(),
+        typ => format!("*{}", typ),
+    }
+}
+
+/// 字段在 Go 结构体中的类型，允许为 NULL 的字段使用可以表示 NULL 的类型
+/// 匹配 `rule_arr` 中的规则时使用规则中的类型代替默认的映射
+pub fn go_field_type_str(column: &OneColumn, rule_arr: &[TypeRule]) -> String {
+    let typ = match match_type_rule(rule_arr, column) {
+        Some(rule) => rule.target.clone(),
+        None => go_type_str(column.typ.clone()),
+    };
+    if column.nullable {
+        go_nullable_type_str(&typ)
+    } else {
+        typ
+    }
+}
+
+/// gorm tag 中的值，`;` 是各项之间的分隔符，需要转义
+fn gorm_tag_value(value: &str) -> String {
+    value.replace(';', "\\;")
+}
+
+/// 字段所在的索引在 gorm tag 中的写法，如：`uniqueIndex:uk_name,priority:2`
+fn gorm_index_arr(table: &TableSchema, column: &OneColumn) -> Vec<String> {
+    let mut index_arr: Vec<String> = vec![];
+    for index in table.index_arr.iter() {
+        let column_name_arr = index.column_names();
+        let position = match column_name_arr.iter().position(|name| name == &column.name) {
+            Some(position) => position,
+            None => continue,
+        };
+        let (key, class) = match index.typ {
+            IndexIdxTyeEnum::Primary => {
+                index_arr.push("primaryKey".to_string());
+                continue;
+            }
+            IndexIdxTyeEnum::Unique => ("uniqueIndex", None),
+            IndexIdxTyeEnum::FullText => ("index", Some("FULLTEXT")),
+            IndexIdxTyeEnum::Spatial => ("index", Some("SPATIAL")),
+            IndexIdxTyeEnum::Normal | IndexIdxTyeEnum::None => ("index", None),
+        };
+        let mut option_arr: Vec<String> = vec![];
+        if let Some(class) = class {
+            option_arr.push(format!("class:{}", class));
+        }
+        // 联合索引中字段的顺序
+        if index.key_parts.len() > 1 {
+            option_arr.push(format!("priority:{}", position + 1));
+        }
+        let mut item = key.to_string();
+        if !index.name.is_empty() {
+            item += &format!(":{}", index.name);
+            if !option_arr.is_empty() {
+                item += &format!(",{}", option_arr.join(","));
+            }
+        } else if !option_arr.is_empty() {
+            item += &format!(":,{}", option_arr.join(","));
+        }
+        index_arr.push(item);
+    }
+    if column.primary_key && !index_arr.iter().any(|item| item == "primaryKey") {
+        index_arr.insert(0, "primaryKey".to_string());
+    }
+    if column.unique_key {
+        index_arr.push("unique".to_string());
+    }
+    index_arr
+}
+
+/// gorm tag 的内容，如：`column:id;type:bigint unsigned;primaryKey;autoIncrement;not null;comment:主键`
+pub fn gorm_tag_str(table: &TableSchema, column: &OneColumn) -> String {
+    let mut item_arr = vec![
+        format!("column:{}", gorm_tag_value(&column.name)),
+        format!("type:{}", gorm_tag_value(&data_type_sql(&column.typ))),
+    ];
+    item_arr.extend(gorm_index_arr(table, column));
+    if column.auto_increment {
+        item_arr.push("autoIncrement".to_string());
+    }
+    if !column.nullable {
+        item_arr.push("not null".to_string());
+    }
+    // 允许为 NULL 的字段默认就是 NULL，不需要写出来
+    if column.default != DefaultEnum::DefaultNull {
+        if let Some(default) = default_sql(&column.default) {
+            item_arr.push(format!("default:{}", gorm_tag_value(&default)));
+        }
+    }
+    if !column.comment.is_empty() {
+        item_arr.push(format!(
+            "comment:{}",
+            gorm_tag_value(&go_comment(&column.comment))
+        ));
+    }
+    item_arr.join(";")
+}
+
+/// struct tag 中的一项，值中的 `\`、`"` 需要转义，反引号不能出现在 tag 中，替换为单引号
+fn go_tag_item(key: &str, value: &str) -> String {
+    let value = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('`', "'");
+    format!("{}:\"{}\"", key, value)
+}
+
+/// tag 模板中的 `sql_escape` 过滤器，单引号包裹的值中的单引号转义为两个单引号
+fn sql_escape_filter(value: &Value, _: &HashMap<String, Value>) -> tera::Result<Value> {
+    match value.as_str() {
+        Some(value) => Ok(Value::String(value.replace('\'', "''"))),
+        None => Err(tera::Error::msg("sql_escape 只能用于字符串")),
+    }
+}
+
+/// 自定义 tag 的值，没有模板时就是转换后的字段名
+fn custom_tag_value(
+    tag: &TagSetting,
+    table: &TableSchema,
+    column: &OneColumn,
+) -> tera::Result<String> {
+    let name = to_name_style(&column.name, tag.name_style);
+    let template = match &tag.template {
+        Some(template) => template,
+        None => return Ok(name),
+    };
+    let mut ctx = Context::new();
+    ctx.insert("name", &name);
+    ctx.insert("column", column);
+    ctx.insert("sql_type", &data_type_sql(&column.typ));
+    match &column.typ {
+        DataTypeEnum::Char(len) | DataTypeEnum::VarChar(len) => ctx.insert("max_len", len),
+        DataTypeEnum::Enum(value_arr) | DataTypeEnum::Set(value_arr) => {
+            ctx.insert("enum_arr", value_arr)
+        }
+        _ => {}
+    }
+    let required = !column.nullable
+        && column.default == DefaultEnum::DefaultNone
+        && !column.auto_increment
+        && column.generated.is_none();
+    ctx.insert("required", &required);
+    let primary_key = column.primary_key
+        || table.index_arr.iter().any(|index| {
+            index.typ == IndexIdxTyeEnum::Primary && index.column_names().contains(&column.name)
+        });
+    ctx.insert("primary_key", &primary_key);
+    ctx.insert("comment", &go_comment(&column.comment));
+    let mut tera = Tera::default();
+    tera.register_filter("sql_escape", sql_escape_filter);
+    tera.add_raw_template("tag", template)?;
+    Ok(tera.render("tag", &ctx)?.trim().to_string())
+}
+
+/// 字段的 tag，不需要任何 tag 时返回 None，如：`gorm:"column:user_name" json:"userName" form:"userName"`
+/// json、form tag 中的名字按 `field_name_style` 转换，自定义的 tag 按各自的配置生成
+pub fn go_tag_str(
+    table: &TableSchema,
+    column: &OneColumn,
+    setting: &RenderSetting,
+) -> tera::Result<Option<String>> {
+    let name = to_name_style(&column.name, setting.field_name_style);
+    let mut tag_arr: Vec<String> = vec![];
+    if setting.need_gorm_tag {
+        tag_arr.push(go_tag_item("gorm", &gorm_tag_str(table, column)));
+    }
+    if setting.need_json_tag {
+        tag_arr.push(go_tag_item("json", &name));
+    }
+    if setting.need_form_tag {
+        tag_arr.push(go_tag_item("form", &name));
+    }
+    for tag in setting.tag_arr.iter() {
+        let value = custom_tag_value(tag, table, column)?;
+        if !value.is_empty() {
+            tag_arr.push(go_tag_item(&tag.name, &value));
+        }
+    }
+    if tag_arr.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(tag_arr.join(" ")))
+    }
+}
+
+/// 注释只能占一行，换行符替换为空格
+fn go_comment(comment: &str) -> String {
+    comment.replace("\r\n", " ").replace(['\r', '\n'], " ")
+}
+
+/// Go 中双引号包裹的字符串字面量
+fn go_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// 按 gofmt 的规则对齐各行的单元格：每行的最后一个单元格不参与对齐，
+/// 连续若干行在同一列都有单元格时，这些单元格以最宽的一个加一个空格的宽度对齐
+pub fn align_cell_arr(line_arr: &[Vec<String>]) -> Vec<String> {
+    let mut out_arr: Vec<String> = vec![String::new(); line_arr.len()];
+    let mut width_arr: Vec<usize> = vec![];
+    align_block(line_arr, &mut width_arr, 0, line_arr.len(), &mut out_arr);
+    out_arr
+}
+
+/// 对齐 [line0, line1) 中的行，`width_arr` 为外层已经确定的各列宽度
+fn align_block(
+    line_arr: &[Vec<String>],
+    width_arr: &mut Vec<usize>,
+    mut line0: usize,
+    line1: usize,
+    out_arr: &mut [String],
+) {
+    let column = width_arr.len();
+    let mut this = line0;
+    while this < line1 {
+        if column + 1 >= line_arr[this].len() {
+            this += 1;
+            continue;
+        }
+        write_line_arr(line_arr, width_arr, line0, this, out_arr);
+        line0 = this;
+        let mut width = 0;
+        while this < line1 && column + 1 < line_arr[this].len() {
+            width = width.max(line_arr[this][column].chars().count() + 1);
+            this += 1;
+        }
+        width_arr.push(width);
+        align_block(line_arr, width_arr, line0, this, out_arr);
+        width_arr.pop();
+        line0 = this;
+    }
+    write_line_arr(line_arr, width_arr, line0, line1, out_arr);
+}
+
+fn write_line_arr(
+    line_arr: &[Vec<String>],
+    width_arr: &[usize],
+    line0: usize,
+    line1: usize,
+    out_arr: &mut [String],
+) {
+    for i in line0..line1 {
+        let mut line = String::new();
+        for (j, cell) in line_arr[i].iter().enumerate() {
+            line += cell;
+            if let Some(width) = width_arr.get(j) {
+                line += &" ".repeat(width - cell.chars().count());
+            }
+        }
+        out_arr[i] = line;
+    }
+}
+
+/// 根据表结构生成完整的 Go 文件，结构体名为表名的大驼峰形式，
+/// 允许为 NULL 的字段使用 `database/sql` 中的 Null 类型，用到的包会自动 import
+/// 自定义 tag 的模板渲染失败时返回错误
+pub fn render_go_struct(table: &TableSchema, setting: &RenderSetting) -> tera::Result<String> {
+    let struct_name = to_big_case_camel(&table.table_name);
+    let mut import_arr: Vec<&str> = vec![];
+    let mut cell_arr_arr: Vec<Vec<String>> = vec![];
+    for column in table.column_arr.iter() {
+        let typ = go_field_type_str(column, &setting.type_rule_arr);
+        if let Some(import) =
+            match_type_rule(&setting.type_rule_arr, column).and_then(|rule| rule.import.as_deref())
+        {
+            import_arr.push(import);
+        }
+        if typ.contains("time.") {
+            import_arr.push("time");
+        }
+        if typ.contains("sql.") {
+            import_arr.push("database/sql");
+        }
+        let mut cell_arr = vec![to_big_case_camel(&column.name), typ];
+        if let Some(tag) = go_tag_str(table, column, setting)? {
+            cell_arr.push(format!("`{}`", tag));
+        }
+        if !column.comment.is_empty() {
+            cell_arr.push(format!("// {}", go_comment(&column.comment)));
+        }
+        cell_arr_arr.push(cell_arr);
+    }
+    import_arr.sort_unstable();
+    import_arr.dedup();
+
+    let mut go = format!("package {}\n\n", setting.package_name);
+    match import_arr.len() {
+        0 => {}
+        1 => go += &format!("import {}\n\n", go_quote(import_arr[0])),
+        _ => {
+            go += "import (\n";
+            for import in import_arr.iter() {
+                go += &format!("\t{}\n", go_quote(import));
+            }
+            go += ")\n\n";
+        }
+    }
+    if let Some(comment) = &table.option.comment {
+        if !comment.is_empty() {
+            go += &format!("// {} {}\n", struct_name, go_comment(comment));
+        }
+    }
+    go += &format!("type {} struct {{\n", struct_name);
+    for line in align_cell_arr(&cell_arr_arr) {
+        go += &format!("\t{}\n", line);
+    }
+    go += "}\n\n";
+    go += &format!(
+        "// TableName 返回表名\nfunc ({}) TableName() string {{\n\treturn {}\n}}\n",
+        struct_name,
+        go_quote(&table.table_name)
+    );
+    Ok(go)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql1::parse_create_sql;
+    use crate::sql2_render::{FieldNameStyleEnum, TagSetting};
+
+    #[test]
+    fn test_align_cell_arr() {
+        let line_arr: Vec<Vec<String>> = vec![
+            vec!["A", "int", "`json:\"a\"`", "// a"],
+            vec!["Bbb", "string", "// b"],
+            vec!["C", "bool"],
+            vec!["Dd", "int", "`json:\"dd\"`"],
+        ]
+        .into_iter()
+        .map(|cell_arr| cell_arr.into_iter().map(String::from).collect())
+        .collect();
+        assert_eq!(
+            align_cell_arr(&line_arr),
+            vec![
+                "A   int    `json:\"a\"` // a",
+                "Bbb string // b",
+                "C   bool",
+                "Dd  int `json:\"dd\"`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_go_struct() {
+        let sql = r#"CREATE TABLE `user_info` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '主键',
+  `user_name` varchar(64) NOT NULL DEFAULT '' COMMENT '用户名',
+  `avatar` varchar(255) DEFAULT NULL,
+  `created_at` datetime NOT NULL DEFAULT CURRENT_TIMESTAMP COMMENT '创建时间',
+  `deleted_at` datetime DEFAULT NULL COMMENT '删除时间',
+  PRIMARY KEY (`id`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT='用户信息'"#;
+        let (_, table) = parse_create_sql(sql).unwrap();
+        assert_eq!(
+            render_go_struct(&table, &RenderSetting::default()).unwrap(),
+            r#"package model
+
+import (
+	"database/sql"
+	"time"
+)
+
+// UserInfo 用户信息
+type UserInfo struct {
+	Id        uint64         `json:"id"`       // 主键
+	UserName  string         `json:"userName"` // 用户名
+	Avatar    sql.NullString `json:"avatar"`
+	CreatedAt time.Time      `json:"createdAt"` // 创建时间
+	DeletedAt sql.NullTime   `json:"deletedAt"` // 删除时间
+}
+
+// TableName 返回表名
+func (UserInfo) TableName() string {
+	return "user_info"
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_go_tag_str() {
+        let sql = r#"CREATE TABLE `order` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '主键',
+  `userId` bigint NOT NULL COMMENT 'say "hi"; ok',
+  `sn` varchar(32) NOT NULL DEFAULT '' COMMENT '单号',
+  `status` tinyint NOT NULL DEFAULT 1,
+  `remark` text,
+  PRIMARY KEY (`id`),
+  UNIQUE KEY `uk_sn` (`sn`),
+  KEY `idx_user_status` (`userId`, `status`),
+  FULLTEXT KEY `ft_remark` (`remark`)
+)"#;
+        let (_, table) = parse_create_sql(sql).unwrap();
+        let setting = RenderSetting {
+            field_name_style: FieldNameStyleEnum::Underline,
+            need_json_tag: true,
+            need_form_tag: true,
+            need_gorm_tag: true,
+            ..Default::default()
+        };
+        let tag_arr: Vec<Option<String>> = table
+            .column_arr
+            .iter()
+            .map(|column| go_tag_str(&table, column, &setting).unwrap())
+            .collect();
+        assert_eq!(
+            tag_arr,
+            vec![
+                Some(r#"gorm:"column:id;type:bigint unsigned;primaryKey;autoIncrement;not null;comment:主键" json:"id" form:"id""#.to_string()),
+                Some(r#"gorm:"column:userId;type:bigint;index:idx_user_status,priority:1;not null;comment:say \"hi\"\\; ok" json:"user_id" form:"user_id""#.to_string()),
+                Some(r#"gorm:"column:sn;type:varchar(32);uniqueIndex:uk_sn;not null;default:'';comment:单号" json:"sn" form:"sn""#.to_string()),
+                Some(r#"gorm:"column:status;type:tinyint;index:idx_user_status,priority:2;not null;default:1" json:"status" form:"status""#.to_string()),
+                Some(r#"gorm:"column:remark;type:text;index:ft_remark,class:FULLTEXT" json:"remark" form:"remark""#.to_string()),
+            ]
+        );
+        let setting = RenderSetting {
+            need_json_tag: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            go_tag_str(&table, &table.column_arr[0], &setting).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_custom_tag() {
+        let sql = r#"CREATE TABLE `user` (
+  `id` bigint NOT NULL AUTO_INCREMENT,
+  `userName` varchar(50) NOT NULL COMMENT '用户名',
+  `status` enum('on','off') NOT NULL DEFAULT 'on' COMMENT 'user''s status',
+  `email` varchar(128) DEFAULT NULL,
+  PRIMARY KEY (`id`)
+)"#;
+        let (_, table) = parse_create_sql(sql).unwrap();
+        let setting = RenderSetting {
+            need_json_tag: false,
+            tag_arr: vec![
+                TagSetting::db(),
+                TagSetting::xorm(),
+                TagSetting::bson(),
+                TagSetting::new("yaml", FieldNameStyleEnum::SmallCaseCamel),
+                TagSetting::validate(),
+                TagSetting::new("label", FieldNameStyleEnum::Underline)
+                    .with_template("{{ column.comment }}"),
+            ],
+            ..Default::default()
+        };
+        let tag_arr: Vec<String> = table
+            .column_arr
+            .iter()
+            .map(|column| go_tag_str(&table, column, &setting).unwrap().unwrap())
+            .collect();
+        assert_eq!(
+            tag_arr,
+            vec![
+                r#"db:"id" xorm:"'id' bigint pk autoincr notnull" bson:"id" yaml:"id" validate:"omitempty""#,
+                r#"db:"user_name" xorm:"'userName' varchar(50) notnull comment('用户名')" bson:"user_name" yaml:"userName" validate:"required,max=50" label:"用户名""#,
+                r#"db:"status" xorm:"'status' enum('on','off') notnull comment('user''s status')" bson:"status" yaml:"status" validate:"omitempty,oneof=on off" label:"user's status""#,
+                r#"db:"email" xorm:"'email' varchar(128)" bson:"email,omitempty" yaml:"email" validate:"omitempty,max=128""#,
+            ]
+        );
+        let setting = RenderSetting {
+            tag_arr: vec![TagSetting::new("bad", FieldNameStyleEnum::Underline)
+                .with_template("{{ unknown_var }}")],
+            ..Default::default()
+        };
+        assert!(render_go_struct(&table, &setting).is_err());
+    }
+
+    #[test]
+    fn test_type_rule() {
+        let sql = r#"CREATE TABLE `user` (
+  `id` bigint NOT NULL AUTO_INCREMENT,
+  `balance` decimal(10,2) NOT NULL,
+  `created_at` datetime NOT NULL,
+  `deleted_at` datetime DEFAULT NULL,
+  PRIMARY KEY (`id`)
+)"#;
+        let (_, table) = parse_create_sql(sql).unwrap();
+        let setting = RenderSetting {
+            need_json_tag: false,
+            type_rule_arr: vec![
+                TypeRule {
+                    db_type: Some("decimal".to_string()),
+                    import: Some("github.com/shopspring/decimal".to_string()),
+                    ..TypeRule::new("decimal.Decimal")
+                },
+                TypeRule {
+                    column: Some("*_at".to_string()),
+                    ..TypeRule::new("int64")
+                },
+            ],
+            ..Default::default()
+        };
+        let go = render_go_struct(&table, &setting).unwrap();
+        assert!(go.contains(
+            r#"import (
+	"database/sql"
+	"github.com/shopspring/decimal"
+)
+
+type User struct {
+	Id        int64
+	Balance   decimal.Decimal
+	CreatedAt int64
+	DeletedAt sql.NullInt64
+}"#
+        ));
+    }
+}
@@ -0,0 +1,645 @@
+//! 由解析出的表结构生成 JPA 实体，可以是 Java 类，也可以是 Kotlin 的 data class
+//!
+//! 字段名为小驼峰，列名、长度、精度等记录在 `@Column` 上；时间使用 `java.time` 中的类型，
+//! 定点数使用 `BigDecimal`，无符号的 bigint 使用 `BigInteger`。联合主键生成嵌套的主键类，
+//! 并在实体上用 `@IdClass` 声明
+use super::type_map::{match_builtin_rule, match_type_rule, TypeLangEnum, TypeRule};
+use super::{to_big_case_camel, to_small_case_camel, uppercase_first};
+use crate::sql1::{DataTypeEnum, IndexIdxTyeEnum, OneColumn, TableSchema};
+
+/// Java 中的关键字，作为字段名时加上 `_` 后缀
+const JAVA_KEYWORD_ARR: [&str; 53] = [
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "try",
+    "void",
+    "volatile",
+    "while",
+    "true",
+    "false",
+    "null",
+];
+
+/// Kotlin 中的硬关键字，作为属性名时需要用反引号包裹
+const KOTLIN_KEYWORD_ARR: [&str; 21] = [
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+];
+
+/// 生成的 JVM 语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JvmLanguageEnum {
+    /// Java 类，字段为 private，带 getter、setter
+    Java,
+    /// Kotlin 的 data class
+    Kotlin,
+}
+
+/// JPA 实体的渲染设置
+#[derive(Debug, Clone)]
+pub struct JvmRenderSetting {
+    /// 生成的语言
+    pub language: JvmLanguageEnum,
+    /// 包名
+    pub package_name: String,
+    /// JPA 注解所在的包，JPA 3 之前为 `javax.persistence`
+    pub persistence_package: String,
+    /// 是否生成 JPA 注解，不生成时为普通的类
+    pub need_jpa: bool,
+    /// Java 类是否使用 lombok 的 `@Data` 代替 getter、setter，对 Kotlin 无效
+    pub need_lombok: bool,
+    /// 覆盖默认类型映射的规则，只对 `language` 对应的语言生效
+    pub type_rule_arr: Vec<TypeRule>,
+}
+
+impl Default for JvmRenderSetting {
+    fn default() -> Self {
+        JvmRenderSetting {
+            language: JvmLanguageEnum::Java,
+            package_name: "model".to_string(),
+            persistence_package: "jakarta.persistence".to_string(),
+            need_jpa: true,
+            need_lombok: false,
+            type_rule_arr: vec![],
+        }
+    }
+}
+
+/// 数据库类型在 Java、Kotlin 中对应的类型，以及需要 import 的完整类名
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JvmType {
+    /// Java 中的类型，都是包装类型，如：`Long`
+    pub java: String,
+    /// Kotlin 中的类型，不包含表示可空的 `?`
+    pub kotlin: String,
+    /// 需要 import 的类，如：`java.math.BigDecimal`
+    pub import_arr: Vec<&'static str>,
+}
+
+impl JvmType {
+    fn new(java: &str, kotlin: &str, import: Option<&'static str>) -> Self {
+        JvmType {
+            java: java.to_string(),
+            kotlin: kotlin.to_string(),
+            import_arr: import.into_iter().collect(),
+        }
+    }
+}
+
+/// 数据库类型对应到 Java、Kotlin 中的类型，先按两种语言各自的内置规则映射
+pub fn jvm_type(typ: &DataTypeEnum) -> JvmType {
+    let java_rule = match_builtin_rule(TypeLangEnum::Java, typ);
+    let kotlin_rule = match_builtin_rule(TypeLangEnum::Kotlin, typ);
+    if let (Some(java_rule), Some(kotlin_rule)) = (java_rule, kotlin_rule) {
+        let mut import_arr: Vec<&'static str> = java_rule
+            .import
+            .iter()
+            .chain(kotlin_rule.import.iter())
+            .map(|import| import.as_str())
+            .collect();
+        import_arr.dedup();
+        return JvmType {
+            java: java_rule.target.clone(),
+            kotlin: kotlin_rule.target.clone(),
+            import_arr,
+        };
+    }
+    match typ {
+        DataTypeEnum::Array(item_typ) => {
+            let item = jvm_type(item_typ);
+            let mut import_arr = vec!["java.util.List"];
+            import_arr.extend(item.import_arr);
+            JvmType {
+                java: format!("List<{}>", item.java),
+                kotlin: format!("List<{}>", item.kotlin),
+                import_arr,
+            }
+        }
+        // 自定义类型以字符串表示
+        _ => JvmType::new("String", "String", None),
+    }
+}
+
+/// 列名对应的字段名，Java 关键字加上 `_` 后缀，Kotlin 关键字用反引号包裹
+pub fn jvm_field_name(column_name: &str, language: JvmLanguageEnum) -> String {
+    let name = to_small_case_camel(column_name);
+    match language {
+        JvmLanguageEnum::Java if JAVA_KEYWORD_ARR.contains(&name.as_str()) => {
+            format!("{}_", name)
+        }
+        JvmLanguageEnum::Kotlin if KOTLIN_KEYWORD_ARR.contains(&name.as_str()) => {
+            format!("`{}`", name)
+        }
+        _ => name,
+    }
+}
+
+/// 双引号包裹的字符串字面量，Kotlin 中还需要转义字符串模板的 `$`
+fn jvm_quote(value: &str, language: JvmLanguageEnum) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => quoted += "\\\"",
+            '\\' => quoted += "\\\\",
+            '\n' => quoted += "\\n",
+            '\r' => quoted += "\\r",
+            '\t' => quoted += "\\t",
+            '$' if language == JvmLanguageEnum::Kotlin => quoted += "\\$",
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Javadoc 注释，单行注释输出为 `/** 注释 */`
+fn java_doc_arr(comment: &str, indent: &str) -> Vec<String> {
+    let comment = comment.replace("*/", "*&#47;");
+    let line_arr: Vec<&str> = comment.lines().map(|line| line.trim_end()).collect();
+    match line_arr.len() {
+        0 => vec![],
+        1 => vec![format!("{}/** {} */", indent, line_arr[0])],
+        _ => {
+            let mut doc_arr = vec![format!("{}/**", indent)];
+            for line in line_arr {
+                doc_arr.push(format!("{} * {}", indent, line).trim_end().to_string());
+            }
+            doc_arr.push(format!("{} */", indent));
+            doc_arr
+        }
+    }
+}
+
+/// 字段是否属于主键
+fn is_primary_key(table: &TableSchema, column: &OneColumn) -> bool {
+    column.primary_key
+        || table.index_arr.iter().any(|index| {
+            index.typ == IndexIdxTyeEnum::Primary && index.column_names().contains(&column.name)
+        })
+}
+
+/// 字段上的 JPA 注解，如：`@Column(name = "nick", nullable = false, length = 64)`
+/// 联合主键的每个字段都标记为 `@Id`，主键类由 `id_class_code` 生成
+fn jpa_annotation_arr(
+    table: &TableSchema,
+    column: &OneColumn,
+    language: JvmLanguageEnum,
+) -> Vec<String> {
+    let mut annotation_arr = vec![];
+    if is_primary_key(table, column) {
+        annotation_arr.push("@Id".to_string());
+    }
+    if column.auto_increment {
+        annotation_arr.push("@GeneratedValue(strategy = GenerationType.IDENTITY)".to_string());
+    }
+    let mut attr_arr = vec![format!("name = {}", jvm_quote(&column.name, language))];
+    if !column.nullable {
+        attr_arr.push("nullable = false".to_string());
+    }
+    if column.generated.is_some() {
+        attr_arr.push("insertable = false".to_string());
+        attr_arr.push("updatable = false".to_string());
+    }
+    match &column.typ {
+        DataTypeEnum::Char(len) | DataTypeEnum::VarChar(len) => {
+            attr_arr.push(format!("length = {}", len))
+        }
+        DataTypeEnum::Decimal(option) => {
+            if let Some(precision) = option.precision {
+                attr_arr.push(format!("precision = {}", precision));
+            }
+            if let Some(scale) = option.scale {
+                attr_arr.push(format!("scale = {}", scale));
+            }
+        }
+        _ => {}
+    }
+    annotation_arr.push(format!("@Column({})", attr_arr.join(", ")));
+    annotation_arr
+}
+
+/// 联合主键的主键类，作为实体的嵌套类，字段与实体中的主键字段同名、同类型
+/// `key_arr` 中为字段名以及 Java、Kotlin 中的类型
+fn id_class_code(
+    id_class_name: &str,
+    key_arr: &[(String, JvmType)],
+    setting: &JvmRenderSetting,
+) -> String {
+    if setting.language == JvmLanguageEnum::Kotlin {
+        // JPA 要求主键类有无参构造函数，所以字段都有默认值
+        let field_arr: Vec<String> = key_arr
+            .iter()
+            .map(|(name, typ)| format!("        var {}: {}? = null,", name, typ.kotlin))
+            .collect();
+        return format!(
+            "    data class {}(\n{}\n    ) : Serializable\n",
+            id_class_name,
+            field_arr.join("\n")
+        );
+    }
+    let mut code = String::new();
+    if setting.need_lombok {
+        code += "    @Data\n";
+    }
+    code += &format!(
+        "    public static class {} implements Serializable {{\n",
+        id_class_name
+    );
+    for (name, typ) in key_arr.iter() {
+        code += &format!("        private {} {};\n", typ.java, name);
+    }
+    if !setting.need_lombok {
+        // 主键类必须实现 equals、hashCode
+        let equal_arr: Vec<String> = key_arr
+            .iter()
+            .map(|(name, _)| format!("Objects.equals({}, that.{})", name, name))
+            .collect();
+        let name_arr: Vec<&str> = key_arr.iter().map(|(name, _)| name.as_str()).collect();
+        code += &format!(
+            r#"
+        @Override
+        public boolean equals(Object o) {{
+            if (this == o) {{
+                return true;
+            }}
+            if (!(o instanceof {class})) {{
+                return false;
+            }}
+            {class} that = ({class}) o;
+            return {equal};
+        }}
+
+        @Override
+        public int hashCode() {{
+            return Objects.hash({name});
+        }}
+"#,
+            class = id_class_name,
+            equal = equal_arr.join(" && "),
+            name = name_arr.join(", ")
+        );
+    }
+    code += "    }\n";
+    code
+}
+
+/// 根据表结构生成 JPA 实体，按设置输出为 Java 类或 Kotlin 的 data class
+pub fn render_jpa_entity(table: &TableSchema, setting: &JvmRenderSetting) -> String {
+    let class_name = to_big_case_camel(&table.table_name);
+    let is_java = setting.language == JvmLanguageEnum::Java;
+    let mut import_arr: Vec<String> = vec![];
+    let mut field_arr: Vec<String> = vec![];
+    let mut accessor_arr: Vec<String> = vec![];
+    let mut key_arr: Vec<(String, JvmType)> = vec![];
+    for column in table.column_arr.iter() {
+        let typ = match match_type_rule(&setting.type_rule_arr, column) {
+            Some(rule) => JvmType {
+                java: rule.target.clone(),
+                kotlin: rule.target.clone(),
+                import_arr: vec![],
+            },
+            None => jvm_type(&column.typ),
+        };
+        import_arr.extend(typ.import_arr.iter().map(|import| import.to_string()));
+        if let Some(rule) = match_type_rule(&setting.type_rule_arr, column) {
+            import_arr.extend(rule.import.clone());
+        }
+        let field_name = jvm_field_name(&column.name, setting.language);
+        if is_primary_key(table, column) {
+            key_arr.push((field_name.clone(), typ.clone()));
+        }
+        field_arr.extend(java_doc_arr(&column.comment, "    "));
+        if setting.need_jpa {
+            for annotation in jpa_annotation_arr(table, column, setting.language) {
+                field_arr.push(format!("    {}", annotation));
+            }
+        }
+        if is_java {
+            field_arr.push(format!("    private {} {};", typ.java, field_name));
+            if !setting.need_lombok {
+                // 按字段名生成，避免 `class` 列生成与 Object 冲突的 getClass
+                let accessor_name = uppercase_first(&field_name);
+                accessor_arr.push(format!(
+                    "    public {} get{}() {{\n        return {};\n    }}",
+                    typ.java, accessor_name, field_name
+                ));
+                accessor_arr.push(format!(
+                    "    public void set{}({} {}) {{\n        this.{} = {};\n    }}",
+                    accessor_name, typ.java, field_name, field_name, field_name
+                ));
+            }
+        } else if column.nullable || column.auto_increment {
+            // 自增的主键在保存之前没有值
+            field_arr.push(format!("    var {}: {}? = null,", field_name, typ.kotlin));
+        } else {
+            field_arr.push(format!("    var {}: {},", field_name, typ.kotlin));
+        }
+    }
+    // 联合主键需要单独的主键类
+    let id_class_name = if setting.need_jpa && key_arr.len() > 1 {
+        import_arr.push("java.io.Serializable".to_string());
+        if is_java && !setting.need_lombok {
+            import_arr.push("java.util.Objects".to_string());
+        }
+        Some(format!("{}Id", class_name))
+    } else {
+        None
+    };
+    if setting.need_jpa {
+        let mut jpa_arr = vec!["Column", "Entity", "Table"];
+        if table.column_arr.iter().any(|column| column.auto_increment) {
+            jpa_arr.extend(["GeneratedValue", "GenerationType"].iter());
+        }
+        if table
+            .column_arr
+            .iter()
+            .any(|column| is_primary_key(table, column))
+        {
+            jpa_arr.push("Id");
+        }
+        if id_class_name.is_some() {
+            jpa_arr.push("IdClass");
+        }
+        for name in jpa_arr {
+            import_arr.push(format!("{}.{}", setting.persistence_package, name));
+        }
+    }
+    if is_java && setting.need_lombok {
+        import_arr.push("lombok.Data".to_string());
+    }
+    import_arr.sort_unstable();
+    import_arr.dedup();
+
+    let semicolon = if is_java { ";" } else { "" };
+    let mut code = format!("package {}{}\n\n", setting.package_name, semicolon);
+    for import in import_arr.iter() {
+        code += &format!("import {}{}\n", import, semicolon);
+    }
+    if !import_arr.is_empty() {
+        code += "\n";
+    }
+    if let Some(comment) = &table.option.comment {
+        for line in java_doc_arr(comment, "") {
+            code += &format!("{}\n", line);
+        }
+    }
+    if setting.need_jpa {
+        code += "@Entity\n";
+        code += &format!(
+            "@Table(name = {})\n",
+            jvm_quote(&table.table_name, setting.language)
+        );
+        if let Some(id_class_name) = &id_class_name {
+            let class_ref = if is_java { ".class" } else { "::class" };
+            code += &format!("@IdClass({}.{}{})\n", class_name, id_class_name, class_ref);
+        }
+    }
+    let id_class = id_class_name
+        .as_ref()
+        .map(|id_class_name| id_class_code(id_class_name, &key_arr, setting));
+    if is_java {
+        if setting.need_lombok {
+            code += "@Data\n";
+        }
+        code += &format!("public class {} {{\n", class_name);
+        code += &field_arr.join("\n");
+        for accessor in accessor_arr.iter() {
+            code += &format!("\n\n{}", accessor);
+        }
+        if let Some(id_class) = &id_class {
+            code += &format!("\n\n{}", id_class);
+        } else {
+            code += "\n";
+        }
+        code += "}\n";
+    } else {
+        code += &format!("data class {}(\n", class_name);
+        code += &field_arr.join("\n");
+        code += "\n)";
+        if let Some(id_class) = &id_class {
+            code += &format!(" {{\n{}}}", id_class);
+        }
+        code += "\n";
+    }
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql1::parse_create_sql;
+
+    const SQL: &str = r#"CREATE TABLE `course` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '主键',
+  `class` varchar(32) NOT NULL COMMENT '班级',
+  `package` varchar(64) DEFAULT NULL,
+  `object` int unsigned DEFAULT NULL,
+  `price` decimal(10,2) NOT NULL,
+  `started_at` datetime NOT NULL,
+  PRIMARY KEY (`id`)
+) COMMENT='课程'"#;
+
+    #[test]
+    fn test_render_java_entity() {
+        let (_, table) = parse_create_sql(SQL).unwrap();
+        let setting = JvmRenderSetting {
+            need_lombok: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            render_jpa_entity(&table, &setting),
+            r#"package model;
+
+import jakarta.persistence.Column;
+import jakarta.persistence.Entity;
+import jakarta.persistence.GeneratedValue;
+import jakarta.persistence.GenerationType;
+import jakarta.persistence.Id;
+import jakarta.persistence.Table;
+import java.math.BigDecimal;
+import java.math.BigInteger;
+import java.time.LocalDateTime;
+import lombok.Data;
+
+/** 课程 */
+@Entity
+@Table(name = "course")
+@Data
+public class Course {
+    /** 主键 */
+    @Id
+    @GeneratedValue(strategy = GenerationType.IDENTITY)
+    @Column(name = "id", nullable = false)
+    private BigInteger id;
+    /** 班级 */
+    @Column(name = "class", nullable = false, length = 32)
+    private String class_;
+    @Column(name = "package", length = 64)
+    private String package_;
+    @Column(name = "object")
+    private Long object;
+    @Column(name = "price", nullable = false, precision = 10, scale = 2)
+    private BigDecimal price;
+    @Column(name = "started_at", nullable = false)
+    private LocalDateTime startedAt;
+}
+"#
+        );
+
+        let setting = JvmRenderSetting {
+            need_jpa: false,
+            ..Default::default()
+        };
+        let java = render_jpa_entity(&table, &setting);
+        assert!(java.contains("    public String getClass_() {\n        return class_;\n    }"));
+        assert!(java.contains(
+            "    public void setStartedAt(LocalDateTime startedAt) {\n        this.startedAt = startedAt;\n    }\n}\n"
+        ));
+    }
+
+    #[test]
+    fn test_render_kotlin_entity() {
+        let (_, table) = parse_create_sql(SQL).unwrap();
+        let setting = JvmRenderSetting {
+            language: JvmLanguageEnum::Kotlin,
+            ..Default::default()
+        };
+        let kotlin = render_jpa_entity(&table, &setting);
+        assert!(kotlin.starts_with("package model\n\nimport jakarta.persistence.Column\n"));
+        assert!(kotlin.ends_with(
+            r#"data class Course(
+    /** 主键 */
+    @Id
+    @GeneratedValue(strategy = GenerationType.IDENTITY)
+    @Column(name = "id", nullable = false)
+    var id: BigInteger? = null,
+    /** 班级 */
+    @Column(name = "class", nullable = false, length = 32)
+    var `class`: String,
+    @Column(name = "package", length = 64)
+    var `package`: String? = null,
+    @Column(name = "object")
+    var `object`: Long? = null,
+    @Column(name = "price", nullable = false, precision = 10, scale = 2)
+    var price: BigDecimal,
+    @Column(name = "started_at", nullable = false)
+    var startedAt: LocalDateTime,
+)
+"#
+        ));
+    }
+
+    #[test]
+    fn test_render_composite_primary_key() {
+        let sql = r#"CREATE TABLE `user_role` (
+  `user_id` bigint NOT NULL,
+  `role_id` int NOT NULL,
+  `expired_at` datetime DEFAULT NULL,
+  PRIMARY KEY (`user_id`, `role_id`)
+)"#;
+        let (_, table) = parse_create_sql(sql).unwrap();
+        let java = render_jpa_entity(&table, &JvmRenderSetting::default());
+        assert!(java.contains("import jakarta.persistence.IdClass;\n"));
+        assert!(java.contains("import java.io.Serializable;\n"));
+        assert!(java.contains("@IdClass(UserRole.UserRoleId.class)\npublic class UserRole {\n"));
+        assert!(java.contains(
+            r#"    public static class UserRoleId implements Serializable {
+        private Long userId;
+        private Integer roleId;
+"#
+        ));
+        assert!(java.contains(
+            "            return Objects.equals(userId, that.userId) && Objects.equals(roleId, that.roleId);\n"
+        ));
+
+        let setting = JvmRenderSetting {
+            language: JvmLanguageEnum::Kotlin,
+            ..Default::default()
+        };
+        let kotlin = render_jpa_entity(&table, &setting);
+        assert!(kotlin.ends_with(
+            r#"@Entity
+@Table(name = "user_role")
+@IdClass(UserRole.UserRoleId::class)
+data class UserRole(
+    @Id
+    @Column(name = "user_id", nullable = false)
+    var userId: Long,
+    @Id
+    @Column(name = "role_id", nullable = false)
+    var roleId: Int,
+    @Column(name = "expired_at")
+    var expiredAt: LocalDateTime? = null,
+) {
+    data class UserRoleId(
+        var userId: Long? = null,
+        var roleId: Int? = null,
+    ) : Serializable
+}
+"#
+        ));
+    }
+}
@@ -0,0 +1,157 @@
+//! 由解析出的表结构生成 JSON Schema（draft 2020-12）
+//!
+//! 字符串的最大长度、enum 的可选值、是否允许为 NULL 以及注释都来自建表语句
+use serde_json::{json, Map, Value};
+
+use crate::sql1::{DataTypeEnum, IntTypeOption, OneColumn, TableSchema};
+
+/// JSON Schema 的版本
+pub const JSON_SCHEMA_DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// 整数类型的取值范围
+fn int_range(option: &IntTypeOption, bits: u32) -> (i128, i128) {
+    if option.unsigned {
+        (0, (1i128 << bits) - 1)
+    } else {
+        (-(1i128 << (bits - 1)), (1i128 << (bits - 1)) - 1)
+    }
+}
+
+/// 数据库类型对应的 JSON Schema，不包含 NULL
+pub fn json_schema_type(typ: &DataTypeEnum) -> Value {
+    let integer = |(minimum, maximum): (i128, i128)| json!({"type": "integer", "minimum": minimum as i64, "maximum": maximum as u64});
+    let string = |max_len: u64| json!({"type": "string", "maxLength": max_len});
+    match typ {
+        DataTypeEnum::TinyInt(option) => integer(int_range(option, 8)),
+        DataTypeEnum::SmallInt(option) => integer(int_range(option, 16)),
+        DataTypeEnum::MediumInt(option) => integer(int_range(option, 24)),
+        DataTypeEnum::Int(option) => integer(int_range(option, 32)),
+        DataTypeEnum::Bigint(option) => integer(int_range(option, 64)),
+        DataTypeEnum::Year => integer((1901, 2155)),
+        DataTypeEnum::Bool => json!({"type": "boolean"}),
+        DataTypeEnum::Float(option)
+        | DataTypeEnum::Double(option)
+        | DataTypeEnum::Decimal(option) => {
+            if option.unsigned {
+                json!({"type": "number", "minimum": 0})
+            } else {
+                json!({"type": "number"})
+            }
+        }
+        DataTypeEnum::Char(len) | DataTypeEnum::VarChar(len) => string(*len as u64),
+        DataTypeEnum::TinyText => string(255),
+        DataTypeEnum::Text => string(65535),
+        DataTypeEnum::MediumText => string(16_777_215),
+        DataTypeEnum::LongText => string(4_294_967_295),
+        DataTypeEnum::Date => json!({"type": "string", "format": "date"}),
+        DataTypeEnum::Time(_) => json!({"type": "string", "format": "time"}),
+        DataTypeEnum::DateTime(_) | DataTypeEnum::Timestamp(_) | DataTypeEnum::TimestampTz(_) => {
+            json!({"type": "string", "format": "date-time"})
+        }
+        DataTypeEnum::Uuid => json!({"type": "string", "format": "uuid"}),
+        DataTypeEnum::Enum(value_arr) => json!({ "enum": value_arr }),
+        // json 字段可以是任意值
+        DataTypeEnum::Json | DataTypeEnum::Jsonb => json!({}),
+        DataTypeEnum::Bit(_)
+        | DataTypeEnum::Binary(_)
+        | DataTypeEnum::VarBinary(_)
+        | DataTypeEnum::TinyBlob
+        | DataTypeEnum::Blob
+        | DataTypeEnum::MediumBlob
+        | DataTypeEnum::LongBlob => json!({"type": "string", "contentEncoding": "base64"}),
+        DataTypeEnum::Array(item_typ) => {
+            json!({"type": "array", "items": json_schema_type(item_typ)})
+        }
+        _ => json!({"type": "string"}),
+    }
+}
+
+/// 字段的 JSON Schema，允许为 NULL 时在 `type` 或 `enum` 中加上 null
+pub fn json_schema_property(column: &OneColumn) -> Value {
+    let mut property = json_schema_type(&column.typ);
+    if let Value::Object(map) = &mut property {
+        if column.nullable {
+            if let Some(typ) = map.get_mut("type") {
+                *typ = json!([typ.clone(), "null"]);
+            } else if let Some(Value::Array(value_arr)) = map.get_mut("enum") {
+                value_arr.push(Value::Null);
+            }
+        }
+        if !column.comment.is_empty() {
+            map.insert("description".to_string(), json!(column.comment));
+        }
+    }
+    property
+}
+
+/// 根据表结构生成 JSON Schema，不允许为 NULL 的字段是必填的
+pub fn json_schema(table: &TableSchema) -> Value {
+    let mut property_map = Map::new();
+    let mut required_arr: Vec<&str> = vec![];
+    for column in table.column_arr.iter() {
+        property_map.insert(column.name.clone(), json_schema_property(column));
+        if !column.nullable {
+            required_arr.push(&column.name);
+        }
+    }
+    let mut schema = json!({
+        "$schema": JSON_SCHEMA_DRAFT,
+        "title": table.table_name,
+        "type": "object",
+        "properties": property_map,
+        "required": required_arr,
+        "additionalProperties": false,
+    });
+    if let Some(comment) = &table.option.comment {
+        schema["description"] = json!(comment);
+    }
+    schema
+}
+
+/// 生成格式化后的 JSON Schema 文本
+pub fn render_json_schema(table: &TableSchema) -> String {
+    serde_json::to_string_pretty(&json_schema(table)).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql1::parse_create_sql;
+
+    #[test]
+    fn test_json_schema() {
+        let sql = r#"CREATE TABLE `user_info` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '主键',
+  `age` tinyint DEFAULT NULL,
+  `nick` varchar(64) NOT NULL DEFAULT '' COMMENT '昵称',
+  `status` enum('on','off') DEFAULT NULL,
+  `created_at` datetime NOT NULL,
+  PRIMARY KEY (`id`)
+) COMMENT='用户信息'"#;
+        let (_, table) = parse_create_sql(sql).unwrap();
+        assert_eq!(
+            json_schema(&table),
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "title": "user_info",
+                "description": "用户信息",
+                "type": "object",
+                "properties": {
+                    "id": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "maximum": u64::MAX,
+                        "description": "主键",
+                    },
+                    "age": {"type": ["integer", "null"], "minimum": -128, "maximum": 127},
+                    "nick": {"type": "string", "maxLength": 64, "description": "昵称"},
+                    "status": {"enum": ["on", "off", null]},
+                    "created_at": {"type": "string", "format": "date-time"},
+                },
+                "required": ["id", "nick", "created_at"],
+                "additionalProperties": false,
+            })
+        );
+        assert!(render_json_schema(&table).starts_with("{\n  \"$schema\""));
+    }
+}
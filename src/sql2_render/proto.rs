@@ -0,0 +1,248 @@
+//! 由解析出的表结构生成 protobuf 的 message
+//!
+//! 字段编号由调用方保存并在下次生成时传入，已有字段的编号保持不变，新字段使用未用过的编号，
+//! 被删除的字段的编号和名字会被声明为 `reserved`，避免被复用
+use std::collections::BTreeMap;
+
+use super::type_map::{match_builtin_rule, match_type_rule, TypeLangEnum, TypeRule};
+use super::{to_big_case_camel, to_underline};
+use crate::sql1::{DataTypeEnum, TableSchema};
+
+/// protobuf 的渲染设置
+#[derive(Debug, Clone)]
+pub struct ProtoRenderSetting {
+    /// proto 文件的包名
+    pub package_name: String,
+    /// 上一次生成时各字段的编号，键为列名，包括已经删除的字段。为空时按字段顺序从 1 开始编号
+    pub field_number_map: BTreeMap<String, u32>,
+    /// 覆盖默认类型映射的规则，`import` 为类型所在的 proto 文件
+    pub type_rule_arr: Vec<TypeRule>,
+}
+
+impl Default for ProtoRenderSetting {
+    fn default() -> Self {
+        ProtoRenderSetting {
+            package_name: "model".to_string(),
+            field_number_map: BTreeMap::new(),
+            type_rule_arr: vec![],
+        }
+    }
+}
+
+/// 数据库类型对应到 protobuf 中的类型，先按内置规则映射，时间使用 `google.protobuf.Timestamp`，
+/// 定点数使用 `google.type.Decimal`，json 使用 `google.protobuf.Value`
+pub fn proto_type_str(typ: &DataTypeEnum) -> String {
+    if let Some(rule) = match_builtin_rule(TypeLangEnum::Proto, typ) {
+        return rule.target.clone();
+    }
+    match typ {
+        DataTypeEnum::Array(item_typ) => proto_type_str(item_typ),
+        _ => "string".to_string(),
+    }
+}
+
+/// 类型所在的 proto 文件
+fn proto_import(typ: &str) -> Option<&'static str> {
+    match typ {
+        "google.protobuf.Timestamp" => Some("google/protobuf/timestamp.proto"),
+        "google.protobuf.Value" => Some("google/protobuf/struct.proto"),
+        "google.type.Decimal" => Some("google/type/decimal.proto"),
+        _ => None,
+    }
+}
+
+/// 为表中的字段分配编号：已有编号的字段保持不变，新字段从已用过的最大编号之后依次编号
+/// 已删除字段的编号也保留在结果中，以便之后一直声明为 `reserved`，编号不会被重复分配
+/// 返回的结果应当保存下来，作为下次生成时的 `field_number_map`
+pub fn assign_field_number(
+    table: &TableSchema,
+    field_number_map: &BTreeMap<String, u32>,
+) -> BTreeMap<String, u32> {
+    let mut next_number = field_number_map.values().max().map_or(1, |max| max + 1);
+    let mut number_map = field_number_map.clone();
+    for column in table.column_arr.iter() {
+        if !number_map.contains_key(&column.name) {
+            number_map.insert(column.name.clone(), next_number);
+            next_number += 1;
+        }
+    }
+    number_map
+}
+
+/// 注释的每一行输出为一行 `//`
+fn proto_comment_arr(comment: &str, indent: &str) -> Vec<String> {
+    comment
+        .lines()
+        .map(|line| format!("{}// {}", indent, line).trim_end().to_string())
+        .collect()
+}
+
+/// 根据表结构生成 proto 文件，message 名为表名的大驼峰形式，字段名为 snake_case
+/// 允许为 NULL 的标量字段使用 `optional`，以区分 NULL 和零值
+pub fn render_proto_message(table: &TableSchema, setting: &ProtoRenderSetting) -> String {
+    let number_map = assign_field_number(table, &setting.field_number_map);
+    let mut import_arr: Vec<&str> = vec![];
+    let mut field_arr: Vec<String> = vec![];
+    for column in table.column_arr.iter() {
+        let typ = match match_type_rule(&setting.type_rule_arr, column) {
+            Some(rule) => {
+                import_arr.extend(rule.import.as_deref());
+                rule.target.clone()
+            }
+            None => proto_type_str(&column.typ),
+        };
+        import_arr.extend(proto_import(&typ));
+        field_arr.extend(proto_comment_arr(&column.comment, "  "));
+        let label = match &column.typ {
+            DataTypeEnum::Array(_) => "repeated ",
+            // message 类型本身就可以区分是否有值
+            _ if column.nullable && !typ.contains('.') => "optional ",
+            _ => "",
+        };
+        field_arr.push(format!(
+            "  {}{} {} = {};",
+            label,
+            typ,
+            to_underline(&column.name),
+            number_map[&column.name]
+        ));
+    }
+    import_arr.sort_unstable();
+    import_arr.dedup();
+    let removed_arr: Vec<(&String, &u32)> = number_map
+        .iter()
+        .filter(|(name, _)| !table.column_arr.iter().any(|column| &column.name == *name))
+        .collect();
+
+    let mut proto = format!(
+        "syntax = \"proto3\";\n\npackage {};\n\n",
+        setting.package_name
+    );
+    for import in import_arr.iter() {
+        proto += &format!("import \"{}\";\n", import);
+    }
+    if !import_arr.is_empty() {
+        proto += "\n";
+    }
+    if let Some(comment) = &table.option.comment {
+        for line in proto_comment_arr(comment, "") {
+            proto += &format!("{}\n", line);
+        }
+    }
+    proto += &format!("message {} {{\n", to_big_case_camel(&table.table_name));
+    if !removed_arr.is_empty() {
+        let mut number_arr: Vec<u32> = removed_arr.iter().map(|(_, number)| **number).collect();
+        number_arr.sort_unstable();
+        let number_arr: Vec<String> = number_arr.iter().map(|number| number.to_string()).collect();
+        let name_arr: Vec<String> = removed_arr
+            .iter()
+            .map(|(name, _)| format!("\"{}\"", to_underline(name)))
+            .collect();
+        proto += &format!("  reserved {};\n", number_arr.join(", "));
+        proto += &format!("  reserved {};\n\n", name_arr.join(", "));
+    }
+    for line in field_arr {
+        proto += &format!("{}\n", line);
+    }
+    proto += "}\n";
+    proto
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql1::parse_create_sql;
+
+    #[test]
+    fn test_render_proto_message() {
+        let sql = r#"CREATE TABLE `user_info` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '主键',
+  `nick` varchar(64) DEFAULT NULL,
+  `balance` decimal(10,2) NOT NULL COMMENT '余额',
+  `created_at` datetime NOT NULL,
+  PRIMARY KEY (`id`)
+) COMMENT='用户信息'"#;
+        let (_, table) = parse_create_sql(sql).unwrap();
+        let setting = ProtoRenderSetting::default();
+        assert_eq!(
+            render_proto_message(&table, &setting),
+            r#"syntax = "proto3";
+
+package model;
+
+import "google/protobuf/timestamp.proto";
+import "google/type/decimal.proto";
+
+// 用户信息
+message UserInfo {
+  // 主键
+  uint64 id = 1;
+  optional string nick = 2;
+  // 余额
+  google.type.Decimal balance = 3;
+  google.protobuf.Timestamp created_at = 4;
+}
+"#
+        );
+
+        // 删除 nick、新增 avatar 后，已有字段的编号不变
+        let number_map = assign_field_number(&table, &setting.field_number_map);
+        let sql = r#"CREATE TABLE `user_info` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '主键',
+  `avatar` varchar(255) NOT NULL DEFAULT '',
+  `balance` decimal(10,2) NOT NULL COMMENT '余额',
+  `created_at` datetime NOT NULL,
+  PRIMARY KEY (`id`)
+)"#;
+        let (_, table) = parse_create_sql(sql).unwrap();
+        let setting = ProtoRenderSetting {
+            field_number_map: number_map,
+            ..Default::default()
+        };
+        let proto = render_proto_message(&table, &setting);
+        assert!(proto.contains(
+            r#"message UserInfo {
+  reserved 2;
+  reserved "nick";
+
+  // 主键
+  uint64 id = 1;
+  string avatar = 5;
+  // 余额
+  google.type.Decimal balance = 3;
+  google.protobuf.Timestamp created_at = 4;
+}
+"#
+        ));
+
+        // 再次生成时新增 email，nick 仍然是 reserved，编号 2 不会被复用
+        let number_map = assign_field_number(&table, &setting.field_number_map);
+        assert_eq!(number_map.get("nick"), Some(&2));
+        let sql = r#"CREATE TABLE `user_info` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '主键',
+  `avatar` varchar(255) NOT NULL DEFAULT '',
+  `email` varchar(255) NOT NULL DEFAULT '',
+  PRIMARY KEY (`id`)
+)"#;
+        let (_, table) = parse_create_sql(sql).unwrap();
+        let setting = ProtoRenderSetting {
+            field_number_map: number_map,
+            ..Default::default()
+        };
+        let proto = render_proto_message(&table, &setting);
+        assert!(proto.contains(
+            r#"message UserInfo {
+  reserved 2, 3, 4;
+  reserved "balance", "created_at", "nick";
+
+  // 主键
+  uint64 id = 1;
+  string avatar = 5;
+  string email = 6;
+}
+"#
+        ));
+        let number_map = assign_field_number(&table, &setting.field_number_map);
+        assert_eq!(number_map.len(), 6);
+    }
+}
@@ -0,0 +1,321 @@
+//! 由解析出的表结构生成 Python 的 `dataclass` 或 Pydantic 模型
+//!
+//! 字段名为 snake_case，允许为 NULL 的字段为 `Optional[T] = None`；Pydantic 模型会把字符串长度、
+//! 注释等记录在 `Field` 上，字段名与列名不同时使用 `alias`
+use super::type_map::{match_builtin_rule, match_type_rule, TypeLangEnum, TypeRule};
+use super::{to_big_case_camel, to_underline};
+use crate::sql1::{DataTypeEnum, OneColumn, TableSchema};
+
+/// Python 中的关键字，作为字段名时加上 `_` 后缀
+const PYTHON_KEYWORD_ARR: [&str; 35] = [
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// 生成的 Python 模型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PythonModelEnum {
+    /// 标准库的 `@dataclass`
+    Dataclass,
+    /// Pydantic 的 `BaseModel`
+    Pydantic,
+}
+
+/// Python 模型的渲染设置
+#[derive(Debug, Clone)]
+pub struct PythonRenderSetting {
+    /// 生成的模型
+    pub model: PythonModelEnum,
+    /// 覆盖默认类型映射的规则，`import` 形如 `datetime.date`
+    pub type_rule_arr: Vec<TypeRule>,
+}
+
+impl Default for PythonRenderSetting {
+    fn default() -> Self {
+        PythonRenderSetting {
+            model: PythonModelEnum::Dataclass,
+            type_rule_arr: vec![],
+        }
+    }
+}
+
+/// 单引号包裹的字符串字面量
+fn py_quote(value: &str) -> String {
+    let mut quoted = String::from("'");
+    for c in value.chars() {
+        match c {
+            '\'' => quoted += "\\'",
+            '\\' => quoted += "\\\\",
+            '\n' => quoted += "\\n",
+            '\r' => quoted += "\\r",
+            '\t' => quoted += "\\t",
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// 数据库类型对应到 Python 中的类型，以及需要 import 的 `(模块, 名字)`，先按内置规则映射
+pub fn py_type_str(typ: &DataTypeEnum) -> (String, Vec<(&'static str, &'static str)>) {
+    if let Some(rule) = match_builtin_rule(TypeLangEnum::Python, typ) {
+        // 规则中的 import 形如 `datetime.date`
+        let import_arr = rule
+            .import
+            .as_deref()
+            .and_then(|import| import.rsplit_once('.'))
+            .into_iter()
+            .collect();
+        return (rule.target.clone(), import_arr);
+    }
+    match typ {
+        DataTypeEnum::Enum(value_arr) if !value_arr.is_empty() => {
+            let value_arr: Vec<String> = value_arr.iter().map(|value| py_quote(value)).collect();
+            (
+                format!("Literal[{}]", value_arr.join(", ")),
+                vec![("typing", "Literal")],
+            )
+        }
+        DataTypeEnum::Array(item_typ) => {
+            let (item_typ, mut import_arr) = py_type_str(item_typ);
+            import_arr.push(("typing", "List"));
+            (format!("List[{}]", item_typ), import_arr)
+        }
+        _ => ("str".to_string(), vec![]),
+    }
+}
+
+/// 列名对应的字段名，关键字加上 `_` 后缀
+pub fn py_field_name(column_name: &str) -> String {
+    let name = to_underline(column_name);
+    if PYTHON_KEYWORD_ARR.contains(&name.as_str()) {
+        format!("{}_", name)
+    } else {
+        name
+    }
+}
+
+/// 字段是否有默认值：允许为 NULL 的字段默认为 None，自增的字段在插入之前也没有值
+fn has_default(column: &OneColumn) -> bool {
+    column.nullable || column.auto_increment
+}
+
+/// Pydantic 的 `Field(...)` 参数，没有需要记录的内容时返回空
+fn pydantic_field_arg_arr(column: &OneColumn, field_name: &str) -> Vec<String> {
+    let mut arg_arr = vec![];
+    if has_default(column) {
+        arg_arr.push("None".to_string());
+    }
+    if field_name != column.name {
+        arg_arr.push(format!("alias={}", py_quote(&column.name)));
+    }
+    if let DataTypeEnum::Char(len) | DataTypeEnum::VarChar(len) = &column.typ {
+        arg_arr.push(format!("max_length={}", len));
+    }
+    if let DataTypeEnum::Decimal(option) = &column.typ {
+        if let Some(precision) = option.precision {
+            arg_arr.push(format!("max_digits={}", precision));
+        }
+        if let Some(scale) = option.scale {
+            arg_arr.push(format!("decimal_places={}", scale));
+        }
+    }
+    let unsigned = match &column.typ {
+        DataTypeEnum::TinyInt(option)
+        | DataTypeEnum::SmallInt(option)
+        | DataTypeEnum::MediumInt(option)
+        | DataTypeEnum::Int(option)
+        | DataTypeEnum::Bigint(option) => option.unsigned,
+        DataTypeEnum::Float(option) | DataTypeEnum::Double(option) => option.unsigned,
+        _ => false,
+    };
+    if unsigned {
+        arg_arr.push("ge=0".to_string());
+    }
+    if !column.comment.is_empty() {
+        arg_arr.push(format!("description={}", py_quote(&column.comment)));
+    }
+    arg_arr
+}
+
+/// 根据表结构生成 Python 模型，类名为表名的大驼峰形式，表注释输出为类的文档字符串
+/// dataclass 中有默认值的字段必须排在最后，因此没有默认值的字段会被提到前面
+pub fn render_py_model(table: &TableSchema, setting: &PythonRenderSetting) -> String {
+    let mut import_arr: Vec<(&str, &str)> = vec![];
+    let mut column_arr: Vec<&OneColumn> = table.column_arr.iter().collect();
+    match setting.model {
+        PythonModelEnum::Dataclass => {
+            import_arr.push(("dataclasses", "dataclass"));
+            column_arr.sort_by_key(|column| has_default(column));
+        }
+        PythonModelEnum::Pydantic => import_arr.push(("pydantic", "BaseModel")),
+    }
+    let mut line_arr: Vec<String> = vec![];
+    for column in column_arr {
+        let mut typ = match match_type_rule(&setting.type_rule_arr, column) {
+            Some(rule) => {
+                let rule_import = rule.import.as_deref();
+                import_arr.extend(rule_import.and_then(|import| import.rsplit_once('.')));
+                rule.target.clone()
+            }
+            None => {
+                let (typ, typ_import_arr) = py_type_str(&column.typ);
+                import_arr.extend(typ_import_arr);
+                typ
+            }
+        };
+        if has_default(column) {
+            typ = format!("Optional[{}]", typ);
+            import_arr.push(("typing", "Optional"));
+        }
+        let field_name = py_field_name(&column.name);
+        let line = match setting.model {
+            PythonModelEnum::Dataclass => {
+                for comment in column.comment.lines() {
+                    line_arr.push(format!("    #: {}", comment).trim_end().to_string());
+                }
+                if has_default(column) {
+                    format!("    {}: {} = None", field_name, typ)
+                } else {
+                    format!("    {}: {}", field_name, typ)
+                }
+            }
+            PythonModelEnum::Pydantic => {
+                let arg_arr = pydantic_field_arg_arr(column, &field_name);
+                if arg_arr.is_empty() || arg_arr == ["None"] {
+                    let default = if arg_arr.is_empty() { "" } else { " = None" };
+                    format!("    {}: {}{}", field_name, typ, default)
+                } else {
+                    import_arr.push(("pydantic", "Field"));
+                    format!(
+                        "    {}: {} = Field({})",
+                        field_name,
+                        typ,
+                        arg_arr.join(", ")
+                    )
+                }
+            }
+        };
+        line_arr.push(line);
+    }
+    import_arr.sort_unstable();
+    import_arr.dedup();
+
+    let mut py = String::new();
+    let mut module_arr: Vec<&str> = import_arr.iter().map(|(module, _)| *module).collect();
+    module_arr.dedup();
+    for module in module_arr.iter() {
+        let name_arr: Vec<&str> = import_arr
+            .iter()
+            .filter(|(import_module, _)| import_module == module)
+            .map(|(_, name)| *name)
+            .collect();
+        py += &format!("from {} import {}\n", module, name_arr.join(", "));
+    }
+    py += "\n\n";
+    let class_name = to_big_case_camel(&table.table_name);
+    match setting.model {
+        PythonModelEnum::Dataclass => py += &format!("@dataclass\nclass {}:\n", class_name),
+        PythonModelEnum::Pydantic => py += &format!("class {}(BaseModel):\n", class_name),
+    }
+    if let Some(comment) = &table.option.comment {
+        py += &format!(
+            "    \"\"\"{}\"\"\"\n\n",
+            comment.replace("\"\"\"", "\\\"\\\"\\\"")
+        );
+    }
+    if line_arr.is_empty() {
+        line_arr.push("    pass".to_string());
+    }
+    for line in line_arr {
+        py += &format!("{}\n", line);
+    }
+    py
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql1::parse_create_sql;
+
+    const SQL: &str = r#"CREATE TABLE `user_info` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '主键',
+  `nick` varchar(64) DEFAULT NULL,
+  `class` enum('a','b') NOT NULL COMMENT '班级',
+  `balance` decimal(10,2) NOT NULL,
+  `createdAt` datetime NOT NULL,
+  PRIMARY KEY (`id`)
+) COMMENT='用户信息'"#;
+
+    #[test]
+    fn test_render_dataclass() {
+        let (_, table) = parse_create_sql(SQL).unwrap();
+        assert_eq!(
+            render_py_model(&table, &PythonRenderSetting::default()),
+            r#"from dataclasses import dataclass
+from datetime import datetime
+from decimal import Decimal
+from typing import Literal, Optional
+
+
+@dataclass
+class UserInfo:
+    """用户信息"""
+
+    #: 班级
+    class_: Literal['a', 'b']
+    balance: Decimal
+    created_at: datetime
+    #: 主键
+    id: Optional[int] = None
+    nick: Optional[str] = None
+"#
+        );
+    }
+
+    #[test]
+    fn test_render_pydantic() {
+        let (_, table) = parse_create_sql(SQL).unwrap();
+        let setting = PythonRenderSetting {
+            model: PythonModelEnum::Pydantic,
+            ..Default::default()
+        };
+        assert_eq!(
+            render_py_model(&table, &setting),
+            r#"from datetime import datetime
+from decimal import Decimal
+from pydantic import BaseModel, Field
+from typing import Literal, Optional
+
+
+class UserInfo(BaseModel):
+    """用户信息"""
+
+    id: Optional[int] = Field(None, ge=0, description='主键')
+    nick: Optional[str] = Field(None, max_length=64)
+    class_: Literal['a', 'b'] = Field(alias='class', description='班级')
+    balance: Decimal = Field(max_digits=10, decimal_places=2)
+    created_at: datetime = Field(alias='createdAt')
+"#
+        );
+    }
+
+    #[test]
+    fn test_type_rule() {
+        let sql = "CREATE TABLE `t` (`birthday` varchar(10) NOT NULL COMMENT '生日')";
+        let (_, table) = parse_create_sql(sql).unwrap();
+        let setting = PythonRenderSetting {
+            type_rule_arr: vec![TypeRule {
+                comment: Some("生日".to_string()),
+                import: Some("datetime.date".to_string()),
+                ..TypeRule::new("date")
+            }],
+            ..Default::default()
+        };
+        let py = render_py_model(&table, &setting);
+        assert!(py.starts_with("from dataclasses import dataclass\nfrom datetime import date\n"));
+        assert!(py.ends_with("    #: 生日\n    birthday: date\n"));
+    }
+}
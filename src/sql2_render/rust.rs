@@ -0,0 +1,241 @@
+//! 由解析出的表结构生成 Rust 结构体
+//!
+//! 字段名为 snake_case，允许为 NULL 的字段使用 `Option<T>`，时间、定点数、json 分别使用
+//! `chrono`、`rust_decimal`、`serde_json` 中的类型，字段注释输出为文档注释
+use super::type_map::{match_builtin_rule, match_type_rule, TypeLangEnum, TypeRule};
+use super::{to_big_case_camel, to_underline};
+use crate::sql1::{DataTypeEnum, OneColumn, TableSchema};
+
+/// Rust 中的关键字，作为字段名时需要使用 `r#` 前缀
+const RUST_KEYWORD_ARR: [&str; 49] = [
+    "as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn", "for",
+    "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
+    "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where",
+    "while", "async", "await", "dyn", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "typeof", "yield", "try",
+];
+
+/// Rust 结构体的渲染设置
+#[derive(Debug, Clone)]
+pub struct RustRenderSetting {
+    /// 结构体上的 derive，如：`Serialize`、`sqlx::FromRow`、`diesel::Queryable`
+    /// 包含 `Serialize`、`Deserialize` 时会自动 `use serde`，字段名与列名不同时会加上 rename 属性
+    pub derive_arr: Vec<String>,
+    /// 覆盖默认类型映射的规则
+    pub type_rule_arr: Vec<TypeRule>,
+}
+
+impl Default for RustRenderSetting {
+    fn default() -> Self {
+        RustRenderSetting {
+            derive_arr: ["Debug", "Clone", "Serialize", "Deserialize"]
+                .iter()
+                .map(|derive| derive.to_string())
+                .collect(),
+            type_rule_arr: vec![],
+        }
+    }
+}
+
+impl RustRenderSetting {
+    fn has_derive(&self, name_arr: &[&str]) -> bool {
+        self.derive_arr
+            .iter()
+            .any(|derive| name_arr.contains(&derive.as_str()))
+    }
+}
+
+/// 数据库类型对应到 Rust 中的类型，先按内置规则映射，其余的类型以 `String` 表示
+pub fn rust_type_str(typ: &DataTypeEnum) -> String {
+    if let Some(rule) = match_builtin_rule(TypeLangEnum::Rust, typ) {
+        return rule.target.clone();
+    }
+    match typ {
+        DataTypeEnum::Array(item_typ) => format!("Vec<{}>", rust_type_str(item_typ)),
+        _ => "String".to_string(),
+    }
+}
+
+/// 字段在结构体中的类型，允许为 NULL 的字段使用 `Option<T>`
+/// 匹配 `rule_arr` 中的规则时使用规则中的类型代替默认的映射
+pub fn rust_field_type_str(column: &OneColumn, rule_arr: &[TypeRule]) -> String {
+    let typ = match match_type_rule(rule_arr, column) {
+        Some(rule) => rule.target.clone(),
+        None => rust_type_str(&column.typ),
+    };
+    if column.nullable {
+        format!("Option<{}>", typ)
+    } else {
+        typ
+    }
+}
+
+/// 列名对应的字段名，关键字加上 `r#` 前缀，不能作为原始标识符的关键字加上 `_` 后缀
+pub fn rust_field_name(column_name: &str) -> String {
+    let name = to_underline(column_name);
+    match name.as_str() {
+        "self" | "Self" | "crate" | "super" => format!("{}_", name),
+        _ if RUST_KEYWORD_ARR.contains(&name.as_str()) => format!("r#{}", name),
+        _ => name,
+    }
+}
+
+/// 类型中用到的、需要 use 的类型，如：`("chrono", "NaiveDateTime")`
+fn used_type_arr(typ: &str) -> Vec<(&'static str, &'static str)> {
+    let mut used_arr = vec![];
+    for name in ["NaiveDate", "NaiveTime", "NaiveDateTime", "DateTime", "Utc"].iter() {
+        let is_used = typ
+            .split(|c: char| !c.is_alphanumeric() && c != '_' && c != ':')
+            .any(|word| word == *name);
+        if is_used {
+            used_arr.push(("chrono", *name));
+        }
+    }
+    if typ.contains("Decimal") {
+        used_arr.push(("rust_decimal", "Decimal"));
+    }
+    used_arr
+}
+
+/// 文档注释，多行注释的每一行都输出为一行 `///`
+fn doc_comment_arr(comment: &str, indent: &str) -> Vec<String> {
+    comment
+        .lines()
+        .map(|line| line.trim_end())
+        .map(|line| {
+            if line.is_empty() {
+                format!("{}///", indent)
+            } else {
+                format!("{}/// {}", indent, line)
+            }
+        })
+        .collect()
+}
+
+/// 根据表结构生成 Rust 结构体，包括用到的 use 语句以及表名常量 `TABLE_NAME`
+pub fn render_rust_struct(table: &TableSchema, setting: &RustRenderSetting) -> String {
+    let struct_name = to_big_case_camel(&table.table_name);
+    let mut use_arr: Vec<(&str, &str)> = vec![];
+    let mut line_arr: Vec<String> = vec![];
+    for column in table.column_arr.iter() {
+        let typ = rust_field_type_str(column, &setting.type_rule_arr);
+        use_arr.extend(used_type_arr(&typ));
+        // 规则中的 import 形如 `chrono::NaiveDate`
+        let rule_import = match_type_rule(&setting.type_rule_arr, column)
+            .and_then(|rule| rule.import.as_deref())
+            .and_then(|import| import.rsplit_once("::"));
+        use_arr.extend(rule_import);
+        line_arr.extend(doc_comment_arr(&column.comment, "    "));
+        let field_name = rust_field_name(&column.name);
+        if field_name.trim_start_matches("r#") != column.name {
+            if setting.has_derive(&["Serialize", "Deserialize"]) {
+                line_arr.push(format!("    #[serde(rename = \"{}\")]", column.name));
+            }
+            if setting.has_derive(&["FromRow", "sqlx::FromRow"]) {
+                line_arr.push(format!("    #[sqlx(rename = \"{}\")]", column.name));
+            }
+        }
+        line_arr.push(format!("    pub {}: {},", field_name, typ));
+    }
+    for derive in ["Deserialize", "Serialize"].iter() {
+        if setting.derive_arr.iter().any(|name| name == derive) {
+            use_arr.push(("serde", derive));
+        }
+    }
+    use_arr.sort_unstable();
+    use_arr.dedup();
+
+    let mut rust = String::new();
+    let mut crate_arr: Vec<&str> = use_arr.iter().map(|(krate, _)| *krate).collect();
+    crate_arr.dedup();
+    for krate in crate_arr.iter() {
+        let name_arr: Vec<&str> = use_arr
+            .iter()
+            .filter(|(use_crate, _)| use_crate == krate)
+            .map(|(_, name)| *name)
+            .collect();
+        if name_arr.len() == 1 {
+            rust += &format!("use {}::{};\n", krate, name_arr[0]);
+        } else {
+            rust += &format!("use {}::{{{}}};\n", krate, name_arr.join(", "));
+        }
+    }
+    if !crate_arr.is_empty() {
+        rust += "\n";
+    }
+    if let Some(comment) = &table.option.comment {
+        for line in doc_comment_arr(comment, "") {
+            rust += &format!("{}\n", line);
+        }
+    }
+    if !setting.derive_arr.is_empty() {
+        rust += &format!("#[derive({})]\n", setting.derive_arr.join(", "));
+    }
+    rust += &format!("pub struct {} {{\n", struct_name);
+    for line in line_arr {
+        rust += &format!("{}\n", line);
+    }
+    rust += "}\n\n";
+    rust += &format!(
+        "impl {} {{\n    /// 表名\n    pub const TABLE_NAME: &'static str = {:?};\n}}\n",
+        struct_name, table.table_name
+    );
+    rust
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql1::parse_create_sql;
+
+    #[test]
+    fn test_rust_field_name() {
+        assert_eq!(rust_field_name("userName"), "user_name");
+        assert_eq!(rust_field_name("type"), "r#type");
+        assert_eq!(rust_field_name("try"), "r#try");
+        assert_eq!(rust_field_name("self"), "self_");
+    }
+
+    #[test]
+    fn test_render_rust_struct() {
+        let sql = r#"CREATE TABLE `order_item` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '主键',
+  `type` tinyint NOT NULL DEFAULT 0 COMMENT '类型',
+  `price` decimal(10,2) NOT NULL,
+  `extra` json DEFAULT NULL,
+  `createdAt` datetime NOT NULL,
+  `paid_at` timestamp NULL DEFAULT NULL,
+  PRIMARY KEY (`id`)
+) COMMENT='订单明细'"#;
+        let (_, table) = parse_create_sql(sql).unwrap();
+        let mut setting = RustRenderSetting::default();
+        setting.derive_arr.push("sqlx::FromRow".to_string());
+        assert_eq!(
+            render_rust_struct(&table, &setting),
+            r#"use chrono::{DateTime, NaiveDateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// 订单明细
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct OrderItem {
+    /// 主键
+    pub id: u64,
+    /// 类型
+    pub r#type: i8,
+    pub price: Decimal,
+    pub extra: Option<serde_json::Value>,
+    #[serde(rename = "createdAt")]
+    #[sqlx(rename = "createdAt")]
+    pub created_at: NaiveDateTime,
+    pub paid_at: Option<DateTime<Utc>>,
+}
+
+impl OrderItem {
+    /// 表名
+    pub const TABLE_NAME: &'static str = "order_item";
+}
+"#
+        );
+    }
+}
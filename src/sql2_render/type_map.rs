@@ -0,0 +1,984 @@
+//! 可由用户覆盖的类型映射规则
+//!
+//! 每种目标语言都有内置的默认映射，即 `TypeMapConfig::builtin()`，在此之上可以从 TOML 或 JSON 配置中加载规则，
+//! 按数据库类型、是否无符号、是否允许为 NULL、列名的通配符以及注释匹配字段。规则按顺序匹配，第一条匹配的生效，
+//! 用户的规则通过 `merge` 排在内置规则之前：
+//!
+//! ```toml
+//! [[go]]
+//! column = "*_at"
+//! type = "bigint"
+//! target = "int64"
+//!
+//! [[typescript]]
+//! comment = "unix 时间戳"
+//! target = "number"
+//! ```
+//!
+//! `target` 替换的是基础类型，允许为 NULL 的字段仍会按各语言的方式包装，
+//! 如 Go 的 `sql.NullInt64`、Rust 的 `Option<T>`、TypeScript 的 `T | null`
+//!
+//! 各语言生成代码时，字段先按用户的规则匹配，没有匹配时按内置规则映射。
+//! 内置规则不包含无法用规则表示的映射，这些类型由各语言的代码处理：
+//! 数组、TypeScript 和 Python 中 enum 的字面量类型、Go 中按精度区分的 decimal、Zod 中带长度的字符串
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::sql1::ddl::data_type_sql;
+use crate::sql1::{DataTypeEnum, OneColumn};
+
+/// 类型映射的目标语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeLangEnum {
+    /// Go 结构体
+    Go,
+    /// Rust 结构体
+    Rust,
+    /// TypeScript 的 interface
+    TypeScript,
+    /// TypeScript 中生成的 Zod 校验，如：`z.number().int()`
+    Zod,
+    /// protobuf 的 message
+    Proto,
+    /// Java 实体类
+    Java,
+    /// Kotlin 的 data class
+    Kotlin,
+    /// Python 的 dataclass、Pydantic 模型
+    Python,
+}
+
+/// 所有的目标语言
+const TYPE_LANG_ARR: [TypeLangEnum; 8] = [
+    TypeLangEnum::Go,
+    TypeLangEnum::Rust,
+    TypeLangEnum::TypeScript,
+    TypeLangEnum::Zod,
+    TypeLangEnum::Proto,
+    TypeLangEnum::Java,
+    TypeLangEnum::Kotlin,
+    TypeLangEnum::Python,
+];
+
+/// 一条类型映射规则，没有设置的条件不参与匹配，设置了的条件都满足时规则生效
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TypeRule {
+    /// 数据库类型名，不区分大小写，不含长度、精度等参数，如：`bigint`、`varchar`、`decimal`
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub db_type: Option<String>,
+    /// 是否无符号，非数值类型都视为有符号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unsigned: Option<bool>,
+    /// 是否允许为 NULL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
+    /// 列名的通配符，`*` 匹配任意个字符，`?` 匹配一个字符，如：`*_at`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+    /// 字段注释中包含的文本
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// 映射到的类型
+    pub target: String,
+    /// 类型需要导入的包，写法与各语言一致，如 Go 的 `github.com/shopspring/decimal`、
+    /// Java 的 `java.time.Instant`、Rust 的 `chrono::NaiveDate`、Python 的 `datetime.date`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub import: Option<String>,
+}
+
+impl TypeRule {
+    /// 匹配所有字段的规则
+    pub fn new(target: &str) -> Self {
+        TypeRule {
+            target: target.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// 字段是否满足规则的所有条件
+    pub fn is_match(&self, column: &OneColumn) -> bool {
+        if let Some(db_type) = &self.db_type {
+            if !db_type.eq_ignore_ascii_case(&db_type_name(&column.typ)) {
+                return false;
+            }
+        }
+        if let Some(unsigned) = self.unsigned {
+            if unsigned != is_unsigned(&column.typ) {
+                return false;
+            }
+        }
+        if let Some(nullable) = self.nullable {
+            if nullable != column.nullable {
+                return false;
+            }
+        }
+        if let Some(pattern) = &self.column {
+            if !glob_match(pattern, &column.name) {
+                return false;
+            }
+        }
+        if let Some(comment) = &self.comment {
+            if !column.comment.contains(comment.as_str()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// 第一条匹配字段的规则
+pub fn match_type_rule<'a>(rule_arr: &'a [TypeRule], column: &OneColumn) -> Option<&'a TypeRule> {
+    rule_arr.iter().find(|rule| rule.is_match(column))
+}
+
+/// 类型名，即 DDL 写法中参数之前的部分，如：`decimal(10,2) unsigned` -> `decimal`，
+/// 数组为元素的类型名加上 `[]`，如：`varchar(64)[]` -> `varchar[]`
+pub fn db_type_name(typ: &DataTypeEnum) -> String {
+    if let DataTypeEnum::Array(item_typ) = typ {
+        return format!("{}[]", db_type_name(item_typ));
+    }
+    let sql = data_type_sql(typ);
+    match sql.find(['(', ' ']) {
+        Some(end) => sql[..end].to_string(),
+        None => sql,
+    }
+}
+
+/// 数值类型是否为无符号
+fn is_unsigned(typ: &DataTypeEnum) -> bool {
+    match typ {
+        DataTypeEnum::TinyInt(option)
+        | DataTypeEnum::SmallInt(option)
+        | DataTypeEnum::MediumInt(option)
+        | DataTypeEnum::Int(option)
+        | DataTypeEnum::Bigint(option) => option.unsigned,
+        DataTypeEnum::Float(option)
+        | DataTypeEnum::Double(option)
+        | DataTypeEnum::Decimal(option) => option.unsigned,
+        _ => false,
+    }
+}
+
+/// 通配符匹配，`*` 匹配任意个字符，`?` 匹配一个字符
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // 上一个 `*` 的位置，以及它当时匹配到的文本位置
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            // 让 `*` 多匹配一个字符后重试
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// 内置规则：(数据库类型名, 是否无符号, 映射到的类型, 需要导入的包)
+type BuiltinRule = (
+    &'static str,
+    Option<bool>,
+    &'static str,
+    Option<&'static str>,
+);
+
+/// 映射为字符串的类型
+const STRING_TYPE_ARR: [&str; 11] = [
+    "char",
+    "varchar",
+    "tinytext",
+    "text",
+    "mediumtext",
+    "longtext",
+    "bigtext",
+    "enum",
+    "set",
+    "uuid",
+    "time",
+];
+
+/// 二进制类型
+const BINARY_TYPE_ARR: [&str; 7] = [
+    "bit",
+    "binary",
+    "varbinary",
+    "tinyblob",
+    "blob",
+    "mediumblob",
+    "longblob",
+];
+
+/// 空间类型
+const SPATIAL_TYPE_ARR: [&str; 8] = [
+    "geometry",
+    "point",
+    "linestring",
+    "polygon",
+    "multipoint",
+    "multilinestring",
+    "multipolygon",
+    "geometrycollection",
+];
+
+/// Go 的内置规则，decimal 按精度区分，由代码处理。import 由类型推导，
+/// 如 `time.Time` 允许为 NULL 时为 `sql.NullTime`，不再需要 `time`
+const GO_BUILTIN_ARR: [BuiltinRule; 20] = [
+    ("tinyint", Some(false), "int", None),
+    ("tinyint", Some(true), "uint", None),
+    ("smallint", Some(false), "int", None),
+    ("smallint", Some(true), "uint", None),
+    ("mediumint", Some(false), "int", None),
+    ("mediumint", Some(true), "uint", None),
+    ("int", Some(false), "int", None),
+    ("int", Some(true), "uint", None),
+    ("bigint", Some(false), "int64", None),
+    ("bigint", Some(true), "uint64", None),
+    ("year", None, "int", None),
+    ("bool", None, "bool", None),
+    ("float", None, "float32", None),
+    ("double", None, "float64", None),
+    ("date", None, "time.Time", None),
+    ("datetime", None, "time.Time", None),
+    ("timestamp", None, "time.Time", None),
+    ("timestamptz", None, "time.Time", None),
+    ("json", None, "string", None),
+    ("jsonb", None, "string", None),
+];
+
+/// Rust 的内置规则
+const RUST_BUILTIN_ARR: [BuiltinRule; 22] = [
+    ("tinyint", Some(false), "i8", None),
+    ("tinyint", Some(true), "u8", None),
+    ("smallint", Some(false), "i16", None),
+    ("smallint", Some(true), "u16", None),
+    ("mediumint", Some(false), "i32", None),
+    ("mediumint", Some(true), "u32", None),
+    ("int", Some(false), "i32", None),
+    ("int", Some(true), "u32", None),
+    ("bigint", Some(false), "i64", None),
+    ("bigint", Some(true), "u64", None),
+    ("year", None, "u16", None),
+    ("bool", None, "bool", None),
+    ("float", None, "f32", None),
+    ("double", None, "f64", None),
+    ("decimal", None, "Decimal", Some("rust_decimal::Decimal")),
+    ("date", None, "NaiveDate", Some("chrono::NaiveDate")),
+    ("time", None, "NaiveTime", Some("chrono::NaiveTime")),
+    (
+        "datetime",
+        None,
+        "NaiveDateTime",
+        Some("chrono::NaiveDateTime"),
+    ),
+    ("timestamp", None, "DateTime<Utc>", None),
+    ("timestamptz", None, "DateTime<Utc>", None),
+    ("json", None, "serde_json::Value", None),
+    ("jsonb", None, "serde_json::Value", None),
+];
+
+/// TypeScript 的内置规则，enum 映射为字面量的联合类型，由代码处理
+const TS_BUILTIN_ARR: [BuiltinRule; 16] = [
+    ("tinyint", None, "number", None),
+    ("smallint", None, "number", None),
+    ("mediumint", None, "number", None),
+    ("int", None, "number", None),
+    ("float", None, "number", None),
+    ("double", None, "number", None),
+    ("year", None, "number", None),
+    ("bool", None, "boolean", None),
+    ("json", None, "unknown", None),
+    ("jsonb", None, "unknown", None),
+    // bigint、decimal 超出了 number 的精度，和时间一样以字符串表示
+    ("bigint", None, "string", None),
+    ("decimal", None, "string", None),
+    ("date", None, "string", None),
+    ("datetime", None, "string", None),
+    ("timestamp", None, "string", None),
+    ("timestamptz", None, "string", None),
+];
+
+/// Zod 的内置规则，带长度的字符串以及 enum 由代码处理
+const ZOD_BUILTIN_ARR: [BuiltinRule; 22] = [
+    ("tinyint", Some(false), "z.number().int()", None),
+    (
+        "tinyint",
+        Some(true),
+        "z.number().int().nonnegative()",
+        None,
+    ),
+    ("smallint", Some(false), "z.number().int()", None),
+    (
+        "smallint",
+        Some(true),
+        "z.number().int().nonnegative()",
+        None,
+    ),
+    ("mediumint", Some(false), "z.number().int()", None),
+    (
+        "mediumint",
+        Some(true),
+        "z.number().int().nonnegative()",
+        None,
+    ),
+    ("int", Some(false), "z.number().int()", None),
+    ("int", Some(true), "z.number().int().nonnegative()", None),
+    ("year", None, "z.number().int()", None),
+    ("float", Some(false), "z.number()", None),
+    ("float", Some(true), "z.number().nonnegative()", None),
+    ("double", Some(false), "z.number()", None),
+    ("double", Some(true), "z.number().nonnegative()", None),
+    ("bool", None, "z.boolean()", None),
+    ("json", None, "z.unknown()", None),
+    ("jsonb", None, "z.unknown()", None),
+    ("bigint", None, "z.string()", None),
+    ("decimal", None, "z.string()", None),
+    ("date", None, "z.string()", None),
+    ("datetime", None, "z.string()", None),
+    ("timestamp", None, "z.string()", None),
+    ("timestamptz", None, "z.string()", None),
+];
+
+/// protobuf 的内置规则，import 为类型所在的 proto 文件
+const PROTO_BUILTIN_ARR: [BuiltinRule; 21] = [
+    ("tinyint", Some(false), "int32", None),
+    ("tinyint", Some(true), "uint32", None),
+    ("smallint", Some(false), "int32", None),
+    ("smallint", Some(true), "uint32", None),
+    ("mediumint", Some(false), "int32", None),
+    ("mediumint", Some(true), "uint32", None),
+    ("int", Some(false), "int32", None),
+    ("int", Some(true), "uint32", None),
+    ("bigint", Some(false), "int64", None),
+    ("bigint", Some(true), "uint64", None),
+    ("year", None, "int32", None),
+    ("bool", None, "bool", None),
+    ("float", None, "float", None),
+    ("double", None, "double", None),
+    (
+        "decimal",
+        None,
+        "google.type.Decimal",
+        Some("google/type/decimal.proto"),
+    ),
+    (
+        "datetime",
+        None,
+        "google.protobuf.Timestamp",
+        Some("google/protobuf/timestamp.proto"),
+    ),
+    (
+        "timestamp",
+        None,
+        "google.protobuf.Timestamp",
+        Some("google/protobuf/timestamp.proto"),
+    ),
+    (
+        "timestamptz",
+        None,
+        "google.protobuf.Timestamp",
+        Some("google/protobuf/timestamp.proto"),
+    ),
+    (
+        "json",
+        None,
+        "google.protobuf.Value",
+        Some("google/protobuf/struct.proto"),
+    ),
+    (
+        "jsonb",
+        None,
+        "google.protobuf.Value",
+        Some("google/protobuf/struct.proto"),
+    ),
+    ("date", None, "string", None),
+];
+
+/// Java 的内置规则
+const JAVA_BUILTIN_ARR: [BuiltinRule; 20] = [
+    ("tinyint", None, "Integer", None),
+    ("smallint", None, "Integer", None),
+    ("mediumint", None, "Integer", None),
+    ("year", None, "Integer", None),
+    ("int", Some(false), "Integer", None),
+    ("int", Some(true), "Long", None),
+    ("bigint", Some(false), "Long", None),
+    (
+        "bigint",
+        Some(true),
+        "BigInteger",
+        Some("java.math.BigInteger"),
+    ),
+    ("bool", None, "Boolean", None),
+    ("float", None, "Float", None),
+    ("double", None, "Double", None),
+    ("decimal", None, "BigDecimal", Some("java.math.BigDecimal")),
+    ("date", None, "LocalDate", Some("java.time.LocalDate")),
+    ("time", None, "LocalTime", Some("java.time.LocalTime")),
+    (
+        "datetime",
+        None,
+        "LocalDateTime",
+        Some("java.time.LocalDateTime"),
+    ),
+    (
+        "timestamp",
+        None,
+        "LocalDateTime",
+        Some("java.time.LocalDateTime"),
+    ),
+    (
+        "timestamptz",
+        None,
+        "OffsetDateTime",
+        Some("java.time.OffsetDateTime"),
+    ),
+    ("uuid", None, "UUID", Some("java.util.UUID")),
+    ("json", None, "String", None),
+    ("jsonb", None, "String", None),
+];
+
+/// Kotlin 的内置规则
+const KOTLIN_BUILTIN_ARR: [BuiltinRule; 20] = [
+    ("tinyint", None, "Int", None),
+    ("smallint", None, "Int", None),
+    ("mediumint", None, "Int", None),
+    ("year", None, "Int", None),
+    ("int", Some(false), "Int", None),
+    ("int", Some(true), "Long", None),
+    ("bigint", Some(false), "Long", None),
+    (
+        "bigint",
+        Some(true),
+        "BigInteger",
+        Some("java.math.BigInteger"),
+    ),
+    ("bool", None, "Boolean", None),
+    ("float", None, "Float", None),
+    ("double", None, "Double", None),
+    ("decimal", None, "BigDecimal", Some("java.math.BigDecimal")),
+    ("date", None, "LocalDate", Some("java.time.LocalDate")),
+    ("time", None, "LocalTime", Some("java.time.LocalTime")),
+    (
+        "datetime",
+        None,
+        "LocalDateTime",
+        Some("java.time.LocalDateTime"),
+    ),
+    (
+        "timestamp",
+        None,
+        "LocalDateTime",
+        Some("java.time.LocalDateTime"),
+    ),
+    (
+        "timestamptz",
+        None,
+        "OffsetDateTime",
+        Some("java.time.OffsetDateTime"),
+    ),
+    ("uuid", None, "UUID", Some("java.util.UUID")),
+    ("json", None, "String", None),
+    ("jsonb", None, "String", None),
+];
+
+/// Python 的内置规则，import 形如 `datetime.date`，enum 映射为 `Literal`，由代码处理
+const PYTHON_BUILTIN_ARR: [BuiltinRule; 18] = [
+    ("tinyint", None, "int", None),
+    ("smallint", None, "int", None),
+    ("mediumint", None, "int", None),
+    ("int", None, "int", None),
+    ("bigint", None, "int", None),
+    ("year", None, "int", None),
+    ("bool", None, "bool", None),
+    ("float", None, "float", None),
+    ("double", None, "float", None),
+    ("decimal", None, "Decimal", Some("decimal.Decimal")),
+    ("date", None, "date", Some("datetime.date")),
+    ("time", None, "time", Some("datetime.time")),
+    ("datetime", None, "datetime", Some("datetime.datetime")),
+    ("timestamp", None, "datetime", Some("datetime.datetime")),
+    ("timestamptz", None, "datetime", Some("datetime.datetime")),
+    ("uuid", None, "UUID", Some("uuid.UUID")),
+    ("json", None, "Any", Some("typing.Any")),
+    ("jsonb", None, "Any", Some("typing.Any")),
+];
+
+/// 某个语言的内置规则，字符串、二进制以及空间类型按各语言的映射追加在后面
+fn builtin_rule_arr(lang: TypeLangEnum) -> Vec<TypeRule> {
+    let (builtin_arr, string_type, binary_type): (&[BuiltinRule], &str, &str) = match lang {
+        TypeLangEnum::Go => (&GO_BUILTIN_ARR, "string", "[]byte"),
+        TypeLangEnum::Rust => (&RUST_BUILTIN_ARR, "String", "Vec<u8>"),
+        TypeLangEnum::TypeScript => (&TS_BUILTIN_ARR, "string", "string"),
+        TypeLangEnum::Zod => (&ZOD_BUILTIN_ARR, "z.string()", "z.string()"),
+        TypeLangEnum::Proto => (&PROTO_BUILTIN_ARR, "string", "bytes"),
+        TypeLangEnum::Java => (&JAVA_BUILTIN_ARR, "String", "byte[]"),
+        TypeLangEnum::Kotlin => (&KOTLIN_BUILTIN_ARR, "String", "ByteArray"),
+        TypeLangEnum::Python => (&PYTHON_BUILTIN_ARR, "str", "bytes"),
+    };
+    // 由代码处理的字符串类型：enum 的字面量类型，Zod 中带长度的字符串
+    let skip_string_arr: &[&str] = match lang {
+        TypeLangEnum::TypeScript | TypeLangEnum::Python => &["enum"],
+        TypeLangEnum::Zod => &["enum", "char", "varchar"],
+        _ => &[],
+    };
+    // 只有 Go、Rust、protobuf 将空间类型映射为二进制
+    let spatial_type = match lang {
+        TypeLangEnum::Go | TypeLangEnum::Rust | TypeLangEnum::Proto => binary_type,
+        _ => string_type,
+    };
+    let mut rule_arr: Vec<TypeRule> = builtin_arr
+        .iter()
+        .map(|(db_type, unsigned, target, import)| TypeRule {
+            db_type: Some(db_type.to_string()),
+            unsigned: *unsigned,
+            import: import.map(|import| import.to_string()),
+            ..TypeRule::new(target)
+        })
+        .collect();
+    // 已经在内置规则中的类型不再追加，如 Rust 中的 time 为 `NaiveTime`
+    let is_listed = |db_type: &&&str| builtin_arr.iter().any(|(name, ..)| name == *db_type);
+    let type_arr = STRING_TYPE_ARR
+        .iter()
+        .filter(|db_type| !skip_string_arr.contains(db_type) && !is_listed(db_type))
+        .map(|db_type| (db_type, string_type))
+        .chain(BINARY_TYPE_ARR.iter().map(|db_type| (db_type, binary_type)))
+        .chain(
+            SPATIAL_TYPE_ARR
+                .iter()
+                .map(|db_type| (db_type, spatial_type)),
+        );
+    for (db_type, target) in type_arr {
+        rule_arr.push(TypeRule {
+            db_type: Some(db_type.to_string()),
+            ..TypeRule::new(target)
+        });
+    }
+    rule_arr
+}
+
+/// 按某个语言的内置规则映射数据库类型，没有匹配的规则时返回 `None`，由各语言的代码处理
+pub fn match_builtin_rule(lang: TypeLangEnum, typ: &DataTypeEnum) -> Option<&'static TypeRule> {
+    static RULE_ARR_ARR: OnceLock<Vec<Vec<TypeRule>>> = OnceLock::new();
+    let rule_arr_arr = RULE_ARR_ARR.get_or_init(|| {
+        TYPE_LANG_ARR
+            .iter()
+            .map(|lang| builtin_rule_arr(*lang))
+            .collect()
+    });
+    let pos = TYPE_LANG_ARR.iter().position(|item| *item == lang)?;
+    // 内置规则只按类型名以及是否无符号匹配
+    let column = OneColumn {
+        typ: typ.clone(),
+        ..Default::default()
+    };
+    match_type_rule(&rule_arr_arr[pos], &column)
+}
+
+/// 各目标语言的类型映射规则，通常从配置文件中加载，配置中的键即字段名
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TypeMapConfig {
+    /// `[[go]]` 中的规则
+    pub go: Vec<TypeRule>,
+    /// `[[rust]]` 中的规则
+    pub rust: Vec<TypeRule>,
+    /// `[[typescript]]` 中的规则
+    pub typescript: Vec<TypeRule>,
+    /// `[[zod]]` 中的规则
+    pub zod: Vec<TypeRule>,
+    /// `[[proto]]` 中的规则
+    pub proto: Vec<TypeRule>,
+    /// `[[java]]` 中的规则
+    pub java: Vec<TypeRule>,
+    /// `[[kotlin]]` 中的规则
+    pub kotlin: Vec<TypeRule>,
+    /// `[[python]]` 中的规则
+    pub python: Vec<TypeRule>,
+}
+
+impl TypeMapConfig {
+    /// 从 TOML 文本中加载
+    pub fn from_toml_str(input: &str) -> Result<Self, String> {
+        toml::from_str(input).map_err(|err| err.to_string())
+    }
+
+    /// 从 JSON 文本中加载
+    pub fn from_json_str(input: &str) -> Result<Self, String> {
+        serde_json::from_str(input).map_err(|err| err.to_string())
+    }
+
+    /// 从文件中加载，扩展名为 `.toml` 时按 TOML 解析，否则按 JSON 解析
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let input =
+            fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+        let is_toml = path.extension().is_some_and(|ext| ext == "toml");
+        let config = if is_toml {
+            Self::from_toml_str(&input)
+        } else {
+            Self::from_json_str(&input)
+        };
+        config.map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    /// 内置的默认映射，与各语言生成代码时的默认映射一致，可以作为编写配置的参考
+    pub fn builtin() -> Self {
+        let mut config = TypeMapConfig::default();
+        for lang in TYPE_LANG_ARR.iter() {
+            *config.rule_arr_mut(*lang) = builtin_rule_arr(*lang);
+        }
+        config
+    }
+
+    /// 合并两份配置，`self` 中的规则排在 `other` 之前，因此优先生效，
+    /// 如：`user_config.merge(TypeMapConfig::builtin())`
+    pub fn merge(mut self, mut other: TypeMapConfig) -> Self {
+        for lang in TYPE_LANG_ARR.iter() {
+            let other_arr = std::mem::take(other.rule_arr_mut(*lang));
+            self.rule_arr_mut(*lang).extend(other_arr);
+        }
+        self
+    }
+
+    /// 某个语言的规则，没有配置时为空
+    pub fn rule_arr(&self, lang: TypeLangEnum) -> Vec<TypeRule> {
+        let rule_arr = match lang {
+            TypeLangEnum::Go => &self.go,
+            TypeLangEnum::Rust => &self.rust,
+            TypeLangEnum::TypeScript => &self.typescript,
+            TypeLangEnum::Zod => &self.zod,
+            TypeLangEnum::Proto => &self.proto,
+            TypeLangEnum::Java => &self.java,
+            TypeLangEnum::Kotlin => &self.kotlin,
+            TypeLangEnum::Python => &self.python,
+        };
+        rule_arr.clone()
+    }
+
+    fn rule_arr_mut(&mut self, lang: TypeLangEnum) -> &mut Vec<TypeRule> {
+        match lang {
+            TypeLangEnum::Go => &mut self.go,
+            TypeLangEnum::Rust => &mut self.rust,
+            TypeLangEnum::TypeScript => &mut self.typescript,
+            TypeLangEnum::Zod => &mut self.zod,
+            TypeLangEnum::Proto => &mut self.proto,
+            TypeLangEnum::Java => &mut self.java,
+            TypeLangEnum::Kotlin => &mut self.kotlin,
+            TypeLangEnum::Python => &mut self.python,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql1::{parse_create_sql, DecimalTypeOption, IntTypeOption, TableSchema};
+    use crate::sql2_render::go::render_go_struct;
+    use crate::sql2_render::java::{render_jpa_entity, JvmLanguageEnum, JvmRenderSetting};
+    use crate::sql2_render::proto::{render_proto_message, ProtoRenderSetting};
+    use crate::sql2_render::python::{render_py_model, PythonRenderSetting};
+    use crate::sql2_render::rust::{render_rust_struct, RustRenderSetting};
+    use crate::sql2_render::typescript::{render_ts_interface, TsRenderSetting};
+    use crate::sql2_render::RenderSetting;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*_at", "created_at"));
+        assert!(glob_match("*_at", "_at"));
+        assert!(!glob_match("*_at", "created_at_ms"));
+        assert!(glob_match("is_*", "is_vip"));
+        assert!(glob_match("*id*", "user_id_list"));
+        assert!(glob_match("a?c", "abc"));
+        assert!(!glob_match("a?c", "ac"));
+        assert!(glob_match("*", ""));
+    }
+
+    #[test]
+    fn test_type_rule() {
+        let sql = r#"CREATE TABLE `user_info` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT,
+  `created_at` bigint NOT NULL COMMENT '创建时间，unix 时间戳',
+  `deleted_at` datetime DEFAULT NULL,
+  PRIMARY KEY (`id`)
+)"#;
+        let (_, table) = parse_create_sql(sql).unwrap();
+        let toml = r#"
+[[go]]
+column = "*_at"
+type = "BIGINT"
+target = "int64"
+
+[[go]]
+column = "*_at"
+nullable = true
+target = "gorm.DeletedAt"
+import = "gorm.io/gorm"
+
+[[typescript]]
+unsigned = true
+target = "bigint"
+"#;
+        let config = TypeMapConfig::from_toml_str(toml).unwrap();
+        let json = r#"{
+  "go": [
+    {"column": "*_at", "type": "BIGINT", "target": "int64"},
+    {"column": "*_at", "nullable": true, "target": "gorm.DeletedAt", "import": "gorm.io/gorm"}
+  ],
+  "typescript": [{"unsigned": true, "target": "bigint"}]
+}"#;
+        assert_eq!(TypeMapConfig::from_json_str(json).unwrap(), config);
+
+        let go_rule_arr = config.rule_arr(TypeLangEnum::Go);
+        assert_eq!(match_type_rule(&go_rule_arr, &table.column_arr[0]), None);
+        assert_eq!(
+            match_type_rule(&go_rule_arr, &table.column_arr[1]).map(|rule| rule.target.as_str()),
+            Some("int64")
+        );
+        assert_eq!(
+            match_type_rule(&go_rule_arr, &table.column_arr[2]).map(|rule| rule.target.as_str()),
+            Some("gorm.DeletedAt")
+        );
+        let ts_rule_arr = config.rule_arr(TypeLangEnum::TypeScript);
+        assert!(match_type_rule(&ts_rule_arr, &table.column_arr[0]).is_some());
+        assert!(config.rule_arr(TypeLangEnum::Rust).is_empty());
+
+        let rule = TypeRule {
+            comment: Some("unix".to_string()),
+            ..TypeRule::new("number")
+        };
+        assert!(rule.is_match(&table.column_arr[1]));
+        assert!(!rule.is_match(&table.column_arr[2]));
+
+        let err = TypeMapConfig::from_toml_str("[[go]]\ncolumn = \"*_at\"\n").unwrap_err();
+        assert!(err.contains("target"), "{}", err);
+        let err = TypeMapConfig::from_json_str(r#"{"golang": []}"#).unwrap_err();
+        assert!(err.contains("golang"), "{}", err);
+    }
+
+    /// 每种类型各一个字段，允许为 NULL 和不允许为 NULL 的各一个
+    fn all_type_table() -> TableSchema {
+        let int = |unsigned: bool| IntTypeOption {
+            unsigned,
+            ..Default::default()
+        };
+        let decimal = |precision: u32, scale: u32, unsigned: bool| DecimalTypeOption {
+            precision: Some(precision),
+            scale: Some(scale),
+            unsigned,
+            ..Default::default()
+        };
+        let mut typ_arr = vec![];
+        for unsigned in [false, true].iter() {
+            typ_arr.push(DataTypeEnum::TinyInt(int(*unsigned)));
+            typ_arr.push(DataTypeEnum::SmallInt(int(*unsigned)));
+            typ_arr.push(DataTypeEnum::MediumInt(int(*unsigned)));
+            typ_arr.push(DataTypeEnum::Int(int(*unsigned)));
+            typ_arr.push(DataTypeEnum::Bigint(int(*unsigned)));
+            typ_arr.push(DataTypeEnum::Float(decimal(10, 2, *unsigned)));
+            typ_arr.push(DataTypeEnum::Double(decimal(10, 2, *unsigned)));
+            typ_arr.push(DataTypeEnum::Decimal(decimal(10, 2, *unsigned)));
+            typ_arr.push(DataTypeEnum::Decimal(decimal(10, 0, *unsigned)));
+        }
+        typ_arr.extend(vec![
+            DataTypeEnum::Bit(1),
+            DataTypeEnum::Bool,
+            DataTypeEnum::Char(8),
+            DataTypeEnum::VarChar(64),
+            DataTypeEnum::Binary(16),
+            DataTypeEnum::VarBinary(16),
+            DataTypeEnum::TinyText,
+            DataTypeEnum::Text,
+            DataTypeEnum::MediumText,
+            DataTypeEnum::LongText,
+            DataTypeEnum::BigText,
+            DataTypeEnum::TinyBlob,
+            DataTypeEnum::Blob,
+            DataTypeEnum::MediumBlob,
+            DataTypeEnum::LongBlob,
+            DataTypeEnum::Date,
+            DataTypeEnum::Time(0),
+            DataTypeEnum::DateTime(3),
+            DataTypeEnum::Timestamp(0),
+            DataTypeEnum::Year,
+            DataTypeEnum::Json,
+            DataTypeEnum::Enum(vec!["on".to_string(), "off".to_string()]),
+            DataTypeEnum::Set(vec!["a".to_string(), "b".to_string()]),
+            DataTypeEnum::Geometry,
+            DataTypeEnum::Point,
+            DataTypeEnum::LineString,
+            DataTypeEnum::Polygon,
+            DataTypeEnum::MultiPoint,
+            DataTypeEnum::MultiLineString,
+            DataTypeEnum::MultiPolygon,
+            DataTypeEnum::GeometryCollection,
+            DataTypeEnum::Uuid,
+            DataTypeEnum::Jsonb,
+            DataTypeEnum::TimestampTz(0),
+            DataTypeEnum::Array(Box::new(DataTypeEnum::Int(int(false)))),
+            DataTypeEnum::Custom("citext".to_string()),
+        ]);
+        let (_, mut table) = parse_create_sql("CREATE TABLE `all_type` (`id` int)").unwrap();
+        table.column_arr = vec![];
+        for (i, typ) in typ_arr.into_iter().enumerate() {
+            for nullable in [false, true].iter() {
+                table.column_arr.push(OneColumn {
+                    name: format!("c{}_{}", i, nullable),
+                    typ: typ.clone(),
+                    nullable: *nullable,
+                    ..Default::default()
+                });
+            }
+        }
+        table
+    }
+
+    #[test]
+    fn test_builtin_same_as_default() {
+        let table = all_type_table();
+        let builtin = TypeMapConfig::builtin();
+
+        // 每一条内置规则都是某个字段第一条匹配的规则，没有被前面的规则遮住
+        for lang in TYPE_LANG_ARR.iter() {
+            let rule_arr = builtin.rule_arr(*lang);
+            for rule in rule_arr.iter() {
+                let is_used = table
+                    .column_arr
+                    .iter()
+                    .any(|column| match_type_rule(&rule_arr, column) == Some(rule));
+                assert!(is_used, "{:?} {:?}", lang, rule);
+            }
+        }
+
+        let setting = RenderSetting {
+            type_rule_arr: builtin.rule_arr(TypeLangEnum::Go),
+            ..Default::default()
+        };
+        assert_eq!(
+            render_go_struct(&table, &setting).unwrap(),
+            render_go_struct(&table, &RenderSetting::default()).unwrap()
+        );
+        let setting = RustRenderSetting {
+            type_rule_arr: builtin.rule_arr(TypeLangEnum::Rust),
+            ..Default::default()
+        };
+        assert_eq!(
+            render_rust_struct(&table, &setting),
+            render_rust_struct(&table, &RustRenderSetting::default())
+        );
+        let default_setting = TsRenderSetting {
+            need_zod: true,
+            ..Default::default()
+        };
+        let setting = TsRenderSetting {
+            type_rule_arr: builtin.rule_arr(TypeLangEnum::TypeScript),
+            zod_type_rule_arr: builtin.rule_arr(TypeLangEnum::Zod),
+            ..default_setting.clone()
+        };
+        assert_eq!(
+            render_ts_interface(&table, &setting),
+            render_ts_interface(&table, &default_setting)
+        );
+        let setting = ProtoRenderSetting {
+            type_rule_arr: builtin.rule_arr(TypeLangEnum::Proto),
+            ..Default::default()
+        };
+        assert_eq!(
+            render_proto_message(&table, &setting),
+            render_proto_message(&table, &ProtoRenderSetting::default())
+        );
+        for (language, lang) in [
+            (JvmLanguageEnum::Java, TypeLangEnum::Java),
+            (JvmLanguageEnum::Kotlin, TypeLangEnum::Kotlin),
+        ]
+        .iter()
+        {
+            let default_setting = JvmRenderSetting {
+                language: *language,
+                ..Default::default()
+            };
+            let setting = JvmRenderSetting {
+                type_rule_arr: builtin.rule_arr(*lang),
+                ..default_setting.clone()
+            };
+            assert_eq!(
+                render_jpa_entity(&table, &setting),
+                render_jpa_entity(&table, &default_setting)
+            );
+        }
+        let setting = PythonRenderSetting {
+            type_rule_arr: builtin.rule_arr(TypeLangEnum::Python),
+            ..Default::default()
+        };
+        assert_eq!(
+            render_py_model(&table, &setting),
+            render_py_model(&table, &PythonRenderSetting::default())
+        );
+    }
+
+    #[test]
+    fn test_match_builtin_rule() {
+        let varchar_arr = DataTypeEnum::Array(Box::new(DataTypeEnum::VarChar(64)));
+        assert_eq!(db_type_name(&varchar_arr), "varchar[]");
+        assert_eq!(match_builtin_rule(TypeLangEnum::Rust, &varchar_arr), None);
+        assert_eq!(
+            match_builtin_rule(TypeLangEnum::Rust, &DataTypeEnum::VarChar(64))
+                .map(|rule| rule.target.as_str()),
+            Some("String")
+        );
+        let unsigned_int = DataTypeEnum::Int(IntTypeOption {
+            unsigned: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            match_builtin_rule(TypeLangEnum::Java, &unsigned_int).map(|rule| rule.target.as_str()),
+            Some("Long")
+        );
+        // 由代码处理的类型没有内置规则
+        let decimal = DataTypeEnum::Decimal(DecimalTypeOption::default());
+        assert_eq!(match_builtin_rule(TypeLangEnum::Go, &decimal), None);
+        assert_eq!(
+            match_builtin_rule(TypeLangEnum::Zod, &DataTypeEnum::VarChar(64)),
+            None
+        );
+        let enum_typ = DataTypeEnum::Enum(vec!["on".to_string()]);
+        assert_eq!(match_builtin_rule(TypeLangEnum::Python, &enum_typ), None);
+    }
+
+    #[test]
+    fn test_merge() {
+        let config = TypeMapConfig::from_toml_str("[[go]]\ntype = \"bigint\"\ntarget = \"int\"\n")
+            .unwrap()
+            .merge(TypeMapConfig::builtin());
+        let go_rule_arr = config.rule_arr(TypeLangEnum::Go);
+        assert_eq!(
+            go_rule_arr.len(),
+            builtin_rule_arr(TypeLangEnum::Go).len() + 1
+        );
+        let (_, table) = parse_create_sql("CREATE TABLE t (a bigint, b bigint unsigned)").unwrap();
+        let target_arr: Vec<Option<&str>> = table
+            .column_arr
+            .iter()
+            .map(|column| match_type_rule(&go_rule_arr, column).map(|rule| rule.target.as_str()))
+            .collect();
+        assert_eq!(target_arr, vec![Some("int"), Some("int")]);
+        assert_eq!(
+            config.rule_arr(TypeLangEnum::Rust),
+            builtin_rule_arr(TypeLangEnum::Rust)
+        );
+    }
+}
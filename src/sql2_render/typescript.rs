@@ -0,0 +1,258 @@
+//! 由解析出的表结构生成 TypeScript 的 `interface`，以及可选的 Zod 校验
+//!
+//! bigint、decimal 映射为 string，避免 JavaScript 中的 number 丢失精度；允许为 NULL 的字段为 `T | null`
+use super::type_map::{match_builtin_rule, match_type_rule, TypeLangEnum, TypeRule};
+use super::{to_big_case_camel, to_name_style, FieldNameStyleEnum};
+use crate::sql1::{DataTypeEnum, OneColumn, TableSchema};
+
+/// TypeScript 的渲染设置
+#[derive(Debug, Clone)]
+pub struct TsRenderSetting {
+    /// 属性名的风格，为 None 时保持列名不变
+    pub field_name_style: Option<FieldNameStyleEnum>,
+    /// 是否同时生成 Zod 校验，如：`export const UserSchema = z.object({...});`
+    pub need_zod: bool,
+    /// 覆盖默认类型映射的规则，`import` 不会被使用
+    pub type_rule_arr: Vec<TypeRule>,
+    /// 覆盖默认 Zod 校验的规则，如：`z.coerce.date()`
+    pub zod_type_rule_arr: Vec<TypeRule>,
+}
+
+impl Default for TsRenderSetting {
+    fn default() -> Self {
+        TsRenderSetting {
+            field_name_style: Some(FieldNameStyleEnum::SmallCaseCamel),
+            need_zod: false,
+            type_rule_arr: vec![],
+            zod_type_rule_arr: vec![],
+        }
+    }
+}
+
+/// 单引号包裹的字符串字面量
+fn ts_quote(value: &str) -> String {
+    let mut quoted = String::from("'");
+    for c in value.chars() {
+        match c {
+            '\'' => quoted += "\\'",
+            '\\' => quoted += "\\\\",
+            '\n' => quoted += "\\n",
+            '\r' => quoted += "\\r",
+            '\t' => quoted += "\\t",
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// 属性名，不是合法标识符时用引号包裹
+fn ts_property_name(column: &OneColumn, setting: &TsRenderSetting) -> String {
+    let name = match setting.field_name_style {
+        Some(style) => to_name_style(&column.name, style),
+        None => column.name.clone(),
+    };
+    let is_identifier = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        name
+    } else {
+        ts_quote(&name)
+    }
+}
+
+/// 数据库类型对应到 TypeScript 中的类型，先按内置规则映射，enum 映射为字面量的联合类型
+pub fn ts_type_str(typ: &DataTypeEnum) -> String {
+    if let Some(rule) = match_builtin_rule(TypeLangEnum::TypeScript, typ) {
+        return rule.target.clone();
+    }
+    match typ {
+        DataTypeEnum::Enum(value_arr) if !value_arr.is_empty() => {
+            let value_arr: Vec<String> = value_arr.iter().map(|value| ts_quote(value)).collect();
+            value_arr.join(" | ")
+        }
+        DataTypeEnum::Array(item_typ) => {
+            let item_typ = ts_type_str(item_typ);
+            if item_typ.contains(' ') {
+                format!("({})[]", item_typ)
+            } else {
+                format!("{}[]", item_typ)
+            }
+        }
+        _ => "string".to_string(),
+    }
+}
+
+/// 数据库类型对应的 Zod 校验，先按内置规则映射，如：`z.string().max(64)`
+pub fn zod_type_str(typ: &DataTypeEnum) -> String {
+    if let Some(rule) = match_builtin_rule(TypeLangEnum::Zod, typ) {
+        return rule.target.clone();
+    }
+    match typ {
+        DataTypeEnum::Char(len) | DataTypeEnum::VarChar(len) => {
+            format!("z.string().max({})", len)
+        }
+        DataTypeEnum::Enum(value_arr) if !value_arr.is_empty() => {
+            let value_arr: Vec<String> = value_arr.iter().map(|value| ts_quote(value)).collect();
+            format!("z.enum([{}])", value_arr.join(", "))
+        }
+        DataTypeEnum::Array(item_typ) => format!("z.array({})", zod_type_str(item_typ)),
+        _ => "z.string()".to_string(),
+    }
+}
+
+/// JSDoc 注释，多行注释每行前加 ` * `
+fn js_doc_arr(comment: &str, indent: &str) -> Vec<String> {
+    let comment = comment.replace("*/", "*\\/");
+    let line_arr: Vec<&str> = comment.lines().map(|line| line.trim_end()).collect();
+    match line_arr.len() {
+        0 => vec![],
+        1 => vec![format!("{}/** {} */", indent, line_arr[0])],
+        _ => {
+            let mut doc_arr = vec![format!("{}/**", indent)];
+            for line in line_arr {
+                doc_arr.push(format!("{} * {}", indent, line).trim_end().to_string());
+            }
+            doc_arr.push(format!("{} */", indent));
+            doc_arr
+        }
+    }
+}
+
+/// 根据表结构生成 TypeScript 的 `interface`，需要时在之后生成同名加 `Schema` 后缀的 Zod 校验
+pub fn render_ts_interface(table: &TableSchema, setting: &TsRenderSetting) -> String {
+    let interface_name = to_big_case_camel(&table.table_name);
+    let mut ts = String::new();
+    if setting.need_zod {
+        ts += "import { z } from 'zod';\n\n";
+    }
+    if let Some(comment) = &table.option.comment {
+        for line in js_doc_arr(comment, "") {
+            ts += &format!("{}\n", line);
+        }
+    }
+    ts += &format!("export interface {} {{\n", interface_name);
+    for column in table.column_arr.iter() {
+        for line in js_doc_arr(&column.comment, "  ") {
+            ts += &format!("{}\n", line);
+        }
+        let mut typ = match match_type_rule(&setting.type_rule_arr, column) {
+            Some(rule) => rule.target.clone(),
+            None => ts_type_str(&column.typ),
+        };
+        if column.nullable {
+            typ += " | null";
+        }
+        ts += &format!("  {}: {};\n", ts_property_name(column, setting), typ);
+    }
+    ts += "}\n";
+    if setting.need_zod {
+        ts += &format!("\nexport const {}Schema = z.object({{\n", interface_name);
+        for column in table.column_arr.iter() {
+            let mut typ = match match_type_rule(&setting.zod_type_rule_arr, column) {
+                Some(rule) => rule.target.clone(),
+                None => zod_type_str(&column.typ),
+            };
+            if column.nullable {
+                typ += ".nullable()";
+            }
+            ts += &format!("  {}: {},\n", ts_property_name(column, setting), typ);
+        }
+        ts += "});\n";
+    }
+    ts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql1::parse_create_sql;
+
+    #[test]
+    fn test_render_ts_interface() {
+        let sql = r#"CREATE TABLE `user_info` (
+  `id` bigint unsigned NOT NULL AUTO_INCREMENT COMMENT '主键',
+  `user_name` varchar(64) NOT NULL DEFAULT '',
+  `age` tinyint unsigned DEFAULT NULL,
+  `balance` decimal(10,2) NOT NULL COMMENT '余额\n单位：元',
+  `status` enum('on','off') NOT NULL DEFAULT 'on',
+  `is_vip` bool NOT NULL DEFAULT FALSE,
+  `profile` json DEFAULT NULL,
+  PRIMARY KEY (`id`)
+) COMMENT='用户信息'"#;
+        let (_, mut table) = parse_create_sql(sql).unwrap();
+        // 列名不是合法的标识符时属性名需要用引号包裹
+        table.column_arr[5].name = "is-vip".to_string();
+        let setting = TsRenderSetting {
+            need_zod: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            render_ts_interface(&table, &setting),
+            r#"import { z } from 'zod';
+
+/** 用户信息 */
+export interface UserInfo {
+  /** 主键 */
+  id: string;
+  userName: string;
+  age: number | null;
+  /**
+   * 余额
+   * 单位：元
+   */
+  balance: string;
+  status: 'on' | 'off';
+  'is-vip': boolean;
+  profile: unknown | null;
+}
+
+export const UserInfoSchema = z.object({
+  id: z.string(),
+  userName: z.string().max(64),
+  age: z.number().int().nonnegative().nullable(),
+  balance: z.string(),
+  status: z.enum(['on', 'off']),
+  'is-vip': z.boolean(),
+  profile: z.unknown().nullable(),
+});
+"#
+        );
+        let setting = TsRenderSetting {
+            field_name_style: None,
+            need_zod: false,
+            type_rule_arr: vec![],
+            zod_type_rule_arr: vec![],
+        };
+        let ts = render_ts_interface(&table, &setting);
+        assert!(ts.contains("  user_name: string;\n"));
+        assert!(!ts.contains("zod"));
+    }
+
+    #[test]
+    fn test_type_rule() {
+        let sql =
+            "CREATE TABLE `t` (`id` bigint unsigned NOT NULL, `created_at` bigint DEFAULT NULL)";
+        let (_, table) = parse_create_sql(sql).unwrap();
+        let setting = TsRenderSetting {
+            need_zod: true,
+            type_rule_arr: vec![TypeRule {
+                column: Some("*_at".to_string()),
+                ..TypeRule::new("Date")
+            }],
+            zod_type_rule_arr: vec![TypeRule {
+                column: Some("*_at".to_string()),
+                ..TypeRule::new("z.coerce.date()")
+            }],
+            ..Default::default()
+        };
+        let ts = render_ts_interface(&table, &setting);
+        assert!(ts.contains("  id: string;\n  createdAt: Date | null;\n"));
+        assert!(ts.contains("  createdAt: z.coerce.date().nullable(),\n"));
+    }
+}